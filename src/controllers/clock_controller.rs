@@ -0,0 +1,39 @@
+use actix_web::{web, HttpResponse, Responder};
+use crate::data::clock_data::{StepClockRequest, UpdateClockRequest};
+use crate::utils::sim_clock;
+
+/// [GET] /api/clock - Mengambil waktu simulasi, speed, dan status pause.
+pub async fn get_clock() -> impl Responder {
+    HttpResponse::Ok().json(serde_json::json!({
+        "message": "Simulation clock retrieved successfully.",
+        "data": sim_clock::status()
+    }))
+}
+
+/// [PATCH] /api/clock - Mengatur waktu simulasi (tanggal mulai), speed 0.1–100×, dan pause.
+pub async fn update_clock(body: web::Json<UpdateClockRequest>) -> impl Responder {
+    let status = sim_clock::update(body.into_inner());
+    println!("[Clock]: {} at {}x{}", status.time, status.speed, if status.paused { " (paused)" } else { "" });
+    HttpResponse::Ok().json(serde_json::json!({
+        "message": "Simulation clock updated successfully.",
+        "data": status
+    }))
+}
+
+/// [POST] /api/clock/step - Menjalankan sejumlah langkah kalkulasi (default 1), biasanya saat pause.
+/// Pause + step adalah mode deterministik: langkah tidak bergantung pada waktu nyata.
+pub async fn step_clock(body: Option<web::Json<StepClockRequest>>) -> impl Responder {
+    let steps = body.map(|b| b.steps).unwrap_or(1);
+    HttpResponse::Ok().json(serde_json::json!({
+        "message": format!("{} step(s) queued.", steps),
+        "data": sim_clock::step(steps)
+    }))
+}
+
+/// [DELETE] /api/clock - Kembali ke waktu nyata (sekarang, 1×, berjalan).
+pub async fn reset_clock() -> impl Responder {
+    HttpResponse::Ok().json(serde_json::json!({
+        "message": "Simulation clock reset to real time.",
+        "data": sim_clock::reset()
+    }))
+}
@@ -0,0 +1,75 @@
+use actix_web::{web, HttpResponse, Responder};
+use crate::data::depth_data::LoadGridRequest;
+use crate::services::depth_service::Depth;
+use crate::services::registry::SensorHandle;
+use crate::utils::bathymetry::BathymetryGrid;
+use crate::utils::sim_clock;
+use std::sync::Arc;
+
+type DepthHandle = web::Data<SensorHandle<Depth>>;
+
+fn depth_not_found() -> HttpResponse {
+    HttpResponse::NotFound().json(serde_json::json!({ "message": "Depth Data not found" }))
+}
+
+// === GRID HANDLERS ===
+
+/// [GET] /api/depth/grid - Mengambil info grid batimetri yang sedang dipakai.
+pub async fn get_grid(sensor: DepthHandle) -> impl Responder {
+    let guard = sensor.state.read().unwrap();
+    let Some(state) = guard.as_ref() else { return depth_not_found() };
+    match &state.grid {
+        Some(grid) => HttpResponse::Ok().json(serde_json::json!({
+            "message": "Bathymetry grid retrieved successfully.",
+            "data": grid
+        })),
+        None => HttpResponse::NotFound().json(serde_json::json!({ "message": "No bathymetry grid loaded" })),
+    }
+}
+
+/// [POST] /api/depth/grid - Memuat grid batimetri (ESRI ASCII `.asc` atau GeoTIFF `.tif`) dari file.
+pub async fn load_grid(sensor: DepthHandle, body: web::Json<LoadGridRequest>) -> impl Responder {
+    if sensor.state.read().unwrap().is_none() {
+        return depth_not_found();
+    }
+
+    let req = body.into_inner();
+    let path = req.path.clone();
+    let loaded = web::block(move || BathymetryGrid::load(&req.path, req.positive_down)).await;
+    let grid = match loaded {
+        Ok(Ok(grid)) => grid,
+        Ok(Err(e)) => {
+            eprintln!("[Depth Service]: Failed to load grid {}: {}", path, e);
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "message": format!("Failed to load bathymetry grid: {}", e)
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({ "message": e.to_string() }));
+        }
+    };
+
+    let info = grid.info();
+    let mut guard = sensor.state.write().unwrap();
+    let Some(state) = guard.as_mut() else { return depth_not_found() };
+    state.bathymetry = Some(Arc::new(grid));
+    state.grid = Some(info.clone());
+    state.last_update = sim_clock::now();
+    println!("[Depth Service]: Bathymetry grid loaded: {} ({}x{})", info.path, info.ncols, info.nrows);
+
+    HttpResponse::Ok().json(serde_json::json!({
+        "message": "Bathymetry grid loaded successfully.",
+        "data": info
+    }))
+}
+
+/// [DELETE] /api/depth/grid - Melepas grid; kedalaman kembali ke `constant_depth`.
+pub async fn delete_grid(sensor: DepthHandle) -> impl Responder {
+    let mut guard = sensor.state.write().unwrap();
+    let Some(state) = guard.as_mut() else { return depth_not_found() };
+    state.bathymetry = None;
+    state.grid = None;
+    state.last_update = sim_clock::now();
+
+    HttpResponse::Ok().json(serde_json::json!({ "message": "Bathymetry grid removed successfully." }))
+}
@@ -0,0 +1,120 @@
+use actix_web::{web, HttpResponse, Responder};
+use crate::data::fault_data::{CreateFaultRequest, Fault, FaultKind, FaultStatus};
+use crate::services::registry::SensorHandle;
+use crate::services::sensor::Sensor;
+use crate::utils::sim_clock;
+use chrono::Duration;
+
+// Handler generik fault injection; fault hanya mengubah output, bukan state simulasi.
+
+fn validate<S: Sensor>(req: &CreateFaultRequest) -> Result<(), String> {
+    if !req.duration.is_finite() || req.duration <= 0.0 {
+        return Err("Invalid duration: must be a positive number of seconds.".to_string());
+    }
+    if !req.delay.is_finite() || req.delay < 0.0 {
+        return Err("Invalid delay: must be zero or a positive number of seconds.".to_string());
+    }
+    if req.probability.is_some_and(|probability| !(0.0..=1.0).contains(&probability)) {
+        return Err("Invalid probability: must be between 0 and 1.".to_string());
+    }
+    if matches!(req.kind, FaultKind::Spike | FaultKind::Bias) && !req.magnitude.is_some_and(f64::is_finite) {
+        return Err("Spike and bias faults require a finite magnitude.".to_string());
+    }
+    if req.kind.uses_field() {
+        if S::FAULT_FIELDS.is_empty() {
+            return Err(format!("{} has no fields that support value faults.", S::LABEL));
+        }
+        if let Some(field) = req.field.as_deref().filter(|field| !S::FAULT_FIELDS.contains(field)) {
+            return Err(format!("Unknown field \"{}\": expected one of {}.", field, S::FAULT_FIELDS.join(", ")));
+        }
+    } else if req.field.is_some() {
+        return Err("Only freeze, spike, bias, and nan faults take a field.".to_string());
+    }
+    Ok(())
+}
+
+fn status(fault: &Fault) -> FaultStatus {
+    FaultStatus { fault: fault.clone(), active: fault.is_active(sim_clock::now()) }
+}
+
+/// [GET] /api/{sensor}/faults - Mengambil fault terjadwal/aktif dan field yang bisa diganggu.
+pub async fn get_faults<S: Sensor>(sensor: web::Data<SensorHandle<S>>) -> impl Responder {
+    let now = sim_clock::now();
+    let faults: Vec<FaultStatus> = sensor
+        .faults
+        .read()
+        .unwrap()
+        .faults
+        .iter()
+        .filter(|fault| now < fault.end)
+        .map(status)
+        .collect();
+    HttpResponse::Ok().json(serde_json::json!({
+        "message": format!("{} faults retrieved successfully.", S::LABEL),
+        "data": { "time": now, "fields": S::FAULT_FIELDS, "faults": faults }
+    }))
+}
+
+/// [POST] /api/{sensor}/faults - Menjadwalkan fault (langsung aktif jika tanpa `start`/`delay`).
+pub async fn create_fault<S: Sensor>(
+    sensor: web::Data<SensorHandle<S>>,
+    body: web::Json<CreateFaultRequest>,
+) -> impl Responder {
+    let req = body.into_inner();
+    if let Err(e) = validate::<S>(&req) {
+        return HttpResponse::BadRequest().json(serde_json::json!({ "message": e }));
+    }
+
+    // Dihitung sebelum lock diambil: overflow chrono tidak boleh panic (dan meracuni lock jadwal)
+    let offset = |seconds: f64| Duration::try_milliseconds((seconds * 1000.0).round() as i64);
+    let window = offset(req.delay)
+        .and_then(|delay| req.start.unwrap_or_else(sim_clock::now).checked_add_signed(delay))
+        .and_then(|start| Some((start, start.checked_add_signed(offset(req.duration)?)?)));
+    let Some((start, end)) = window else {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "message": "Invalid start, delay, or duration: fault window is out of range."
+        }));
+    };
+    let fault = {
+        let mut schedule = sensor.faults.write().unwrap();
+        schedule.next_id += 1;
+        let fault = Fault {
+            id: schedule.next_id,
+            kind: req.kind,
+            field: req.field,
+            magnitude: req.magnitude,
+            start,
+            end,
+            probability: req.probability,
+        };
+        schedule.faults.push(fault.clone());
+        fault
+    };
+    println!("[{} Service]: Fault #{} {:?} scheduled {} – {}", S::LABEL, fault.id, fault.kind, fault.start, fault.end);
+
+    HttpResponse::Created().json(serde_json::json!({
+        "message": format!("{} fault scheduled successfully.", S::LABEL),
+        "data": status(&fault)
+    }))
+}
+
+/// [DELETE] /api/{sensor}/faults - Menghapus semua fault; output kembali normal.
+pub async fn delete_faults<S: Sensor>(sensor: web::Data<SensorHandle<S>>) -> impl Responder {
+    sensor.faults.write().unwrap().faults.clear();
+    HttpResponse::Ok().json(serde_json::json!({
+        "message": format!("{} faults cleared successfully.", S::LABEL)
+    }))
+}
+
+/// [DELETE] /api/{sensor}/faults/{id} - Menghapus satu fault.
+pub async fn delete_fault<S: Sensor>(sensor: web::Data<SensorHandle<S>>, path: web::Path<u64>) -> impl Responder {
+    let id = path.into_inner();
+    let mut schedule = sensor.faults.write().unwrap();
+    let count = schedule.faults.len();
+    schedule.faults.retain(|fault| fault.id != id);
+    if schedule.faults.len() < count {
+        HttpResponse::Ok().json(serde_json::json!({ "message": format!("{} fault {} deleted successfully.", S::LABEL, id) }))
+    } else {
+        HttpResponse::NotFound().json(serde_json::json!({ "message": format!("{} fault {} not found", S::LABEL, id) }))
+    }
+}
@@ -1,10 +1,12 @@
+pub mod sensor_controller;
 pub mod gps_controller;
-// pub mod anemo_controller;
-// pub mod baro_controller;
-pub mod gyro_controller;
-// pub mod thermal_controller;
-
-use crate::utils::mqtt_manager::MqttCommand;
-use tokio::sync::mpsc;
-
-pub type MqttCommandTx = mpsc::Sender<MqttCommand>;
+pub mod thermal_controller;
+pub mod depth_controller;
+pub mod vessel_controller;
+pub mod route_controller;
+pub mod output_controller;
+pub mod signalk_controller;
+pub mod recording_controller;
+pub mod clock_controller;
+pub mod seed_controller;
+pub mod fault_controller;
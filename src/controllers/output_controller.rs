@@ -0,0 +1,142 @@
+use actix_web::{web, HttpResponse, Responder};
+use crate::data::output_data::{
+    CanOutputConfig, CreateCanOutputRequest, CreateUdpOutputRequest, UdpOutputConfig,
+    UpdateCanOutputRequest, UpdateUdpOutputRequest, DEFAULT_UDP_PORT,
+};
+use crate::utils::can_output::SharedCanOutput;
+use crate::utils::net::StreamFormat;
+use crate::utils::udp_output::SharedUdpOutput;
+
+// === UDP OUTPUT HANDLERS ===
+
+/// [GET] /api/outputs/udp - Mengambil konfigurasi output UDP saat ini.
+pub async fn get_udp(output: web::Data<SharedUdpOutput>) -> impl Responder {
+    match output.config() {
+        Some(config) => HttpResponse::Ok().json(serde_json::json!({
+            "message": "UDP output retrieved successfully.",
+            "data": config
+        })),
+        None => HttpResponse::NotFound().json(serde_json::json!({ "message": "UDP output not configured" })),
+    }
+}
+
+/// [POST] /api/outputs/udp - Membuat (atau menimpa) output UDP.
+pub async fn create_udp(
+    output: web::Data<SharedUdpOutput>,
+    body: web::Json<CreateUdpOutputRequest>,
+) -> impl Responder {
+    let req = body.into_inner();
+    let config = UdpOutputConfig {
+        destination: req.destination,
+        port: req.port.unwrap_or(DEFAULT_UDP_PORT),
+        ttl: req.ttl.unwrap_or(1),
+        format: req.format.unwrap_or(StreamFormat::Nmea),
+        enabled: req.enabled.unwrap_or(true),
+    };
+
+    match output.configure(config.clone()).await {
+        Ok(()) => HttpResponse::Created().json(serde_json::json!({
+            "message": "UDP output created successfully.",
+            "data": config
+        })),
+        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "message": e })),
+    }
+}
+
+/// [PATCH] /api/outputs/udp - Memperbarui sebagian konfigurasi output UDP.
+pub async fn update_udp(
+    output: web::Data<SharedUdpOutput>,
+    body: web::Json<UpdateUdpOutputRequest>,
+) -> impl Responder {
+    let Some(mut config) = output.config() else {
+        return HttpResponse::NotFound().json(serde_json::json!({ "message": "UDP output not configured" }));
+    };
+
+    let patch = body.into_inner();
+    if let Some(destination) = patch.destination { config.destination = destination; }
+    if let Some(port) = patch.port { config.port = port; }
+    if let Some(ttl) = patch.ttl { config.ttl = ttl; }
+    if let Some(format) = patch.format { config.format = format; }
+    if let Some(enabled) = patch.enabled { config.enabled = enabled; }
+
+    match output.configure(config.clone()).await {
+        Ok(()) => HttpResponse::Ok().json(serde_json::json!({
+            "message": "UDP output updated successfully.",
+            "data": config
+        })),
+        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "message": e })),
+    }
+}
+
+/// [DELETE] /api/outputs/udp - Menghentikan dan menghapus output UDP.
+pub async fn delete_udp(output: web::Data<SharedUdpOutput>) -> impl Responder {
+    if output.clear() {
+        HttpResponse::Ok().json(serde_json::json!({ "message": "UDP output deleted successfully." }))
+    } else {
+        HttpResponse::NotFound().json(serde_json::json!({ "message": "UDP output not configured" }))
+    }
+}
+
+// === CAN (NMEA 2000) OUTPUT HANDLERS ===
+
+/// [GET] /api/outputs/can - Mengambil konfigurasi output SocketCAN saat ini.
+pub async fn get_can(output: web::Data<SharedCanOutput>) -> impl Responder {
+    match output.config() {
+        Some(config) => HttpResponse::Ok().json(serde_json::json!({
+            "message": "CAN output retrieved successfully.",
+            "data": config
+        })),
+        None => HttpResponse::NotFound().json(serde_json::json!({ "message": "CAN output not configured" })),
+    }
+}
+
+/// [POST] /api/outputs/can - Membuka interface SocketCAN (mis. vcan0) untuk output NMEA 2000.
+pub async fn create_can(
+    output: web::Data<SharedCanOutput>,
+    body: web::Json<CreateCanOutputRequest>,
+) -> impl Responder {
+    let req = body.into_inner();
+    let config = CanOutputConfig {
+        interface: req.interface,
+        enabled: req.enabled.unwrap_or(true),
+    };
+
+    match output.configure(config.clone()) {
+        Ok(()) => HttpResponse::Created().json(serde_json::json!({
+            "message": "CAN output created successfully.",
+            "data": config
+        })),
+        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "message": e })),
+    }
+}
+
+/// [PATCH] /api/outputs/can - Memperbarui sebagian konfigurasi output SocketCAN.
+pub async fn update_can(
+    output: web::Data<SharedCanOutput>,
+    body: web::Json<UpdateCanOutputRequest>,
+) -> impl Responder {
+    let Some(mut config) = output.config() else {
+        return HttpResponse::NotFound().json(serde_json::json!({ "message": "CAN output not configured" }));
+    };
+
+    let patch = body.into_inner();
+    if let Some(interface) = patch.interface { config.interface = interface; }
+    if let Some(enabled) = patch.enabled { config.enabled = enabled; }
+
+    match output.configure(config.clone()) {
+        Ok(()) => HttpResponse::Ok().json(serde_json::json!({
+            "message": "CAN output updated successfully.",
+            "data": config
+        })),
+        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "message": e })),
+    }
+}
+
+/// [DELETE] /api/outputs/can - Menutup output SocketCAN.
+pub async fn delete_can(output: web::Data<SharedCanOutput>) -> impl Responder {
+    if output.clear() {
+        HttpResponse::Ok().json(serde_json::json!({ "message": "CAN output deleted successfully." }))
+    } else {
+        HttpResponse::NotFound().json(serde_json::json!({ "message": "CAN output not configured" }))
+    }
+}
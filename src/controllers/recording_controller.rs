@@ -0,0 +1,193 @@
+use actix_web::{web, HttpResponse, Responder};
+use crate::data::recording_data::{
+    RecordingFormat, StartNmeaLogRequest, StartRecordingRequest, StartReplayRequest, UpdateReplayRequest,
+};
+use crate::utils::nmea_log::{self, SharedNmeaLogSource};
+use crate::utils::recording::{self, SharedRecorder};
+use crate::utils::replay::SharedReplayer;
+
+fn no_replay() -> HttpResponse {
+    HttpResponse::NotFound().json(serde_json::json!({ "message": "No replay loaded" }))
+}
+
+// === RECORDING HANDLERS ===
+
+/// [GET] /api/recording - Mengambil status rekaman yang sedang berjalan.
+pub async fn get_recording(recorder: web::Data<SharedRecorder>) -> impl Responder {
+    match recorder.status() {
+        Some(status) => HttpResponse::Ok().json(serde_json::json!({
+            "message": "Recording status retrieved successfully.",
+            "data": status
+        })),
+        None => HttpResponse::NotFound().json(serde_json::json!({ "message": "No recording in progress" })),
+    }
+}
+
+/// [POST] /api/recording - Mulai merekam semua pesan yang dipublikasikan (NMEA, JSONL, atau bincode).
+pub async fn start_recording(
+    recorder: web::Data<SharedRecorder>,
+    body: web::Json<StartRecordingRequest>,
+) -> impl Responder {
+    match recorder.start(body.into_inner()) {
+        Ok(status) => HttpResponse::Created().json(serde_json::json!({
+            "message": "Recording started successfully.",
+            "data": status
+        })),
+        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "message": e })),
+    }
+}
+
+/// [DELETE] /api/recording - Menghentikan rekaman dan menutup file.
+pub async fn stop_recording(recorder: web::Data<SharedRecorder>) -> impl Responder {
+    match recorder.stop() {
+        Some(status) => HttpResponse::Ok().json(serde_json::json!({
+            "message": "Recording stopped successfully.",
+            "data": status
+        })),
+        None => HttpResponse::NotFound().json(serde_json::json!({ "message": "No recording in progress" })),
+    }
+}
+
+// === REPLAY HANDLERS ===
+
+/// [GET] /api/replay - Mengambil status replay (posisi, kecepatan, pause/loop).
+pub async fn get_replay(replayer: web::Data<SharedReplayer>) -> impl Responder {
+    match replayer.status() {
+        Some(status) => HttpResponse::Ok().json(serde_json::json!({
+            "message": "Replay status retrieved successfully.",
+            "data": status
+        })),
+        None => no_replay(),
+    }
+}
+
+/// [POST] /api/replay - Memuat file rekaman dan memutarnya ulang ke semua output.
+/// Publikasi live dihentikan sampai replay dihapus.
+pub async fn start_replay(replayer: web::Data<SharedReplayer>, body: web::Json<StartReplayRequest>) -> impl Responder {
+    let req = body.into_inner();
+    let Some(format) = req.format.or_else(|| RecordingFormat::from_path(&req.path)) else {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "message": "Cannot detect recording format from file extension, please set format."
+        }));
+    };
+
+    let path = match recording::recording_path(&req.path) {
+        Ok(path) => path.display().to_string(),
+        Err(e) => return HttpResponse::BadRequest().json(serde_json::json!({ "message": e })),
+    };
+    let loaded = web::block(move || recording::read_recording(&path, format)).await;
+    let messages = match loaded {
+        Ok(Ok(messages)) => messages,
+        Ok(Err(e)) => {
+            eprintln!("[Replay]: Failed to load {}: {}", req.path, e);
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "message": format!("Failed to load recording: {}", e)
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({ "message": e.to_string() }));
+        }
+    };
+
+    HttpResponse::Created().json(serde_json::json!({
+        "message": "Replay started successfully.",
+        "data": replayer.start(req, format, messages)
+    }))
+}
+
+/// [PATCH] /api/replay - Mengatur kecepatan, pause/resume, loop, dan seek (`position` dalam detik).
+pub async fn update_replay(replayer: web::Data<SharedReplayer>, body: web::Json<UpdateReplayRequest>) -> impl Responder {
+    match replayer.update(body.into_inner()) {
+        Some(status) => HttpResponse::Ok().json(serde_json::json!({
+            "message": "Replay updated successfully.",
+            "data": status
+        })),
+        None => no_replay(),
+    }
+}
+
+/// [DELETE] /api/replay - Menghentikan replay; publikasi live berjalan lagi.
+pub async fn stop_replay(replayer: web::Data<SharedReplayer>) -> impl Responder {
+    if replayer.stop() {
+        HttpResponse::Ok().json(serde_json::json!({ "message": "Replay stopped successfully." }))
+    } else {
+        no_replay()
+    }
+}
+
+// === NMEA LOG SOURCE HANDLERS ===
+
+fn no_nmea_log() -> HttpResponse {
+    HttpResponse::NotFound().json(serde_json::json!({ "message": "No NMEA log loaded" }))
+}
+
+/// [GET] /api/nmea-log - Mengambil status pemutaran log NMEA sebagai sumber GPS/gyro.
+pub async fn get_nmea_log(source: web::Data<SharedNmeaLogSource>) -> impl Responder {
+    match source.status() {
+        Some(status) => HttpResponse::Ok().json(serde_json::json!({
+            "message": "NMEA log status retrieved successfully.",
+            "data": status
+        })),
+        None => no_nmea_log(),
+    }
+}
+
+/// [POST] /api/nmea-log - Memuat log NMEA 0183 (RMC/GGA/VTG/HDT/ROT) dan memakainya sebagai
+/// sumber data GPS dan gyro menggantikan model sintetis.
+pub async fn start_nmea_log(source: web::Data<SharedNmeaLogSource>, body: web::Json<StartNmeaLogRequest>) -> impl Responder {
+    let req = body.into_inner();
+    let path = match recording::recording_path(&req.path) {
+        Ok(path) => path.display().to_string(),
+        Err(e) => return HttpResponse::BadRequest().json(serde_json::json!({ "message": e })),
+    };
+    let loaded = web::block(move || nmea_log::read_nmea_log(&path)).await;
+    let sentences = match loaded {
+        Ok(Ok(sentences)) => sentences,
+        Ok(Err(e)) => {
+            eprintln!("[NMEA Log]: Failed to load {}: {}", req.path, e);
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "message": format!("Failed to load NMEA log: {}", e)
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({ "message": e.to_string() }));
+        }
+    };
+
+    match source.start(req, sentences) {
+        Ok(status) => HttpResponse::Created().json(serde_json::json!({
+            "message": "NMEA log playback started successfully.",
+            "data": status
+        })),
+        Err(e) => HttpResponse::Conflict().json(serde_json::json!({ "message": e })),
+    }
+}
+
+/// [PATCH] /api/nmea-log - Mengatur kecepatan, pause/resume, loop, dan seek (`position` dalam detik).
+pub async fn update_nmea_log(source: web::Data<SharedNmeaLogSource>, body: web::Json<UpdateReplayRequest>) -> impl Responder {
+    match source.update(body.into_inner()) {
+        Some(status) => HttpResponse::Ok().json(serde_json::json!({
+            "message": "NMEA log playback updated successfully.",
+            "data": status
+        })),
+        None => no_nmea_log(),
+    }
+}
+
+/// [DELETE] /api/nmea-log - Menghentikan log; GPS dan gyro kembali ke model sintetis.
+pub async fn stop_nmea_log(source: web::Data<SharedNmeaLogSource>) -> impl Responder {
+    if source.stop() {
+        HttpResponse::Ok().json(serde_json::json!({ "message": "NMEA log playback stopped successfully." }))
+    } else {
+        no_nmea_log()
+    }
+}
+
+/// [DELETE] /api/nmea-log/overrides - Menghapus override PATCH GPS/gyro di atas data log.
+pub async fn clear_nmea_log_overrides(source: web::Data<SharedNmeaLogSource>) -> impl Responder {
+    if source.clear_overrides() {
+        HttpResponse::Ok().json(serde_json::json!({ "message": "Overrides cleared successfully." }))
+    } else {
+        no_nmea_log()
+    }
+}
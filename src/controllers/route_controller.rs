@@ -0,0 +1,64 @@
+use actix_web::{web, HttpResponse, Responder};
+use crate::data::route_data::{CreateRouteRequest, ImportRouteQuery};
+use crate::services::registry::SensorHandle;
+use crate::services::route_service::{self, Route};
+use crate::services::sensor::{Sensor, SensorContext};
+use crate::utils::route_import;
+
+type RouteHandle = web::Data<SensorHandle<Route>>;
+
+/// [POST] /api/route/import - Mengimpor rute dari file GPX (`rte`/`trk`), KML (LineString), atau RTZ.
+/// Body berisi isi file; rute yang ada diganti waypoint-nya, jika belum ada rute baru dibuat.
+pub async fn import_route(
+    sensor: RouteHandle,
+    ctx: web::Data<SensorContext>,
+    query: web::Query<ImportRouteQuery>,
+    body: String,
+) -> impl Responder {
+    let query = query.into_inner();
+    let waypoints = match route_import::parse_route(&body, &query) {
+        Ok(waypoints) => waypoints,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "message": format!("Failed to import route: {}", e)
+            }));
+        }
+    };
+
+    let (state, created) = {
+        let mut guard = sensor.state.write().unwrap();
+        let created = guard.is_none();
+        if created && !sensor.config.read().unwrap().is_complete() {
+            return HttpResponse::Conflict().json(serde_json::json!({
+                "message": "Cannot create route simulation: Configuration is incomplete. Please set IP, port, and update_rate."
+            }));
+        }
+
+        let state = guard.get_or_insert_with(|| {
+            Route::create(CreateRouteRequest {
+                waypoints: Vec::new(),
+                arrival_radius: query.arrival_radius,
+                turn_rate: query.turn_rate,
+                looping: query.looping,
+                is_running: query.is_running,
+            })
+        });
+        route_service::replace_waypoints(state, waypoints, query.start_at_first);
+        (state.clone(), created)
+    };
+
+    if query.start_at_first {
+        route_service::reposition_to_start(&ctx, &state.waypoints);
+    }
+    println!("[Route Service]: Imported {} waypoints", state.waypoints.len());
+
+    let body = serde_json::json!({
+        "message": format!("Route imported successfully ({} waypoints).", state.waypoints.len()),
+        "data": state
+    });
+    if created {
+        HttpResponse::Created().json(body)
+    } else {
+        HttpResponse::Ok().json(body)
+    }
+}
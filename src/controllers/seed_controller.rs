@@ -0,0 +1,29 @@
+use actix_web::{web, HttpResponse, Responder};
+use crate::data::seed_data::UpdateSeedRequest;
+use crate::utils::{n2k, sim_rng};
+
+/// [GET] /api/seed - Mengambil master seed dan seed turunan setiap simulator.
+pub async fn get_seed(names: web::Data<Vec<&'static str>>) -> impl Responder {
+    HttpResponse::Ok().json(serde_json::json!({
+        "message": "Random seed retrieved successfully.",
+        "data": sim_rng::status(&names)
+    }))
+}
+
+/// [PATCH] /api/seed - Mengganti master seed (tanpa `seed` = acak); semua stream noise dan counter
+/// SID/sequence NMEA 2000 dimulai ulang.
+pub async fn update_seed(names: web::Data<Vec<&'static str>>, body: Option<web::Json<UpdateSeedRequest>>) -> impl Responder {
+    let seed = match body.and_then(|b| b.into_inner().seed) {
+        Some(seed) => {
+            sim_rng::set_seed(seed);
+            seed
+        }
+        None => sim_rng::randomize(),
+    };
+    n2k::reset_counters();
+    println!("[Seed]: Master seed {}", seed);
+    HttpResponse::Ok().json(serde_json::json!({
+        "message": "Random seed updated successfully.",
+        "data": sim_rng::status(&names)
+    }))
+}
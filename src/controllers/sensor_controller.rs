@@ -0,0 +1,180 @@
+use actix_web::{web, HttpResponse, Responder};
+use crate::data::sensor_data::{SensorConfig, UpdateSensorConfigRequest};
+use crate::services::registry::SensorHandle;
+use crate::services::sensor::{Sensor, SensorContext, SensorOptions};
+use crate::utils::mqtt_manager::MqttCommand;
+use crate::utils::n2k;
+use crate::utils::nmea;
+use crate::utils::sim_rng;
+
+// Handler generik untuk setiap sensor di registry; path `{sensor}` = `Sensor::NAME`.
+
+// === CONFIG HANDLERS ===
+
+/// [GET] /api/{sensor}/config - Mengambil konfigurasi sensor saat ini.
+pub async fn get_config<S: Sensor>(sensor: web::Data<SensorHandle<S>>) -> impl Responder {
+    let guard = sensor.config.read().unwrap();
+    HttpResponse::Ok().json(serde_json::json!({
+        "message": format!("{} Config retrieved successfully.", S::LABEL),
+        "data": &*guard
+    }))
+}
+
+/// [POST] /api/{sensor}/config - Mengisi atau menimpa semua nilai config.
+pub async fn post_config<S: Sensor>(
+    sensor: web::Data<SensorHandle<S>>,
+    body: web::Json<UpdateSensorConfigRequest<S::Options>>,
+) -> impl Responder {
+    let patch = body.into_inner();
+
+    if let Some(talker) = &patch.nmea_talker {
+        if !nmea::is_valid_talker(talker) {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "message": format!("Invalid nmea_talker: must be two uppercase letters or digits, e.g. \"{}\".", S::DEFAULT_TALKER)
+            }));
+        }
+    }
+    if let Err(e) = patch.options.validate() {
+        return HttpResponse::BadRequest().json(serde_json::json!({ "message": e }));
+    }
+
+    let updated = {
+        let mut guard = sensor.config.write().unwrap();
+
+        // Terapkan semua nilai dari request, gunakan nilai lama jika tidak ada yang baru
+        guard.ip = patch.ip.or_else(|| guard.ip.clone());
+        guard.port = patch.port.or(guard.port);
+        guard.username = patch.username.or_else(|| guard.username.clone());
+        guard.password = patch.password.or_else(|| guard.password.clone());
+        guard.update_rate = patch.update_rate.or(guard.update_rate);
+        guard.topics = patch.topics.or_else(|| guard.topics.clone());
+        guard.nmea_talker = patch.nmea_talker.or_else(|| guard.nmea_talker.clone());
+        guard.n2k_source_address = patch.n2k_source_address.or(guard.n2k_source_address);
+        guard.options.merge(patch.options);
+        guard.clone()
+    };
+
+    // Kirim perintah untuk menyambung ulang
+    sensor.mqtt.send_command(MqttCommand::Reconnect).await;
+
+    HttpResponse::Ok().json(serde_json::json!({
+        "message": format!("{} Config updated successfully.", S::LABEL),
+        "data": updated
+    }))
+}
+
+/// [DELETE] /api/{sensor}/config - Mengosongkan (reset) semua nilai config menjadi null.
+pub async fn delete_config<S: Sensor>(sensor: web::Data<SensorHandle<S>>) -> impl Responder {
+    *sensor.config.write().unwrap() = SensorConfig::default(); // Ganti dengan struct default yang semua fieldnya None
+
+    // Kirim perintah untuk menyambung ulang (efektifnya akan memutuskan koneksi)
+    sensor.mqtt.send_command(MqttCommand::Reconnect).await;
+
+    HttpResponse::Ok().json(serde_json::json!({
+        "message": format!("{} Config deleted successfully.", S::LABEL)
+    }))
+}
+
+/// [GET] /api/{sensor}/status - Mengambil status koneksi MQTT sensor dan seed noise-nya.
+pub async fn get_status<S: Sensor>(sensor: web::Data<SensorHandle<S>>) -> impl Responder {
+    HttpResponse::Ok().json(serde_json::json!({
+        "message": format!("{} status retrieved successfully.", S::LABEL),
+        "data": {
+            "mqtt": sensor.mqtt.status(),
+            "seed": sim_rng::seed(),
+            "stream_seed": sim_rng::stream_seed(S::NAME)
+        }
+    }))
+}
+
+// === SENSOR STATE HANDLERS ===
+
+/// [POST] /api/{sensor} - Membuat instance simulasi sensor.
+pub async fn create_sensor<S: Sensor>(
+    sensor: web::Data<SensorHandle<S>>,
+    body: web::Json<S::CreateRequest>,
+) -> impl Responder {
+    // Validasi: Pastikan config sudah diisi sebelum membuat simulasi
+    if !sensor.config.read().unwrap().is_complete() {
+        return HttpResponse::Conflict().json(serde_json::json!({
+            "message": "Cannot create sensor simulation: Configuration is incomplete. Please set IP, port, and update_rate."
+        }));
+    }
+
+    let n2k_source = sensor.config.read().unwrap().n2k_source_address.unwrap_or(S::DEFAULT_N2K_SOURCE);
+
+    let mut data_guard = sensor.state.write().unwrap();
+    if data_guard.is_some() {
+        return HttpResponse::Conflict().json(serde_json::json!({
+            "message": format!("{} instance already exists. Please delete it first.", S::LABEL)
+        }));
+    }
+
+    // Simulasi baru memulai stream noise dan counter NMEA 2000-nya dari awal, agar skenario yang sama dapat diulang
+    sim_rng::reset_stream(S::NAME);
+    n2k::reset_source(n2k_source);
+    let new_state = S::create(body.into_inner());
+    *data_guard = Some(new_state.clone());
+
+    HttpResponse::Created().json(serde_json::json!({
+        "message": format!("{} created successfully.", S::LABEL),
+        "data": new_state
+    }))
+}
+
+/// [GET] /api/{sensor} - Mengambil state simulasi sensor saat ini.
+pub async fn get_sensor<S: Sensor>(sensor: web::Data<SensorHandle<S>>) -> impl Responder {
+    let guard = sensor.state.read().unwrap();
+    match guard.as_ref() {
+        Some(state) => HttpResponse::Ok().json(serde_json::json!({
+            "message": format!("{} retrieved successfully.", S::LABEL),
+            "data": state
+        })),
+        None => HttpResponse::NotFound().json(serde_json::json!({ "message": format!("{} Data not found", S::LABEL) })),
+    }
+}
+
+/// [PATCH] /api/{sensor} - Memperbarui sebagian state simulasi sensor.
+pub async fn update_sensor<S: Sensor>(
+    sensor: web::Data<SensorHandle<S>>,
+    ctx: web::Data<SensorContext>,
+    body: web::Json<S::UpdateRequest>,
+) -> impl Responder {
+    let config_complete = sensor.config.read().unwrap().is_complete();
+
+    let mut data_guard = sensor.state.write().unwrap();
+    let Some(ref mut state) = *data_guard else {
+        return HttpResponse::NotFound().json(serde_json::json!({ "message": format!("{} Data not found to update", S::LABEL) }));
+    };
+
+    let patch = body.into_inner();
+    if let Err(e) = S::check_update(state, &patch, &ctx) {
+        return HttpResponse::Conflict().json(serde_json::json!({ "message": e }));
+    }
+
+    let mut updated = state.clone();
+    S::update(&mut updated, patch);
+
+    // Validasi: Jika mencoba menyalakan simulasi, pastikan config lengkap
+    if !S::is_running(state) && S::is_running(&updated) && !config_complete {
+        return HttpResponse::Conflict().json(serde_json::json!({
+            "message": "Cannot start simulation: Configuration is incomplete."
+        }));
+    }
+
+    *state = updated.clone();
+    HttpResponse::Ok().json(serde_json::json!({
+        "message": format!("{} updated successfully.", S::LABEL),
+        "data": updated
+    }))
+}
+
+/// [DELETE] /api/{sensor} - Menghapus instance simulasi sensor.
+pub async fn delete_sensor<S: Sensor>(sensor: web::Data<SensorHandle<S>>) -> impl Responder {
+    let mut guard = sensor.state.write().unwrap();
+    if guard.take().is_some() {
+        HttpResponse::Ok().json(serde_json::json!({ "message": format!("Success to delete {} live tracking.", S::LABEL) }))
+    } else {
+        HttpResponse::NotFound().json(serde_json::json!({ "message": format!("{} running currently not found", S::LABEL) }))
+    }
+}
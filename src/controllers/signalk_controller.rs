@@ -0,0 +1,24 @@
+use actix_web::{HttpRequest, HttpResponse, Responder};
+use crate::utils::signalk::{SIGNALK_STREAM_PATH, SIGNALK_VERSION};
+
+/// Port server WebSocket (lihat main.rs)
+const WEBSOCKET_PORT: u16 = 8081;
+
+/// [GET] /signalk - Discovery endpoint agar client Signal K menemukan stream WebSocket.
+pub async fn get_discovery(req: HttpRequest) -> impl Responder {
+    let info = req.connection_info();
+    let host = info.host().rsplit_once(':').map(|(h, _)| h).unwrap_or(info.host());
+
+    HttpResponse::Ok().json(serde_json::json!({
+        "endpoints": {
+            "v1": {
+                "version": SIGNALK_VERSION,
+                "signalk-ws": format!("ws://{}:{}{}", host, WEBSOCKET_PORT, SIGNALK_STREAM_PATH)
+            }
+        },
+        "server": {
+            "id": "vessel-nddu",
+            "version": env!("CARGO_PKG_VERSION")
+        }
+    }))
+}
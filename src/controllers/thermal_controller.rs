@@ -0,0 +1,109 @@
+use actix_web::{web, HttpResponse, Responder};
+use crate::data::thermal_data::{CreateThermalChannelRequest, UpdateThermalChannelRequest};
+use crate::services::registry::SensorHandle;
+use crate::services::thermal_service::{self, Thermal};
+use crate::utils::sim_clock;
+
+type ThermalHandle = web::Data<SensorHandle<Thermal>>;
+
+fn thermal_not_found() -> HttpResponse {
+    HttpResponse::NotFound().json(serde_json::json!({ "message": "Thermal Data not found" }))
+}
+
+fn channel_not_found(name: &str) -> HttpResponse {
+    HttpResponse::NotFound().json(serde_json::json!({ "message": format!("Thermal channel '{}' not found", name) }))
+}
+
+// === CHANNEL HANDLERS ===
+
+/// [GET] /api/thermal/channels - Mengambil semua channel suhu.
+pub async fn get_channels(sensor: ThermalHandle) -> impl Responder {
+    let guard = sensor.state.read().unwrap();
+    match guard.as_ref() {
+        Some(state) => HttpResponse::Ok().json(serde_json::json!({
+            "message": "Thermal channels retrieved successfully.",
+            "data": state.channels
+        })),
+        None => thermal_not_found(),
+    }
+}
+
+/// [POST] /api/thermal/channels - Menambah channel baru.
+pub async fn create_channel(sensor: ThermalHandle, body: web::Json<CreateThermalChannelRequest>) -> impl Responder {
+    let req = body.into_inner();
+    if !thermal_service::is_valid_channel_name(&req.name) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "message": "Invalid channel name: use 1-16 letters, digits, '_' or '-'."
+        }));
+    }
+
+    let mut guard = sensor.state.write().unwrap();
+    let Some(state) = guard.as_mut() else { return thermal_not_found() };
+    if state.channel(&req.name).is_some() {
+        return HttpResponse::Conflict().json(serde_json::json!({
+            "message": format!("Thermal channel '{}' already exists.", req.name)
+        }));
+    }
+
+    let channel = thermal_service::new_channel(req);
+    state.channels.push(channel.clone());
+    state.last_update = sim_clock::now();
+
+    HttpResponse::Created().json(serde_json::json!({
+        "message": "Thermal channel created successfully.",
+        "data": channel
+    }))
+}
+
+/// [GET] /api/thermal/channels/{name} - Mengambil satu channel.
+pub async fn get_channel(sensor: ThermalHandle, path: web::Path<String>) -> impl Responder {
+    let name = path.into_inner();
+    let guard = sensor.state.read().unwrap();
+    let Some(state) = guard.as_ref() else { return thermal_not_found() };
+    match state.channel(&name) {
+        Some(channel) => HttpResponse::Ok().json(serde_json::json!({
+            "message": "Thermal channel retrieved successfully.",
+            "data": channel
+        })),
+        None => channel_not_found(&name),
+    }
+}
+
+/// [PATCH] /api/thermal/channels/{name} - Memperbarui setpoint, konstanta waktu, noise, atau drift.
+pub async fn update_channel(
+    sensor: ThermalHandle,
+    path: web::Path<String>,
+    body: web::Json<UpdateThermalChannelRequest>,
+) -> impl Responder {
+    let name = path.into_inner();
+    let mut guard = sensor.state.write().unwrap();
+    let Some(state) = guard.as_mut() else { return thermal_not_found() };
+    let Some(channel) = state.channel_mut(&name) else { return channel_not_found(&name) };
+
+    thermal_service::update_channel(channel, body.into_inner());
+    let updated = channel.clone();
+    state.last_update = sim_clock::now();
+
+    HttpResponse::Ok().json(serde_json::json!({
+        "message": "Thermal channel updated successfully.",
+        "data": updated
+    }))
+}
+
+/// [DELETE] /api/thermal/channels/{name} - Menghapus satu channel.
+pub async fn delete_channel(sensor: ThermalHandle, path: web::Path<String>) -> impl Responder {
+    let name = path.into_inner();
+    let mut guard = sensor.state.write().unwrap();
+    let Some(state) = guard.as_mut() else { return thermal_not_found() };
+
+    let before = state.channels.len();
+    state.channels.retain(|c| c.name != name);
+    if state.channels.len() == before {
+        return channel_not_found(&name);
+    }
+    state.last_update = sim_clock::now();
+
+    HttpResponse::Ok().json(serde_json::json!({
+        "message": format!("Thermal channel '{}' deleted successfully.", name)
+    }))
+}
@@ -0,0 +1,60 @@
+use actix_web::{web, HttpResponse, Responder};
+use crate::data::vessel_data::{CreateVesselRequest, UpdateVesselRequest, VesselState};
+use crate::services::vessel_service;
+use std::sync::RwLock;
+
+type VesselData = web::Data<RwLock<Option<VesselState>>>;
+
+fn vessel_not_found() -> HttpResponse {
+    HttpResponse::NotFound().json(serde_json::json!({ "message": "Vessel Data not found" }))
+}
+
+/// [POST] /api/vessel - Membuat model kapal; gyro, GPS, dan speed log mulai mengikuti model ini.
+pub async fn create_vessel(vessel: VesselData, body: web::Json<CreateVesselRequest>) -> impl Responder {
+    let mut guard = vessel.write().unwrap();
+    if guard.is_some() {
+        return HttpResponse::Conflict().json(serde_json::json!({
+            "message": "Vessel instance already exists. Please delete it first."
+        }));
+    }
+
+    let state = vessel_service::create_vessel(body.into_inner());
+    *guard = Some(state.clone());
+
+    HttpResponse::Created().json(serde_json::json!({
+        "message": "Vessel created successfully.",
+        "data": state
+    }))
+}
+
+/// [GET] /api/vessel - Mengambil state model kapal saat ini.
+pub async fn get_vessel(vessel: VesselData) -> impl Responder {
+    match vessel.read().unwrap().as_ref() {
+        Some(state) => HttpResponse::Ok().json(serde_json::json!({
+            "message": "Vessel retrieved successfully.",
+            "data": state
+        })),
+        None => vessel_not_found(),
+    }
+}
+
+/// [PATCH] /api/vessel - Mengubah kemudi, kecepatan, arus, atau mereposisi kapal.
+pub async fn update_vessel(vessel: VesselData, body: web::Json<UpdateVesselRequest>) -> impl Responder {
+    let mut guard = vessel.write().unwrap();
+    let Some(state) = guard.as_mut() else { return vessel_not_found() };
+
+    vessel_service::update_vessel(state, body.into_inner());
+
+    HttpResponse::Ok().json(serde_json::json!({
+        "message": "Vessel updated successfully.",
+        "data": state.clone()
+    }))
+}
+
+/// [DELETE] /api/vessel - Menghapus model kapal; sensor kembali berjalan mandiri.
+pub async fn delete_vessel(vessel: VesselData) -> impl Responder {
+    if vessel.write().unwrap().take().is_none() {
+        return vessel_not_found();
+    }
+    HttpResponse::Ok().json(serde_json::json!({ "message": "Vessel deleted successfully." }))
+}
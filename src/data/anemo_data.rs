@@ -0,0 +1,75 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct AnemoState {
+    /// Angin sebenarnya rata-rata: kecepatan (knot) dan arah asal angin (derajat true)
+    pub true_wind_speed: f64,
+    pub true_wind_direction: f64,
+    /// Standar deviasi hembusan (knot) dan pergeseran arah angin (derajat)
+    pub gust_amplitude: f64,
+    pub direction_shift: f64,
+    /// Angin sebenarnya sesaat setelah hembusan dan pergeseran arah
+    pub wind_speed: f64,
+    pub wind_direction: f64,
+    /// Sudut angin sebenarnya relatif terhadap haluan (0..360, searah jarum jam)
+    pub true_wind_angle: f64,
+    /// Angin semu yang dirasakan kapal (dari SOG/COG GPS dan heading gyro)
+    pub apparent_wind_speed: f64,
+    pub apparent_wind_angle: f64,
+    pub is_running: bool,
+    pub last_update: DateTime<Utc>,
+    #[serde(skip)]
+    pub calculation_rate_ms: u64,
+    /// Offset hembusan/pergeseran saat ini (proses Ornstein-Uhlenbeck)
+    #[serde(skip)]
+    pub gust_offset: f64,
+    #[serde(skip)]
+    pub shift_offset: f64,
+}
+
+/// Opsi khusus anemometer di dalam `SensorConfig` (field None = pakai default).
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct AnemoOptions {
+    pub nmea_sentences: Option<Vec<AnemoSentence>>,
+}
+
+/// Jenis kalimat NMEA 0183 yang bisa dihasilkan dari AnemoState.
+/// MWV dikirim dua kali: relatif (R) dan true (T).
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum AnemoSentence {
+    Mwv,
+    Mwd,
+    Vwr,
+}
+
+impl AnemoSentence {
+    /// Urutan default jika `nmea_sentences` belum diatur.
+    pub const ALL: [AnemoSentence; 3] = [
+        AnemoSentence::Mwv,
+        AnemoSentence::Mwd,
+        AnemoSentence::Vwr,
+    ];
+}
+
+// Struct Request API.
+#[derive(Deserialize, Debug)]
+pub struct CreateAnemoRequest {
+    pub true_wind_speed: f64,
+    pub true_wind_direction: f64,
+    #[serde(default)]
+    pub gust_amplitude: f64,
+    #[serde(default)]
+    pub direction_shift: f64,
+    pub is_running: bool,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct UpdateAnemoRequest {
+    pub true_wind_speed: Option<f64>,
+    pub true_wind_direction: Option<f64>,
+    pub gust_amplitude: Option<f64>,
+    pub direction_shift: Option<f64>,
+    pub is_running: Option<bool>,
+}
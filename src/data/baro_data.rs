@@ -0,0 +1,95 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct BaroState {
+    /// Nilai dasar sebelum tren, variasi harian, dan noise (hPa, °C, %)
+    pub base_pressure: f64,
+    pub base_temperature: f64,
+    pub base_humidity: f64,
+    pub trend: PressureTrend,
+    /// Aktifkan variasi harian (pasang atmosfer semi-diurnal, suhu & kelembapan harian)
+    pub diurnal: bool,
+    /// Standar deviasi noise tekanan (hPa); suhu dan kelembapan memakai skala yang sama
+    pub noise: f64,
+    /// Nilai hasil simulasi
+    pub pressure: f64,
+    pub air_temperature: f64,
+    pub relative_humidity: f64,
+    pub dew_point: f64,
+    /// Perubahan tekanan 3 jam terakhir (hPa), positif = naik
+    pub pressure_tendency: f64,
+    /// Waktu sejak tren dimulai (jam)
+    pub elapsed_hours: f64,
+    pub is_running: bool,
+    pub last_update: DateTime<Utc>,
+    #[serde(skip)]
+    pub calculation_rate_ms: u64,
+}
+
+/// Tren tekanan sinoptik.
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PressureTrend {
+    #[default]
+    Steady,
+    /// Perubahan linear, hPa per 3 jam
+    Linear { rate: f64 },
+    /// Sistem tekanan rendah yang lewat: tekanan turun sebesar `depth` hPa di tengah `duration_hours`,
+    /// lalu naik kembali; setelah front lewat udara lebih dingin dan kering
+    PassingLow { depth: f64, duration_hours: f64 },
+}
+
+/// Opsi khusus barometer di dalam `SensorConfig` (field None = pakai default).
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct BaroOptions {
+    pub nmea_sentences: Option<Vec<BaroSentence>>,
+}
+
+/// Jenis kalimat NMEA 0183 yang bisa dihasilkan dari BaroState.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum BaroSentence {
+    Xdr,
+    Mda,
+}
+
+impl BaroSentence {
+    /// Urutan default jika `nmea_sentences` belum diatur.
+    pub const ALL: [BaroSentence; 2] = [BaroSentence::Xdr, BaroSentence::Mda];
+}
+
+fn default_diurnal() -> bool {
+    true
+}
+
+fn default_noise() -> f64 {
+    0.05
+}
+
+// Struct Request API.
+#[derive(Deserialize, Debug)]
+pub struct CreateBaroRequest {
+    pub base_pressure: f64,
+    pub base_temperature: f64,
+    pub base_humidity: f64,
+    #[serde(default)]
+    pub trend: PressureTrend,
+    #[serde(default = "default_diurnal")]
+    pub diurnal: bool,
+    #[serde(default = "default_noise")]
+    pub noise: f64,
+    pub is_running: bool,
+}
+
+/// Mengganti `trend` akan memulai tren dari awal (`elapsed_hours` = 0).
+#[derive(Deserialize, Debug, Default)]
+pub struct UpdateBaroRequest {
+    pub base_pressure: Option<f64>,
+    pub base_temperature: Option<f64>,
+    pub base_humidity: Option<f64>,
+    pub trend: Option<PressureTrend>,
+    pub diurnal: Option<bool>,
+    pub noise: Option<f64>,
+    pub is_running: Option<bool>,
+}
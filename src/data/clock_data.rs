@@ -0,0 +1,36 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Debug)]
+pub struct ClockStatus {
+    /// Waktu simulasi saat ini (UTC)
+    pub time: DateTime<Utc>,
+    /// Kelipatan waktu nyata (0.1–100)
+    pub speed: f64,
+    pub paused: bool,
+    /// Panjang satu langkah kalkulasi (ms waktu simulasi)
+    pub step_ms: u64,
+    /// Langkah dari `POST /api/clock/step` yang belum dijalankan
+    pub pending_steps: u32,
+    /// Total waktu simulasi yang dilewati (ms) karena thread kalkulasi tidak bisa mengikuti speed
+    pub lag_ms: u64,
+}
+
+/// Request `PATCH /api/clock` (field None = nilai lama).
+#[derive(Deserialize, Debug, Default)]
+pub struct UpdateClockRequest {
+    /// Memindahkan waktu simulasi, mis. ke tanggal mulai skenario
+    pub time: Option<DateTime<Utc>>,
+    pub speed: Option<f64>,
+    pub paused: Option<bool>,
+}
+
+fn default_steps() -> u32 {
+    1
+}
+
+#[derive(Deserialize, Debug)]
+pub struct StepClockRequest {
+    #[serde(default = "default_steps")]
+    pub steps: u32,
+}
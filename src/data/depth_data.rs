@@ -0,0 +1,119 @@
+use crate::utils::bathymetry::{BathymetryGrid, GridInfo};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct DepthState {
+    /// Kedalaman air (m dari permukaan) jika grid belum dimuat atau posisi di luar grid
+    pub constant_depth: f64,
+    /// Kedalaman transduser di bawah garis air (m)
+    pub transducer_offset: f64,
+    /// Jarak transduser ke lunas (m), positif = lunas lebih dalam dari transduser
+    pub keel_offset: f64,
+    /// Standar deviasi noise pengukuran (m)
+    pub noise: f64,
+    /// Jangkauan maksimum sounder (m di bawah transduser); lebih dalam = tidak ada echo
+    pub max_range: f64,
+    pub echo_mode: EchoMode,
+    /// Kedalaman air sebenarnya di posisi kapal (m dari permukaan)
+    pub water_depth: f64,
+    pub depth_source: DepthSource,
+    /// Nilai terukur (m); None = tidak ada echo dasar laut
+    pub depth_below_transducer: Option<f64>,
+    pub depth_below_surface: Option<f64>,
+    pub depth_below_keel: Option<f64>,
+    /// true jika sampel terakhir adalah echo palsu
+    pub false_echo: bool,
+    /// Grid batimetri yang sedang dipakai
+    pub grid: Option<GridInfo>,
+    pub is_running: bool,
+    pub last_update: DateTime<Utc>,
+    #[serde(skip)]
+    pub bathymetry: Option<Arc<BathymetryGrid>>,
+}
+
+/// Asal nilai `water_depth`.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DepthSource {
+    Grid,
+    Constant,
+}
+
+/// Mode gangguan echo per sampel.
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EchoMode {
+    #[default]
+    Normal,
+    /// Echo hilang (aerasi, kecepatan tinggi) dengan peluang `probability` per sampel
+    Dropout { probability: f64 },
+    /// Echo palsu yang lebih dangkal (ikan, termoklin) dengan peluang `probability` per sampel
+    FalseEcho { probability: f64 },
+}
+
+/// Opsi khusus depth sounder di dalam `SensorConfig` (field None = pakai default).
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct DepthOptions {
+    pub nmea_sentences: Option<Vec<DepthSentence>>,
+}
+
+/// Jenis kalimat NMEA 0183 yang bisa dihasilkan dari DepthState.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum DepthSentence {
+    Dbt,
+    Dpt,
+    Dbs,
+}
+
+impl DepthSentence {
+    /// Urutan default jika `nmea_sentences` belum diatur.
+    pub const ALL: [DepthSentence; 3] = [DepthSentence::Dbt, DepthSentence::Dpt, DepthSentence::Dbs];
+}
+
+fn default_noise() -> f64 {
+    0.05
+}
+
+fn default_max_range() -> f64 {
+    200.0
+}
+
+// Struct Request API.
+#[derive(Deserialize, Debug)]
+pub struct CreateDepthRequest {
+    pub constant_depth: f64,
+    #[serde(default)]
+    pub transducer_offset: f64,
+    #[serde(default)]
+    pub keel_offset: f64,
+    #[serde(default = "default_noise")]
+    pub noise: f64,
+    #[serde(default = "default_max_range")]
+    pub max_range: f64,
+    #[serde(default)]
+    pub echo_mode: EchoMode,
+    pub is_running: bool,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct UpdateDepthRequest {
+    pub constant_depth: Option<f64>,
+    pub transducer_offset: Option<f64>,
+    pub keel_offset: Option<f64>,
+    pub noise: Option<f64>,
+    pub max_range: Option<f64>,
+    pub echo_mode: Option<EchoMode>,
+    pub is_running: Option<bool>,
+}
+
+/// Memuat grid batimetri dari file di mesin simulator.
+#[derive(Deserialize, Debug)]
+pub struct LoadGridRequest {
+    pub path: String,
+    /// true jika nilai grid adalah kedalaman (positif ke bawah), default elevasi
+    #[serde(default)]
+    pub positive_down: bool,
+}
@@ -0,0 +1,85 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+
+pub type SharedFaults = Arc<RwLock<FaultSchedule>>;
+
+/// Jenis gangguan output sensor. Semua fault hanya mengubah salinan state yang dipublikasikan;
+/// state simulasi sebenarnya tidak tersentuh.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FaultKind {
+    /// Tidak ada output sama sekali (MQTT, WebSocket, TCP, UDP, CAN)
+    Silence,
+    /// Field tetap pada nilai publikasi sebelumnya; tanpa `field` = semua field fault
+    Freeze,
+    /// Lonjakan: `magnitude` ditambahkan ke field (biasanya di luar rentang wajar)
+    Spike,
+    /// Offset tetap `magnitude` pada field
+    Bias,
+    /// Field menjadi NaN (`null` di JSON/Signal K, `NaN` di NMEA 0183)
+    Nan,
+    /// Checksum kalimat NMEA 0183 salah
+    BadChecksum,
+    /// Flag status NMEA 0183 tidak valid, mis. status RMC `V` atau kualitas GGA 0
+    InvalidStatus,
+}
+
+impl FaultKind {
+    /// Fault yang mengubah nilai field (bukan output secara keseluruhan).
+    pub fn uses_field(self) -> bool {
+        matches!(self, FaultKind::Freeze | FaultKind::Spike | FaultKind::Bias | FaultKind::Nan)
+    }
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct Fault {
+    pub id: u64,
+    pub kind: FaultKind,
+    /// Field yang diganggu; `None` = field utama sensor (freeze: semua field)
+    pub field: Option<String>,
+    pub magnitude: Option<f64>,
+    /// Awal dan akhir fault (waktu simulasi)
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    /// Peluang fault diterapkan pada setiap tick publikasi; `None` = selalu
+    pub probability: Option<f64>,
+}
+
+impl Fault {
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.start <= now && now < self.end
+    }
+}
+
+/// Fault yang dijadwalkan untuk satu sensor; fault yang sudah berakhir dibuang saat publikasi.
+#[derive(Clone, Debug, Default)]
+pub struct FaultSchedule {
+    pub next_id: u64,
+    pub faults: Vec<Fault>,
+}
+
+/// Fault beserta statusnya untuk respons API.
+#[derive(Clone, Serialize, Debug)]
+pub struct FaultStatus {
+    #[serde(flatten)]
+    pub fault: Fault,
+    pub active: bool,
+}
+
+/// Request `POST /api/{sensor}/faults`. Tanpa `start`/`delay` fault langsung aktif.
+#[derive(Deserialize, Debug)]
+pub struct CreateFaultRequest {
+    pub kind: FaultKind,
+    pub field: Option<String>,
+    /// Wajib untuk `spike` dan `bias`
+    pub magnitude: Option<f64>,
+    /// Waktu mulai (waktu simulasi); default sekarang
+    pub start: Option<DateTime<Utc>>,
+    /// Penundaan dari `start` (detik waktu simulasi)
+    #[serde(default)]
+    pub delay: f64,
+    /// Lama fault (detik waktu simulasi)
+    pub duration: f64,
+    pub probability: Option<f64>,
+}
@@ -1,13 +1,11 @@
+use crate::data::sensor_data::DataSource;
+use crate::data::track_data::SharedTrack;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::sync::{Arc, RwLock};
-
-// DIUBAH: Sekarang ada dua tipe alias terpisah untuk State dan Config.
-pub type SharedGpsState = Arc<RwLock<Option<GpsState>>>;
-pub type SharedGpsConfig = Arc<RwLock<GpsConfig>>;
 
 // DIUBAH: Struct ini sekarang ramping dan HANYA berisi data sensor.
 // Field `config` telah dihapus.
+/// Posisi, SOG, dan COG yang dilaporkan GPS (sudah termasuk error GNSS); nilai sebenarnya di `truth`.
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct GpsState {
     pub latitude: f64,
@@ -15,47 +13,273 @@ pub struct GpsState {
     pub sog: f64,
     pub cog: f64,
     pub variation: f64,
+    pub fix_quality: FixQuality,
+    /// Dihitung dari geometri satelit jika simulasi konstelasi aktif
+    pub hdop: f64,
+    pub error_model: GnssErrorModel,
+    pub constellation: ConstellationConfig,
+    /// Satelit terlihat dan DOP; `None` jika simulasi konstelasi tidak aktif
+    pub sky: Option<SkyView>,
     pub is_running: bool,
     pub last_update: DateTime<Utc>,
     #[serde(skip)]
     pub calculation_rate_ms: u64,
+    pub source: DataSource,
+    /// Nilai PATCH yang menimpa data log NMEA selama `source` = `nmea_log`
+    pub overrides: GpsOverrides,
+    /// Rekaman track, diekspor lewat `GET /api/gps/track.{gpx,kml,geojson,csv}`
+    #[serde(skip)]
+    pub track: SharedTrack,
+    /// Posisi dan gerak sebenarnya, hanya untuk perbandingan lewat `GET /api/gps/truth`
+    #[serde(skip)]
+    pub truth: GpsTruth,
+    #[serde(skip)]
+    pub error: GnssError,
+}
+
+impl GpsState {
+    /// Menyalin nilai sebenarnya ke nilai terukur, mis. setelah PATCH atau input NMEA;
+    /// error GNSS diterapkan lagi pada langkah berikutnya.
+    pub fn report_truth(&mut self) {
+        self.latitude = self.truth.latitude;
+        self.longitude = self.truth.longitude;
+        self.sog = self.truth.sog;
+        self.cog = self.truth.cog;
+    }
+
+    /// Kualitas fix efektif: kualitas yang dipilih, diturunkan jika satelit yang dipakai
+    /// kurang dari 4 (fix 2D) atau kurang dari 3 (tanpa fix).
+    pub fn fix(&self) -> FixQuality {
+        match &self.sky {
+            Some(sky) if sky.used < 3 => FixQuality::NoFix,
+            Some(sky) if sky.used < 4 && self.fix_quality != FixQuality::NoFix => FixQuality::Fix2d,
+            _ => self.fix_quality,
+        }
+    }
+
+    /// Jumlah satelit yang dipakai untuk fix (8 jika konstelasi tidak disimulasikan).
+    pub fn satellites_used(&self) -> u8 {
+        match &self.sky {
+            Some(sky) => sky.used,
+            None if self.fix_quality.has_fix() => 8,
+            None => 0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default)]
+pub struct GpsTruth {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub sog: f64,
+    pub cog: f64,
+}
+
+/// Kualitas fix GNSS; menentukan skala error dan field status di NMEA/NMEA 2000.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub enum FixQuality {
+    #[serde(rename = "no_fix")]
+    NoFix,
+    #[serde(rename = "2d")]
+    Fix2d,
+    #[serde(rename = "3d")]
+    #[default]
+    Fix3d,
+    #[serde(rename = "dgps")]
+    Dgps,
+    #[serde(rename = "rtk")]
+    Rtk,
+}
+
+impl FixQuality {
+    pub fn has_fix(self) -> bool {
+        self != FixQuality::NoFix
+    }
+}
+
+/// Parameter model error GNSS (field yang tidak diisi = default). Semua sigma dalam meter
+/// untuk fix 3D; fix lain diskalakan (2D ×2, DGPS ×0.3, RTK ×0.01).
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct GnssErrorModel {
+    pub enabled: bool,
+    /// Standar deviasi stasioner bias posisi Gauss-Markov orde satu, per sumbu
+    pub bias_sigma: f64,
+    /// Konstanta waktu korelasi bias (detik)
+    pub bias_time_constant: f64,
+    /// Noise putih posisi per sumbu pada HDOP 1; dikali HDOP
+    pub noise_sigma: f64,
+    /// Peluang lompatan multipath per detik
+    pub multipath_probability: f64,
+    /// Besar rata-rata lompatan multipath
+    pub multipath_magnitude: f64,
+    /// Lama lompatan multipath (detik)
+    pub multipath_duration: f64,
+    /// Noise kecepatan per sumbu (knot); noise COG membesar saat SOG kecil
+    pub velocity_sigma: f64,
+}
+
+impl Default for GnssErrorModel {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bias_sigma: 2.0,
+            bias_time_constant: 300.0,
+            noise_sigma: 0.7,
+            multipath_probability: 0.002,
+            multipath_magnitude: 10.0,
+            multipath_duration: 10.0,
+            velocity_sigma: 0.05,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum GnssSystem {
+    Gps,
+    Glonass,
+    Galileo,
+    Beidou,
 }
 
-// DIUBAH: Struct ini sekarang independen dan semua field-nya adalah Option<T>.
+/// Sektor langit yang terhalang (mis. bangunan atas di buritan): satelit dengan bearing relatif
+/// terhadap haluan di antara `from` dan `to` (searah jarum jam) di bawah `max_elevation` tidak diterima.
 #[derive(Clone, Serialize, Deserialize, Debug)]
-pub struct GpsConfig {
-    pub ip: Option<String>,
-    pub port: Option<u16>,
-    pub username: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub password: Option<String>,
-    pub update_rate: Option<u64>,
-    pub topics: Option<Vec<String>>,
-}
-
-// DIUBAH: Default untuk Config sekarang adalah semua field bernilai None.
-impl Default for GpsConfig {
+pub struct SkyMask {
+    pub from: f64,
+    pub to: f64,
+    pub max_elevation: f64,
+}
+
+/// Konstelasi yang disimulasikan (field yang tidak diisi = default).
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct ConstellationConfig {
+    pub enabled: bool,
+    pub systems: Vec<GnssSystem>,
+    /// Elevasi minimum satelit yang dipakai (derajat)
+    pub elevation_mask: f64,
+    pub masks: Vec<SkyMask>,
+}
+
+impl Default for ConstellationConfig {
     fn default() -> Self {
-        GpsConfig {
-            ip: None,
-            port: None,
-            username: None,
-            password: None,
-            update_rate: None,
-            topics: None,
+        Self {
+            enabled: false,
+            systems: vec![GnssSystem::Gps, GnssSystem::Glonass, GnssSystem::Galileo, GnssSystem::Beidou],
+            elevation_mask: 5.0,
+            masks: Vec::new(),
         }
     }
 }
 
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SatelliteView {
+    pub system: GnssSystem,
+    pub prn: u8,
+    pub elevation: f64,
+    pub azimuth: f64,
+    /// C/N0 (dB-Hz); `None` jika satelit terhalang mask sektor
+    pub snr: Option<f64>,
+    pub used: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SkyView {
+    /// Satelit di atas horizon, urut menurut sistem lalu PRN
+    pub satellites: Vec<SatelliteView>,
+    pub used: u8,
+    pub hdop: f64,
+    pub vdop: f64,
+    pub pdop: f64,
+}
+
+/// State internal model error (meter): bias dan lompatan multipath aktif dalam skala fix 3D,
+/// serta error total terakhir setelah diskalakan kualitas fix.
+#[derive(Clone, Copy, Serialize, Debug, Default)]
+pub struct GnssError {
+    pub bias_north: f64,
+    pub bias_east: f64,
+    pub multipath_north: f64,
+    pub multipath_east: f64,
+    /// Sisa waktu lompatan multipath (detik)
+    pub multipath_remaining: f64,
+    pub north: f64,
+    pub east: f64,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default)]
+pub struct GpsOverrides {
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub sog: Option<f64>,
+    pub cog: Option<f64>,
+}
+
+impl GpsOverrides {
+    pub fn apply(&self, state: &mut GpsTruth) {
+        if let Some(lat) = self.latitude { state.latitude = lat; }
+        if let Some(lon) = self.longitude { state.longitude = lon; }
+        if let Some(sog) = self.sog { state.sog = sog; }
+        if let Some(cog) = self.cog { state.cog = cog; }
+    }
+}
+
+/// Opsi khusus GPS di dalam `SensorConfig` (field None = pakai default).
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct GpsOptions {
+    pub nmea_sentences: Option<Vec<GpsSentence>>,
+}
+
+/// Jenis kalimat NMEA 0183 yang bisa dihasilkan dari GpsState.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum GpsSentence {
+    Rmc,
+    Gga,
+    Vtg,
+    Gll,
+    Zda,
+    /// Hanya dikirim jika simulasi konstelasi aktif
+    Gsa,
+    Gsv,
+}
+
+impl GpsSentence {
+    /// Urutan default jika `nmea_sentences` belum diatur.
+    pub const ALL: [GpsSentence; 7] = [
+        GpsSentence::Rmc,
+        GpsSentence::Gga,
+        GpsSentence::Vtg,
+        GpsSentence::Gll,
+        GpsSentence::Zda,
+        GpsSentence::Gsa,
+        GpsSentence::Gsv,
+    ];
+}
+
 // Struct untuk request API di bawah ini sebagian besar tetap sama,
 // karena sudah dirancang dengan baik.
 
+fn default_hdop() -> f64 {
+    0.9
+}
+
 #[derive(Deserialize, Debug)]
 pub struct CreateGpsRequest {
     pub latitude: f64,
     pub longitude: f64,
     pub sog: f64,
     pub cog: f64,
+    #[serde(default)]
+    pub fix_quality: FixQuality,
+    #[serde(default = "default_hdop")]
+    pub hdop: f64,
+    #[serde(default)]
+    pub error_model: GnssErrorModel,
+    #[serde(default)]
+    pub constellation: ConstellationConfig,
     pub is_running: bool,
 }
 
@@ -65,15 +289,11 @@ pub struct UpdateGpsRequest {
     pub longitude: Option<f64>,
     pub sog: Option<f64>,
     pub cog: Option<f64>,
+    pub fix_quality: Option<FixQuality>,
+    pub hdop: Option<f64>,
+    /// Mengganti seluruh model error (field yang tidak diisi = default)
+    pub error_model: Option<GnssErrorModel>,
+    /// Mengganti seluruh konfigurasi konstelasi (field yang tidak diisi = default)
+    pub constellation: Option<ConstellationConfig>,
     pub is_running: Option<bool>,
 }
-
-#[derive(Deserialize, Debug, Default)]
-pub struct UpdateGpsConfigRequest {
-    pub ip: Option<String>,
-    pub port: Option<u16>,
-    pub username: Option<String>,
-    pub password: Option<String>,
-    pub update_rate: Option<u64>,
-    pub topics: Option<Vec<String>>,
-}
\ No newline at end of file
@@ -1,10 +1,6 @@
+use crate::data::sensor_data::DataSource;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::sync::{Arc, RwLock};
-
-// DIUBAH: Dua tipe alias terpisah.
-pub type SharedGyroState = Arc<RwLock<Option<GyroState>>>;
-pub type SharedGyroConfig = Arc<RwLock<GyroConfig>>;
 
 // DIUBAH: Struct State yang ramping, tanpa config.
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -17,34 +13,58 @@ pub struct GyroState {
     pub last_update: DateTime<Utc>,
     #[serde(skip)]
     pub calculation_rate_ms: u64,
+    pub source: DataSource,
+    /// Nilai PATCH yang menimpa data log NMEA selama `source` = `nmea_log`
+    pub overrides: GyroOverrides,
 }
 
-// DIUBAH: Struct Config yang independen dengan field Option<T>.
-#[derive(Clone, Serialize, Deserialize, Debug)]
-pub struct GyroConfig {
-    pub ip: Option<String>,
-    pub port: Option<u16>,
-    pub username: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub password: Option<String>,
-    pub update_rate: Option<u64>,
-    pub topics: Option<Vec<String>>,
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default)]
+pub struct GyroOverrides {
+    pub yaw: Option<f64>,
+    pub pitch: Option<f64>,
+    pub roll: Option<f64>,
+    pub yaw_rate: Option<f64>,
 }
 
-// DIUBAH: Default untuk Config adalah semua field bernilai None.
-impl Default for GyroConfig {
-    fn default() -> Self {
-        GyroConfig {
-            ip: None,
-            port: None,
-            username: None,
-            password: None,
-            update_rate: None,
-            topics: None,
-        }
+impl GyroOverrides {
+    pub fn apply(&self, state: &mut GyroState) {
+        if let Some(yaw) = self.yaw { state.yaw = yaw; }
+        if let Some(pitch) = self.pitch { state.pitch = pitch; }
+        if let Some(roll) = self.roll { state.roll = roll; }
+        if let Some(yaw_rate) = self.yaw_rate { state.yaw_rate = yaw_rate; }
     }
 }
 
+/// Opsi khusus gyro di dalam `SensorConfig` (field None = pakai default).
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct GyroOptions {
+    pub nmea_sentences: Option<Vec<GyroSentence>>,
+    /// Deviasi kompas magnetik (derajat, positif = East) untuk kalimat HDG
+    pub deviation: Option<f64>,
+}
+
+/// Jenis kalimat NMEA 0183 yang bisa dihasilkan dari GyroState.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum GyroSentence {
+    Hdt,
+    Ths,
+    Rot,
+    Hdg,
+    Xdr,
+}
+
+impl GyroSentence {
+    /// Urutan default jika `nmea_sentences` belum diatur.
+    pub const ALL: [GyroSentence; 5] = [
+        GyroSentence::Hdt,
+        GyroSentence::Ths,
+        GyroSentence::Rot,
+        GyroSentence::Hdg,
+        GyroSentence::Xdr,
+    ];
+}
+
 // Struct Request API.
 #[derive(Deserialize, Debug)]
 pub struct CreateGyroRequest {
@@ -63,13 +83,3 @@ pub struct UpdateGyroRequest {
     pub yaw_rate: Option<f64>,
     pub is_running: Option<bool>,
 }
-
-#[derive(Deserialize, Debug, Default)]
-pub struct UpdateGyroConfigRequest {
-    pub ip: Option<String>,
-    pub port: Option<u16>,
-    pub username: Option<String>,
-    pub password: Option<String>,
-    pub update_rate: Option<u64>,
-    pub topics: Option<Vec<String>>,
-}
\ No newline at end of file
@@ -1,6 +1,7 @@
 use serde::{Serialize, Deserialize};
 use crate::data::gps_data::GpsState;
 
+#[allow(dead_code)]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct MessageData {
     pub message: String,
@@ -1,6 +1,17 @@
 pub mod gps_data;
 pub mod message_data;
-// pub mod anemo_data;
-// pub mod baro_data;
+pub mod output_data;
+pub mod sensor_data;
+pub mod anemo_data;
+pub mod baro_data;
 pub mod gyro_data;
-// pub mod thermal_data;
\ No newline at end of file
+pub mod thermal_data;
+pub mod depth_data;
+pub mod speedlog_data;
+pub mod vessel_data;
+pub mod route_data;
+pub mod track_data;
+pub mod recording_data;
+pub mod clock_data;
+pub mod seed_data;
+pub mod fault_data;
@@ -0,0 +1,52 @@
+use crate::utils::net::StreamFormat;
+use serde::{Deserialize, Serialize};
+
+/// Port standar NMEA 0183 over UDP (IEC 61162-450 / OpenCPN)
+pub const DEFAULT_UDP_PORT: u16 = 10110;
+
+/// Konfigurasi output UDP. Mode unicast/broadcast/multicast ditentukan dari alamat tujuan.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct UdpOutputConfig {
+    pub destination: String,
+    pub port: u16,
+    pub ttl: u32,
+    pub format: StreamFormat,
+    pub enabled: bool,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CreateUdpOutputRequest {
+    pub destination: String,
+    pub port: Option<u16>,
+    pub ttl: Option<u32>,
+    pub format: Option<StreamFormat>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct UpdateUdpOutputRequest {
+    pub destination: Option<String>,
+    pub port: Option<u16>,
+    pub ttl: Option<u32>,
+    pub format: Option<StreamFormat>,
+    pub enabled: Option<bool>,
+}
+
+/// Konfigurasi output NMEA 2000 ke interface SocketCAN.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CanOutputConfig {
+    pub interface: String,
+    pub enabled: bool,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CreateCanOutputRequest {
+    pub interface: String,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct UpdateCanOutputRequest {
+    pub interface: Option<String>,
+    pub enabled: Option<bool>,
+}
@@ -0,0 +1,138 @@
+use crate::utils::n2k::N2kMessage;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Format file rekaman.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RecordingFormat {
+    /// Kalimat NMEA 0183 dengan TAG block `c:` (waktu) dan `s:` (nama sensor)
+    Nmea,
+    /// Satu `RecordedMessage` JSON per baris
+    Jsonl,
+    /// `RecordedMessage` bincode berurutan
+    Bincode,
+}
+
+impl RecordingFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            RecordingFormat::Nmea => "nmea",
+            RecordingFormat::Jsonl => "jsonl",
+            RecordingFormat::Bincode => "bin",
+        }
+    }
+
+    /// Format dari ekstensi file (`.nmea`/`.log`/`.txt`, `.jsonl`/`.json`, `.bin`).
+    pub fn from_path(path: &str) -> Option<Self> {
+        let extension = std::path::Path::new(path).extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "nmea" | "log" | "txt" => Some(RecordingFormat::Nmea),
+            "jsonl" | "json" => Some(RecordingFormat::Jsonl),
+            "bin" | "bincode" => Some(RecordingFormat::Bincode),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct MqttMessage {
+    pub topic: String,
+    pub payload: String,
+}
+
+/// Satu tick publikasi satu sensor, dalam semua representasi yang dikirim ke output.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct RecordedMessage {
+    pub time: DateTime<Utc>,
+    pub sensor: String,
+    pub mqtt: Vec<MqttMessage>,
+    /// Pesan WebSocket `{NAME}_update`; kosong untuk log NMEA
+    pub json: String,
+    pub nmea: Vec<String>,
+    pub n2k: Vec<N2kMessage>,
+    /// Delta Signal K sebagai teks JSON (bincode tidak mendukung `serde_json::Value`)
+    pub signalk: Option<String>,
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct RecordingStatus {
+    pub path: String,
+    pub format: RecordingFormat,
+    pub started: DateTime<Utc>,
+    pub message_count: u64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct StartRecordingRequest {
+    #[serde(default = "default_format")]
+    pub format: RecordingFormat,
+    /// Path di dalam `recordings/`; default `recordings/recording-<waktu mulai>.<ext>`
+    pub path: Option<String>,
+}
+
+fn default_format() -> RecordingFormat {
+    RecordingFormat::Jsonl
+}
+
+#[derive(Clone, Copy, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplayState {
+    Playing,
+    Paused,
+    Finished,
+}
+
+#[derive(Clone, Serialize, Debug)]
+pub struct ReplayStatus {
+    pub path: String,
+    pub format: RecordingFormat,
+    pub message_count: usize,
+    /// Durasi rekaman (detik)
+    pub duration: f64,
+    /// Posisi putar dari awal rekaman (detik)
+    pub position: f64,
+    pub speed: f64,
+    pub looping: bool,
+    pub state: ReplayState,
+}
+
+fn default_speed() -> f64 {
+    1.0
+}
+
+#[derive(Deserialize, Debug)]
+pub struct StartReplayRequest {
+    /// Path di dalam `recordings/`
+    pub path: String,
+    /// Default: dari ekstensi file
+    pub format: Option<RecordingFormat>,
+    #[serde(default = "default_speed")]
+    pub speed: f64,
+    #[serde(default)]
+    pub looping: bool,
+    #[serde(default)]
+    pub paused: bool,
+}
+
+/// Request `PATCH /api/replay`; `position` = seek (detik dari awal rekaman).
+#[derive(Deserialize, Debug, Default)]
+pub struct UpdateReplayRequest {
+    pub speed: Option<f64>,
+    pub paused: Option<bool>,
+    pub looping: Option<bool>,
+    pub position: Option<f64>,
+}
+
+/// Request `POST /api/nmea-log`: log NMEA 0183 menjadi sumber data GPS dan gyro.
+#[derive(Deserialize, Debug)]
+pub struct StartNmeaLogRequest {
+    /// Path di dalam `recordings/`
+    pub path: String,
+    #[serde(default = "default_speed")]
+    pub speed: f64,
+    #[serde(default)]
+    pub looping: bool,
+    #[serde(default)]
+    pub paused: bool,
+}
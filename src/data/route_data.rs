@@ -0,0 +1,170 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct RouteState {
+    pub waypoints: Vec<Waypoint>,
+    /// Radius kedatangan (NM)
+    pub arrival_radius: f64,
+    /// Laju belok maksimum saat mengikuti rute (derajat/detik)
+    pub turn_rate: f64,
+    /// Kembali ke waypoint pertama setelah waypoint terakhir
+    pub looping: bool,
+    /// Indeks waypoint tujuan
+    pub active_index: usize,
+    /// Awal leg aktif: waypoint sebelumnya, atau posisi kapal saat rute (ulang) dimulai
+    pub leg_start: Option<RoutePoint>,
+    pub completed: bool,
+    /// Cross-track error (NM), positif = kapal di kanan track (kemudi ke kiri)
+    pub xte: f64,
+    /// Jarak dan bearing true ke waypoint tujuan (NM, derajat)
+    pub dtw: f64,
+    pub btw: f64,
+    /// Bearing leg dari awal ke waypoint tujuan (derajat true)
+    pub bod: f64,
+    /// Haluan yang dikemudikan autopilot (derajat true)
+    pub desired_course: f64,
+    /// Kecepatan mendekati waypoint (knot)
+    pub vmg: f64,
+    pub arrival_circle_entered: bool,
+    pub perpendicular_passed: bool,
+    pub is_running: bool,
+    pub last_update: DateTime<Utc>,
+    #[serde(skip)]
+    pub calculation_rate_ms: u64,
+}
+
+impl RouteState {
+    pub fn active_waypoint(&self) -> Option<&Waypoint> {
+        if self.completed { None } else { self.waypoints.get(self.active_index) }
+    }
+}
+
+/// Waypoint rute; `speed` dan `leg` berlaku untuk leg MENUJU waypoint ini.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct Waypoint {
+    /// ID waypoint di kalimat NMEA; kosong = `WPnnn`
+    #[serde(default)]
+    pub name: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Kecepatan leg (knot)
+    pub speed: f64,
+    #[serde(default)]
+    pub leg: LegType,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LegType {
+    #[default]
+    GreatCircle,
+    RhumbLine,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct RoutePoint {
+    pub name: String,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// Opsi khusus rute di dalam `SensorConfig` (field None = pakai default).
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct RouteOptions {
+    pub nmea_sentences: Option<Vec<RouteSentence>>,
+}
+
+/// Jenis kalimat NMEA 0183 yang bisa dihasilkan dari RouteState.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum RouteSentence {
+    Rmb,
+    Apb,
+    Xte,
+    Bwc,
+}
+
+impl RouteSentence {
+    /// Urutan default jika `nmea_sentences` belum diatur.
+    pub const ALL: [RouteSentence; 4] = [RouteSentence::Rmb, RouteSentence::Apb, RouteSentence::Xte, RouteSentence::Bwc];
+}
+
+fn default_arrival_radius() -> f64 {
+    0.1
+}
+
+fn default_turn_rate() -> f64 {
+    1.0
+}
+
+// Struct Request API.
+#[derive(Deserialize, Debug)]
+pub struct CreateRouteRequest {
+    pub waypoints: Vec<Waypoint>,
+    #[serde(default = "default_arrival_radius")]
+    pub arrival_radius: f64,
+    #[serde(default = "default_turn_rate")]
+    pub turn_rate: f64,
+    #[serde(default)]
+    pub looping: bool,
+    pub is_running: bool,
+}
+
+/// Mengganti `waypoints` atau `active_index` memulai leg baru dari posisi kapal saat ini.
+#[derive(Deserialize, Debug, Default)]
+pub struct UpdateRouteRequest {
+    pub waypoints: Option<Vec<Waypoint>>,
+    pub arrival_radius: Option<f64>,
+    pub turn_rate: Option<f64>,
+    pub looping: Option<bool>,
+    pub active_index: Option<usize>,
+    pub is_running: Option<bool>,
+}
+
+/// Format file rute yang bisa diimpor.
+#[derive(Clone, Copy, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RouteFormat {
+    Gpx,
+    Kml,
+    Rtz,
+}
+
+fn default_import_speed() -> f64 {
+    10.0
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Query string untuk `POST /api/route/import`; body berisi isi file.
+#[derive(Deserialize, Debug)]
+pub struct ImportRouteQuery {
+    /// Default: dideteksi dari elemen root
+    pub format: Option<RouteFormat>,
+    /// Kecepatan leg (knot) jika file tidak menyediakannya
+    #[serde(default = "default_import_speed")]
+    pub speed: f64,
+    /// Menimpa jenis leg dari file (default great circle)
+    pub leg: Option<LegType>,
+    /// GPX: pakai `trk` walaupun file punya `rte`
+    #[serde(default)]
+    pub track: bool,
+    /// GPX track: kecepatan tiap leg dihitung dari timestamp titik (kecepatan asli)
+    #[serde(default)]
+    pub use_timestamps: bool,
+    /// Pindahkan kapal ke titik pertama dan mulai dari leg kedua
+    #[serde(default)]
+    pub start_at_first: bool,
+    /// Dipakai jika rute baru dibuat
+    #[serde(default = "default_arrival_radius")]
+    pub arrival_radius: f64,
+    #[serde(default = "default_turn_rate")]
+    pub turn_rate: f64,
+    #[serde(default)]
+    pub looping: bool,
+    #[serde(default = "default_true")]
+    pub is_running: bool,
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Clone, Serialize, Debug)]
+pub struct SeedStatus {
+    /// Master seed; run dengan seed, jam simulasi, dan skenario yang sama menghasilkan output identik
+    pub seed: u64,
+    /// Seed turunan per simulator (nama sensor)
+    pub streams: BTreeMap<String, u64>,
+}
+
+/// Request `PATCH /api/seed`; tanpa `seed` = seed acak baru.
+#[derive(Deserialize, Debug, Default)]
+pub struct UpdateSeedRequest {
+    pub seed: Option<u64>,
+}
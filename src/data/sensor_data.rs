@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+
+// State dan config setiap sensor disimpan dengan pola yang sama;
+// tipe state/opsi spesifik ditentukan oleh implementasi `Sensor`.
+pub type SharedSensorState<T> = Arc<RwLock<Option<T>>>;
+pub type SharedSensorConfig<O> = Arc<RwLock<SensorConfig<O>>>;
+
+/// Asal nilai state sensor.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DataSource {
+    /// Model sintetis (dead reckoning, model kapal, gelombang)
+    #[default]
+    Model,
+    /// Log NMEA 0183 yang diputar lewat `/api/nmea-log`
+    NmeaLog,
+}
+
+/// Config umum semua sensor (broker MQTT, laju publikasi, identitas NMEA/N2K),
+/// ditambah opsi khusus sensor yang di-flatten ke objek JSON yang sama.
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct SensorConfig<O> {
+    pub ip: Option<String>,
+    pub port: Option<u16>,
+    pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    pub update_rate: Option<u64>,
+    pub topics: Option<Vec<String>>,
+    pub nmea_talker: Option<String>,
+    pub n2k_source_address: Option<u8>,
+    #[serde(flatten)]
+    pub options: O,
+}
+
+impl<O> SensorConfig<O> {
+    /// Simulasi hanya boleh dibuat/dijalankan jika broker dan laju publikasi sudah diatur.
+    pub fn is_complete(&self) -> bool {
+        self.ip.is_some() && self.port.is_some() && self.update_rate.is_some()
+    }
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct UpdateSensorConfigRequest<O> {
+    pub ip: Option<String>,
+    pub port: Option<u16>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub update_rate: Option<u64>,
+    pub topics: Option<Vec<String>>,
+    pub nmea_talker: Option<String>,
+    pub n2k_source_address: Option<u8>,
+    #[serde(flatten)]
+    pub options: O,
+}
@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SpeedLogState {
+    /// Kecepatan terhadap air (knot)
+    pub stw: f64,
+    /// Heading true (derajat); diambil dari gyro jika ada
+    pub heading: f64,
+    /// Sudut leeway (derajat), positif = kapal hanyut ke kanan (starboard) dari heading
+    pub leeway: f64,
+    /// Arah arus mengalir, bukan asalnya (derajat true)
+    pub current_set: f64,
+    /// Kecepatan arus (knot)
+    pub current_drift: f64,
+    /// Hasil penjumlahan vektor air + arus
+    pub sog: f64,
+    pub cog: f64,
+    /// Komponen kecepatan relatif haluan (knot), transversal positif = ke kanan
+    pub longitudinal_water_speed: f64,
+    pub transverse_water_speed: f64,
+    pub longitudinal_ground_speed: f64,
+    pub transverse_ground_speed: f64,
+    pub is_running: bool,
+    pub last_update: DateTime<Utc>,
+}
+
+/// Opsi khusus speed log di dalam `SensorConfig` (field None = pakai default).
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct SpeedLogOptions {
+    pub nmea_sentences: Option<Vec<SpeedLogSentence>>,
+}
+
+/// Jenis kalimat NMEA 0183 yang bisa dihasilkan dari SpeedLogState.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum SpeedLogSentence {
+    Vhw,
+    Vbw,
+}
+
+impl SpeedLogSentence {
+    /// Urutan default jika `nmea_sentences` belum diatur.
+    pub const ALL: [SpeedLogSentence; 2] = [SpeedLogSentence::Vhw, SpeedLogSentence::Vbw];
+}
+
+// Struct Request API.
+#[derive(Deserialize, Debug)]
+pub struct CreateSpeedLogRequest {
+    pub stw: f64,
+    #[serde(default)]
+    pub heading: f64,
+    #[serde(default)]
+    pub leeway: f64,
+    #[serde(default)]
+    pub current_set: f64,
+    #[serde(default)]
+    pub current_drift: f64,
+    pub is_running: bool,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct UpdateSpeedLogRequest {
+    pub stw: Option<f64>,
+    pub heading: Option<f64>,
+    pub leeway: Option<f64>,
+    pub current_set: Option<f64>,
+    pub current_drift: Option<f64>,
+    pub is_running: Option<bool>,
+}
@@ -0,0 +1,142 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ThermalState {
+    pub channels: Vec<ThermalChannel>,
+    pub is_running: bool,
+    pub last_update: DateTime<Utc>,
+    #[serde(skip)]
+    pub calculation_rate_ms: u64,
+}
+
+impl ThermalState {
+    pub fn channel(&self, name: &str) -> Option<&ThermalChannel> {
+        self.channels.iter().find(|c| c.name == name)
+    }
+
+    pub fn channel_mut(&mut self, name: &str) -> Option<&mut ThermalChannel> {
+        self.channels.iter_mut().find(|c| c.name == name)
+    }
+}
+
+/// Satu titik ukur suhu. Suhu mengikuti setpoint (+ offset drift) dengan respons orde satu.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ThermalChannel {
+    /// Nama unik channel, juga dipakai sebagai ID transduser di XDR
+    pub name: String,
+    pub kind: ThermalKind,
+    /// Suhu target (°C)
+    pub setpoint: f64,
+    /// Konstanta waktu respons (detik)
+    pub time_constant: f64,
+    /// Standar deviasi noise pengukuran (°C)
+    pub noise: f64,
+    /// Skenario over-temperature; `None` = normal
+    pub drift: Option<ThermalDrift>,
+    /// Offset drift yang sudah terkumpul (°C)
+    pub drift_offset: f64,
+    /// Suhu terukur (°C), termasuk noise
+    pub temperature: f64,
+    /// Suhu fisik tanpa noise
+    #[serde(skip)]
+    pub value: f64,
+}
+
+/// Jenis channel; menentukan MTW, sumber PGN 130316, dan path Signal K.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ThermalKind {
+    SeaWater,
+    EngineRoom,
+    EngineCoolant,
+    Exhaust,
+    Cabin,
+    Refrigeration,
+    #[default]
+    Other,
+}
+
+/// Skenario over-temperature: suhu naik `rate` °C/menit sampai `max_offset` °C di atas setpoint.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+pub struct ThermalDrift {
+    pub rate: f64,
+    pub max_offset: f64,
+}
+
+/// Opsi khusus sensor suhu di dalam `SensorConfig` (field None = pakai default).
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct ThermalOptions {
+    pub nmea_sentences: Option<Vec<ThermalSentence>>,
+}
+
+/// Jenis kalimat NMEA 0183 yang bisa dihasilkan dari ThermalState.
+/// MTW hanya untuk channel `sea_water`; XDR satu kalimat per channel.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ThermalSentence {
+    Mtw,
+    Xdr,
+}
+
+impl ThermalSentence {
+    /// Urutan default jika `nmea_sentences` belum diatur.
+    pub const ALL: [ThermalSentence; 2] = [ThermalSentence::Mtw, ThermalSentence::Xdr];
+}
+
+fn default_time_constant() -> f64 {
+    60.0
+}
+
+fn default_noise() -> f64 {
+    0.1
+}
+
+/// Membedakan field yang tidak dikirim (None) dari `null` (Some(None)).
+fn deserialize_some<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+// Struct Request API.
+#[derive(Deserialize, Debug)]
+pub struct CreateThermalRequest {
+    /// Jika kosong, dibuat channel default: sea water, engine coolant, exhaust, dan cabin
+    #[serde(default)]
+    pub channels: Vec<CreateThermalChannelRequest>,
+    pub is_running: bool,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct UpdateThermalRequest {
+    pub is_running: Option<bool>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct CreateThermalChannelRequest {
+    pub name: String,
+    #[serde(default)]
+    pub kind: ThermalKind,
+    pub setpoint: f64,
+    #[serde(default = "default_time_constant")]
+    pub time_constant: f64,
+    #[serde(default = "default_noise")]
+    pub noise: f64,
+    /// Suhu awal (default = setpoint)
+    pub temperature: Option<f64>,
+    pub drift: Option<ThermalDrift>,
+}
+
+/// `drift: null` menghentikan skenario over-temperature.
+#[derive(Deserialize, Debug, Default)]
+pub struct UpdateThermalChannelRequest {
+    pub kind: Option<ThermalKind>,
+    pub setpoint: Option<f64>,
+    pub time_constant: Option<f64>,
+    pub noise: Option<f64>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub drift: Option<Option<ThermalDrift>>,
+}
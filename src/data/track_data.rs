@@ -0,0 +1,82 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// Track yang direkam dari langkah GPS; dibagi lewat `Arc` agar clone `GpsState` tetap murah.
+pub type SharedTrack = Arc<Mutex<TrackLog>>;
+
+/// Satu titik track. `heading`, `roll`, dan `pitch` kosong jika tidak ada gyro/model kapal.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct TrackPoint {
+    pub time: DateTime<Utc>,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub sog: f64,
+    pub cog: f64,
+    pub heading: Option<f64>,
+    pub roll: Option<f64>,
+    pub pitch: Option<f64>,
+}
+
+/// Aturan desimasi: titik baru dicatat setelah kapal bergerak sejauh `meters`
+/// atau setelah `seconds` berlalu sejak titik terakhir.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TrackDecimation {
+    Distance { meters: f64 },
+    Time { seconds: f64 },
+}
+
+impl Default for TrackDecimation {
+    fn default() -> Self {
+        TrackDecimation::Time { seconds: 1.0 }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct TrackSettings {
+    pub enabled: bool,
+    pub decimation: TrackDecimation,
+    /// Jumlah titik maksimum; titik tertua dibuang setelah batas tercapai
+    pub max_points: usize,
+}
+
+impl Default for TrackSettings {
+    fn default() -> Self {
+        Self { enabled: true, decimation: TrackDecimation::default(), max_points: 100_000 }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct TrackLog {
+    pub settings: TrackSettings,
+    pub points: VecDeque<TrackPoint>,
+}
+
+/// Ringkasan track untuk `GET /api/gps/track`.
+#[derive(Serialize, Debug)]
+pub struct TrackInfo {
+    pub settings: TrackSettings,
+    pub point_count: usize,
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+}
+
+/// Request `PATCH /api/gps/track` (field None = nilai lama).
+#[derive(Deserialize, Debug)]
+pub struct UpdateTrackRequest {
+    pub enabled: Option<bool>,
+    pub decimation: Option<TrackDecimation>,
+    pub max_points: Option<usize>,
+}
+
+/// Format ekspor track, dari ekstensi path `GET /api/gps/track.{format}`.
+#[derive(Clone, Copy, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TrackFormat {
+    Gpx,
+    Kml,
+    Geojson,
+    Csv,
+}
@@ -0,0 +1,105 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+
+pub type SharedVesselState = Arc<RwLock<Option<VesselState>>>;
+
+/// Kinematika kapal sebenarnya; gyro, GPS, dan speed log mengambil sampel dari state ini.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct VesselState {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Heading true (derajat)
+    pub heading: f64,
+    /// Laju belok (derajat/detik, positif = ke kanan)
+    pub rate_of_turn: f64,
+    pub steering: Steering,
+    /// Panjang kapal (m) dan konstanta Nomoto tak berdimensi K', T' untuk respons kemudi
+    pub length: f64,
+    pub nomoto_k: f64,
+    pub nomoto_t: f64,
+    /// Kecepatan terhadap air (knot)
+    pub stw: f64,
+    /// Sudut leeway (derajat), positif = hanyut ke kanan dari heading
+    pub leeway: f64,
+    /// Arah arus mengalir (derajat true) dan kecepatannya (knot)
+    pub current_set: f64,
+    pub current_drift: f64,
+    /// Hasil penjumlahan vektor air + arus
+    pub sog: f64,
+    pub cog: f64,
+    pub is_running: bool,
+    pub last_update: DateTime<Utc>,
+}
+
+/// Cara mengubah heading.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Steering {
+    /// Sudut kemudi (derajat, positif = kanan, maks ±35); laju belok mengikuti model Nomoto orde satu
+    Rudder { angle: f64 },
+    /// Laju belok langsung (derajat/detik, positif = kanan)
+    TurnRate { rate: f64 },
+    /// Heading hold (perintah autopilot, mis. HSC/HTC/HTD): laju belok sebanding selisih heading
+    Heading { heading: f64 },
+}
+
+impl Default for Steering {
+    fn default() -> Self {
+        Steering::Rudder { angle: 0.0 }
+    }
+}
+
+fn default_length() -> f64 {
+    50.0
+}
+
+fn default_nomoto_k() -> f64 {
+    1.5
+}
+
+fn default_nomoto_t() -> f64 {
+    1.0
+}
+
+// Struct Request API.
+#[derive(Deserialize, Debug)]
+pub struct CreateVesselRequest {
+    pub latitude: f64,
+    pub longitude: f64,
+    #[serde(default)]
+    pub heading: f64,
+    #[serde(default)]
+    pub steering: Steering,
+    #[serde(default = "default_length")]
+    pub length: f64,
+    #[serde(default = "default_nomoto_k")]
+    pub nomoto_k: f64,
+    #[serde(default = "default_nomoto_t")]
+    pub nomoto_t: f64,
+    #[serde(default)]
+    pub stw: f64,
+    #[serde(default)]
+    pub leeway: f64,
+    #[serde(default)]
+    pub current_set: f64,
+    #[serde(default)]
+    pub current_drift: f64,
+    pub is_running: bool,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct UpdateVesselRequest {
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub heading: Option<f64>,
+    pub steering: Option<Steering>,
+    pub length: Option<f64>,
+    pub nomoto_k: Option<f64>,
+    pub nomoto_t: Option<f64>,
+    pub stw: Option<f64>,
+    pub leeway: Option<f64>,
+    pub current_set: Option<f64>,
+    pub current_drift: Option<f64>,
+    pub is_running: Option<bool>,
+}
@@ -6,73 +6,75 @@ mod utils;
 
 use actix_cors::Cors;
 use actix_web::{web, App, HttpServer, http};
-use std::sync::{Arc, RwLock};
-use crate::data::gps_data::{SharedGpsConfig, SharedGpsState, GpsConfig};
-use crate::data::gyro_data::{SharedGyroConfig, SharedGyroState, GyroConfig};
-use crate::utils::mqtt_manager::{MqttCommand, MqttManager};
-use crate::utils::net::{Clients, handle_websocket_connection, handle_tcp_connection};
+use std::sync::Arc;
+use crate::services::anemo_service::Anemo;
+use crate::services::baro_service::Baro;
+use crate::services::depth_service::Depth;
+use crate::services::gps_service::Gps;
+use crate::services::gyro_service::Gyro;
+use crate::services::registry::SensorRegistry;
+use crate::services::route_service::Route;
+use crate::services::speedlog_service::SpeedLog;
+use crate::services::thermal_service::Thermal;
+use crate::utils::can_output::{CanOutput, SharedCanOutput};
+use crate::utils::nmea_log::{NmeaLogSource, SharedNmeaLogSource};
+use crate::utils::recording::SharedRecorder;
+use crate::utils::replay::{Replayer, SharedReplayer};
+use crate::utils::udp_output::{SharedUdpOutput, UdpOutput};
+use crate::utils::net::{Clients, Outputs, StreamFormat, TcpClients, TcpContext, handle_websocket_connection, handle_tcp_connection};
 use tokio::net::TcpListener;
-use tokio::sync::mpsc;
-use rumqttc::{AsyncClient, MqttOptions};
 
 #[tokio::main]
 async fn main() -> std::io::Result<()> {
     println!("🚀 Server starting...");
 
     // Shared states
-    let shared_gps_config: SharedGpsConfig = Arc::new(RwLock::new(GpsConfig::default()));
-    let shared_gyro_config: SharedGyroConfig = Arc::new(RwLock::new(GyroConfig::default()));
-    let shared_gps_state: SharedGpsState = Arc::new(RwLock::new(None));
-    let shared_gyro_state: SharedGyroState = Arc::new(RwLock::new(None));
     let ws_clients: Clients = Arc::new(tokio::sync::RwLock::new(Vec::new()));
+    let tcp_clients: TcpClients = Arc::new(tokio::sync::RwLock::new(Vec::new()));
+    let udp_output: SharedUdpOutput = Arc::new(UdpOutput::default());
+    let can_output: SharedCanOutput = Arc::new(CanOutput::default());
 
-    // Channels
-    let (gps_command_tx, gps_command_rx) = mpsc::channel::<MqttCommand>(10);
-    let (gyro_command_tx, gyro_command_rx) = mpsc::channel::<MqttCommand>(10);
+    // Sensor simulasi; masing-masing punya config, state, dan koneksi MQTT sendiri
+    let registry = Arc::new(
+        SensorRegistry::default()
+            .register::<Gps>()
+            .register::<Gyro>()
+            .register::<Anemo>()
+            .register::<Baro>()
+            .register::<Thermal>()
+            .register::<Depth>()
+            .register::<SpeedLog>()
+            .register::<Route>(),
+    );
 
-    // MQTT Client (dihubungkan dari config)
-    let mut mqttoptions = MqttOptions::new("vessel-client", "127.0.0.1", 1883);
-    mqttoptions.set_keep_alive(std::time::Duration::from_secs(5));
-    let (client, _eventloop) = AsyncClient::new(mqttoptions, 10);
-    let mqtt_manager = Arc::new(MqttManager::new(client));
+    let outputs = Outputs::new(ws_clients.clone(), tcp_clients.clone(), udp_output.clone(), can_output.clone());
+    let recorder: SharedRecorder = outputs.recorder.clone();
+    let replayer: SharedReplayer = Arc::new(Replayer::new(outputs.clone(), registry.mqtt_managers()));
+    let nmea_log: SharedNmeaLogSource = Arc::new(NmeaLogSource::new(registry.context()));
 
+    println!("🎲 Random seed: {} (set with VESSEL_SEED or PATCH /api/seed)", utils::sim_rng::seed());
     println!("🧠 Starting background services...");
 
     // Jalankan kalkulasi + publikasi
-    services::gps_service::start_gps_calculation_thread(shared_gps_state.clone());
-    services::gps_service::start_gps_publication_thread(
-        shared_gps_config.clone(),
-        shared_gps_state.clone(),
-        ws_clients.clone(),
-        mqtt_manager.clone(),
-        gps_command_rx,
-    );
-
-    services::gyro_service::start_gyro_calculation_thread(shared_gyro_state.clone());
-    services::gyro_service::start_gyro_publication_thread(
-        shared_gyro_config.clone(),
-        shared_gyro_state.clone(),
-        ws_clients.clone(),
-        mqtt_manager.clone(),
-        gyro_command_rx,
-    );
+    registry.start(outputs);
 
     println!("✅ Background services running.");
 
     // API server
-    let ws_clients_for_api = ws_clients.clone();
-    let mqtt_manager_for_api = mqtt_manager.clone();
+    let tcp_ctx = TcpContext {
+        clients: tcp_clients.clone(),
+        sensors: registry.context(),
+    };
+    let registry_for_api = registry.clone();
 
     let api_server = HttpServer::new(move || {
         App::new()
-            .app_data(web::Data::new(shared_gps_config.clone()))
-            .app_data(web::Data::new(shared_gps_state.clone()))
-            .app_data(web::Data::new(shared_gyro_config.clone()))
-            .app_data(web::Data::new(shared_gyro_state.clone()))
-            .app_data(web::Data::new(ws_clients_for_api.clone()))
-            .app_data(web::Data::new(gps_command_tx.clone()))
-            .app_data(web::Data::new(gyro_command_tx.clone()))
-            .app_data(web::Data::new(mqtt_manager_for_api.clone()))
+            .app_data(web::Data::new(udp_output.clone()))
+            .app_data(web::Data::new(can_output.clone()))
+            .app_data(web::Data::new(recorder.clone()))
+            .app_data(web::Data::new(replayer.clone()))
+            .app_data(web::Data::new(nmea_log.clone()))
+            .app_data(web::Data::new(registry_for_api.context()))
             .wrap(
                 Cors::default()
                     .allow_any_origin()
@@ -84,8 +86,13 @@ async fn main() -> std::io::Result<()> {
                     ])
                     .max_age(3600),
             )
-            .configure(routes::gps_routes::init)
-            .configure(routes::gyro_routes::init)
+            .configure(|cfg| routes::sensor_routes::init(&registry_for_api, cfg))
+            .configure(|cfg| routes::vessel_routes::init(&registry_for_api, cfg))
+            .configure(routes::output_routes::init)
+            .configure(routes::signalk_routes::init)
+            .configure(routes::recording_routes::init)
+            .configure(routes::clock_routes::init)
+            .configure(|cfg| routes::seed_routes::init(&registry_for_api, cfg))
     })
     .bind("127.0.0.1:8080")?
     .run();
@@ -101,14 +108,26 @@ async fn main() -> std::io::Result<()> {
         }
     });
 
-    // TCP
-    let tcp_listener = TcpListener::bind("127.0.0.1:9000").await?;
-    tokio::spawn(async move {
-        println!("📡 TCP server started on tcp://127.0.0.1:9000");
-        while let Ok((socket, _)) = tcp_listener.accept().await {
-            tokio::spawn(handle_tcp_connection(socket));
-        }
-    });
+    // TCP (multiplexer NMEA/JSON/NMEA 2000; format default per port, bisa diganti dengan "FORMAT ...")
+    for (addr, format) in [
+        ("127.0.0.1:9000", StreamFormat::Nmea),
+        ("127.0.0.1:9001", StreamFormat::Json),
+        ("127.0.0.1:9002", StreamFormat::Ydraw),
+    ] {
+        let tcp_listener = TcpListener::bind(addr).await?;
+        let tcp_ctx = tcp_ctx.clone();
+        tokio::spawn(async move {
+            println!("📡 TCP server ({:?}) started on tcp://{}", format, addr);
+            while let Ok((socket, _)) = tcp_listener.accept().await {
+                tokio::spawn(handle_tcp_connection(socket, tcp_ctx.clone(), format));
+            }
+        });
+    }
+
+    let result = api_server.await;
+
+    // Hentikan loop MQTT setelah API server berhenti
+    registry.stop().await;
 
-    api_server.await
+    result
 }
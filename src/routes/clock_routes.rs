@@ -0,0 +1,12 @@
+use actix_web::web;
+use crate::controllers::clock_controller;
+
+pub fn init(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/api/clock")
+            .route("", web::get().to(clock_controller::get_clock))
+            .route("", web::patch().to(clock_controller::update_clock))
+            .route("", web::delete().to(clock_controller::reset_clock))
+            .route("/step", web::post().to(clock_controller::step_clock)),
+    );
+}
@@ -0,0 +1,12 @@
+use actix_web::{web, Scope};
+use crate::controllers::depth_controller;
+
+/// Grid batimetri di dalam scope `/api/depth`.
+pub fn grid(scope: Scope) -> Scope {
+    scope.service(
+        web::scope("/grid")
+            .route("", web::get().to(depth_controller::get_grid))
+            .route("", web::post().to(depth_controller::load_grid))
+            .route("", web::delete().to(depth_controller::delete_grid)),
+    )
+}
@@ -1,20 +1,15 @@
-use actix_web::web;
+use actix_web::{web, Scope};
 use crate::controllers::gps_controller;
 
-pub fn init(cfg: &mut web::ServiceConfig) {
-    cfg.service(
-        web::scope("/api/gps")
-            .route("", web::post().to(gps_controller::create_gps))
-            .route("", web::get().to(gps_controller::get_gps))
-            .route("", web::patch().to(gps_controller::update_gps))
-            .route("", web::delete().to(gps_controller::delete_gps))
-            
-            .service(
-                web::scope("/config")
-                    .route("", web::get().to(gps_controller::get_config))
-                    .route("", web::patch().to(gps_controller::post_config))
-                    .route("", web::post().to(gps_controller::post_config))
-                    .route("", web::delete().to(gps_controller::delete_config)),
-            ),
-    );
-}
\ No newline at end of file
+/// Perbandingan dengan posisi sebenarnya, serta rekaman dan ekspor track di dalam scope `/api/gps`.
+pub fn init(scope: Scope) -> Scope {
+    scope
+        .route("/truth", web::get().to(gps_controller::get_truth))
+        .service(
+            web::resource("/track")
+                .route(web::get().to(gps_controller::get_track))
+                .route(web::patch().to(gps_controller::update_track))
+                .route(web::delete().to(gps_controller::clear_track)),
+        )
+        .route("/track.{format}", web::get().to(gps_controller::export_track))
+}
@@ -1,11 +1,11 @@
+pub mod sensor_routes;
 pub mod gps_routes;
-// pub mod anemo_routes;
-// pub mod baro_routes;
-pub mod gyro_routes;
-// pub mod thermal_routes;
-
-// use actix_web::web;
-
-// pub fn init_routes(cfg: &mut web::ServiceConfig) {
-//     gps_routes::init(cfg);
-// }
\ No newline at end of file
+pub mod thermal_routes;
+pub mod depth_routes;
+pub mod vessel_routes;
+pub mod route_routes;
+pub mod output_routes;
+pub mod signalk_routes;
+pub mod recording_routes;
+pub mod clock_routes;
+pub mod seed_routes;
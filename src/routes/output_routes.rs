@@ -0,0 +1,22 @@
+use actix_web::web;
+use crate::controllers::output_controller;
+
+pub fn init(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/api/outputs")
+            .service(
+                web::scope("/udp")
+                    .route("", web::get().to(output_controller::get_udp))
+                    .route("", web::post().to(output_controller::create_udp))
+                    .route("", web::patch().to(output_controller::update_udp))
+                    .route("", web::delete().to(output_controller::delete_udp)),
+            )
+            .service(
+                web::scope("/can")
+                    .route("", web::get().to(output_controller::get_can))
+                    .route("", web::post().to(output_controller::create_can))
+                    .route("", web::patch().to(output_controller::update_can))
+                    .route("", web::delete().to(output_controller::delete_can)),
+            ),
+    );
+}
@@ -0,0 +1,26 @@
+use actix_web::web;
+use crate::controllers::recording_controller;
+
+pub fn init(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/api/recording")
+            .route("", web::get().to(recording_controller::get_recording))
+            .route("", web::post().to(recording_controller::start_recording))
+            .route("", web::delete().to(recording_controller::stop_recording)),
+    )
+    .service(
+        web::scope("/api/replay")
+            .route("", web::get().to(recording_controller::get_replay))
+            .route("", web::post().to(recording_controller::start_replay))
+            .route("", web::patch().to(recording_controller::update_replay))
+            .route("", web::delete().to(recording_controller::stop_replay)),
+    )
+    .service(
+        web::scope("/api/nmea-log")
+            .route("", web::get().to(recording_controller::get_nmea_log))
+            .route("", web::post().to(recording_controller::start_nmea_log))
+            .route("", web::patch().to(recording_controller::update_nmea_log))
+            .route("", web::delete().to(recording_controller::stop_nmea_log))
+            .route("/overrides", web::delete().to(recording_controller::clear_nmea_log_overrides)),
+    );
+}
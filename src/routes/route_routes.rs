@@ -0,0 +1,14 @@
+use actix_web::{web, Scope};
+use crate::controllers::route_controller;
+
+/// Batas ukuran file rute/track yang diimpor
+const IMPORT_LIMIT_BYTES: usize = 16 * 1024 * 1024;
+
+/// Impor GPX/KML/RTZ di dalam scope `/api/route`.
+pub fn import(scope: Scope) -> Scope {
+    scope.service(
+        web::resource("/import")
+            .app_data(web::PayloadConfig::new(IMPORT_LIMIT_BYTES))
+            .route(web::post().to(route_controller::import_route)),
+    )
+}
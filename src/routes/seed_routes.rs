@@ -0,0 +1,13 @@
+use actix_web::web;
+use crate::controllers::seed_controller;
+use crate::services::registry::SensorRegistry;
+
+/// Master seed noise di scope `/api/seed`; nama sensor dari registry untuk daftar stream.
+pub fn init(registry: &SensorRegistry, cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/api/seed")
+            .app_data(web::Data::new(registry.names()))
+            .route("", web::get().to(seed_controller::get_seed))
+            .route("", web::patch().to(seed_controller::update_seed)),
+    );
+}
@@ -0,0 +1,37 @@
+use actix_web::{web, Scope};
+use crate::controllers::{fault_controller, sensor_controller};
+use crate::services::registry::{SensorHandle, SensorRegistry};
+use crate::services::sensor::Sensor;
+use std::sync::Arc;
+
+/// Semua sensor di registry, masing-masing di scope `/api/{NAME}`.
+pub fn init(registry: &SensorRegistry, cfg: &mut web::ServiceConfig) {
+    registry.configure_routes(cfg);
+}
+
+/// Scope REST satu sensor; handle sensor dipasang sebagai app_data di level scope.
+pub fn scope<S: Sensor>(handle: Arc<SensorHandle<S>>) -> Scope {
+    let scope = web::scope(&format!("/api/{}", S::NAME))
+        .app_data(web::Data::from(handle))
+        .route("", web::post().to(sensor_controller::create_sensor::<S>))
+        .route("", web::get().to(sensor_controller::get_sensor::<S>))
+        .route("", web::patch().to(sensor_controller::update_sensor::<S>))
+        .route("", web::delete().to(sensor_controller::delete_sensor::<S>))
+        .route("/status", web::get().to(sensor_controller::get_status::<S>))
+
+        .service(
+            web::scope("/config")
+                .route("", web::get().to(sensor_controller::get_config::<S>))
+                .route("", web::patch().to(sensor_controller::post_config::<S>))
+                .route("", web::post().to(sensor_controller::post_config::<S>))
+                .route("", web::delete().to(sensor_controller::delete_config::<S>)),
+        )
+        .service(
+            web::scope("/faults")
+                .route("", web::get().to(fault_controller::get_faults::<S>))
+                .route("", web::post().to(fault_controller::create_fault::<S>))
+                .route("", web::delete().to(fault_controller::delete_faults::<S>))
+                .route("/{id}", web::delete().to(fault_controller::delete_fault::<S>)),
+        );
+    S::routes(scope)
+}
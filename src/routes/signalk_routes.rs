@@ -0,0 +1,6 @@
+use actix_web::web;
+use crate::controllers::signalk_controller;
+
+pub fn init(cfg: &mut web::ServiceConfig) {
+    cfg.route("/signalk", web::get().to(signalk_controller::get_discovery));
+}
@@ -0,0 +1,14 @@
+use actix_web::{web, Scope};
+use crate::controllers::thermal_controller;
+
+/// CRUD per channel di dalam scope `/api/thermal`.
+pub fn channels(scope: Scope) -> Scope {
+    scope.service(
+        web::scope("/channels")
+            .route("", web::get().to(thermal_controller::get_channels))
+            .route("", web::post().to(thermal_controller::create_channel))
+            .route("/{name}", web::get().to(thermal_controller::get_channel))
+            .route("/{name}", web::patch().to(thermal_controller::update_channel))
+            .route("/{name}", web::delete().to(thermal_controller::delete_channel)),
+    )
+}
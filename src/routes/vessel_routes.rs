@@ -0,0 +1,15 @@
+use actix_web::web;
+use crate::controllers::vessel_controller;
+use crate::services::registry::SensorRegistry;
+
+/// Model kapal bersama di scope `/api/vessel`.
+pub fn init(registry: &SensorRegistry, cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/api/vessel")
+            .app_data(web::Data::from(registry.context().vessel()))
+            .route("", web::post().to(vessel_controller::create_vessel))
+            .route("", web::get().to(vessel_controller::get_vessel))
+            .route("", web::patch().to(vessel_controller::update_vessel))
+            .route("", web::delete().to(vessel_controller::delete_vessel)),
+    );
+}
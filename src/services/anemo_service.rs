@@ -0,0 +1,100 @@
+use crate::data::anemo_data::{AnemoOptions, AnemoSentence, AnemoState, CreateAnemoRequest, UpdateAnemoRequest};
+use crate::services::gps_service::Gps;
+use crate::services::gyro_service::Gyro;
+use crate::services::sensor::{Sensor, SensorContext, SensorOptions};
+use crate::utils;
+use crate::utils::anemo_calculate;
+use crate::utils::n2k::{N2kMessage, DEFAULT_ANEMO_SOURCE};
+use crate::utils::nmea::DEFAULT_ANEMO_TALKER;
+use crate::utils::signalk::Delta;
+use crate::utils::sim_clock;
+use crate::utils::sim_rng;
+
+/// 🔹 Sensor anemometer: angin sebenarnya dengan hembusan dan pergeseran arah,
+/// angin semu dihitung dari SOG/COG GPS dan heading gyro
+pub struct Anemo;
+
+impl Sensor for Anemo {
+    const NAME: &'static str = "anemo";
+    const LABEL: &'static str = "Anemo";
+    const DEFAULT_TOPIC: &'static str = "vessel/anemo";
+    const DEFAULT_TALKER: &'static str = DEFAULT_ANEMO_TALKER;
+    const DEFAULT_N2K_SOURCE: u8 = DEFAULT_ANEMO_SOURCE;
+    const FAULT_FIELDS: &'static [&'static str] = &["apparent_wind_speed", "apparent_wind_angle", "wind_speed", "wind_direction", "true_wind_angle"];
+
+    type State = AnemoState;
+    type Options = AnemoOptions;
+    type CreateRequest = CreateAnemoRequest;
+    type UpdateRequest = UpdateAnemoRequest;
+
+    fn create(req: CreateAnemoRequest) -> AnemoState {
+        let mut state = AnemoState {
+            true_wind_speed: req.true_wind_speed,
+            true_wind_direction: req.true_wind_direction.rem_euclid(360.0),
+            gust_amplitude: req.gust_amplitude,
+            direction_shift: req.direction_shift,
+            wind_speed: req.true_wind_speed,
+            wind_direction: req.true_wind_direction.rem_euclid(360.0),
+            true_wind_angle: 0.0,
+            apparent_wind_speed: 0.0,
+            apparent_wind_angle: 0.0,
+            is_running: req.is_running,
+            last_update: sim_clock::now(),
+            calculation_rate_ms: 100,
+            gust_offset: 0.0,
+            shift_offset: 0.0,
+        };
+        // Kapal dianggap diam sampai langkah kalkulasi pertama membaca GPS/gyro
+        anemo_calculate::update_relative_wind(&mut state, None, None);
+        state
+    }
+
+    fn update(anemo_state: &mut AnemoState, patch: UpdateAnemoRequest) {
+        if let Some(speed) = patch.true_wind_speed { anemo_state.true_wind_speed = speed; }
+        if let Some(direction) = patch.true_wind_direction { anemo_state.true_wind_direction = direction.rem_euclid(360.0); }
+        if let Some(gust) = patch.gust_amplitude { anemo_state.gust_amplitude = gust; }
+        if let Some(shift) = patch.direction_shift { anemo_state.direction_shift = shift; }
+        if let Some(is_running) = patch.is_running { anemo_state.is_running = is_running; }
+        anemo_state.last_update = sim_clock::now();
+    }
+
+    fn is_running(state: &AnemoState) -> bool {
+        state.is_running
+    }
+
+    fn step(state: &mut AnemoState, ctx: &SensorContext) {
+        let (gps, gyro) = (ctx.get::<Gps>(), ctx.get::<Gyro>());
+        sim_rng::with_stream(Self::NAME, |rng| anemo_calculate::calculate_next_anemo_state(state, gps.as_ref(), gyro.as_ref(), rng));
+    }
+
+    fn encode_nmea(state: &AnemoState, talker: &str, options: &AnemoOptions, ctx: &SensorContext) -> Vec<String> {
+        let sentences = options.nmea_sentences.clone().unwrap_or_else(|| AnemoSentence::ALL.to_vec());
+        let variation = ctx.get::<Gps>().map(|gps| gps.variation);
+        utils::anemo_nmea::encode_anemo_sentences(state, talker, &sentences, variation)
+    }
+
+    fn encode_n2k(state: &AnemoState, source: u8, _options: &AnemoOptions, _ctx: &SensorContext) -> Vec<N2kMessage> {
+        utils::n2k::encode_anemo_pgns(state, source)
+    }
+
+    fn signalk_delta(state: &AnemoState) -> Option<Delta> {
+        Some(utils::signalk::anemo_delta(state))
+    }
+
+    fn fault_values<'a>(state: &'a mut AnemoState, field: &str) -> Vec<&'a mut f64> {
+        match field {
+            "apparent_wind_speed" => vec![&mut state.apparent_wind_speed],
+            "apparent_wind_angle" => vec![&mut state.apparent_wind_angle],
+            "wind_speed" => vec![&mut state.wind_speed],
+            "wind_direction" => vec![&mut state.wind_direction],
+            "true_wind_angle" => vec![&mut state.true_wind_angle],
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl SensorOptions for AnemoOptions {
+    fn merge(&mut self, patch: Self) {
+        self.nmea_sentences = patch.nmea_sentences.or_else(|| self.nmea_sentences.clone());
+    }
+}
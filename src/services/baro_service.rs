@@ -0,0 +1,105 @@
+use crate::data::baro_data::{BaroOptions, BaroSentence, BaroState, CreateBaroRequest, UpdateBaroRequest};
+use crate::services::anemo_service::Anemo;
+use crate::services::gps_service::Gps;
+use crate::services::sensor::{Sensor, SensorContext, SensorOptions};
+use crate::utils;
+use crate::utils::baro_calculate;
+use crate::utils::n2k::{N2kMessage, DEFAULT_BARO_SOURCE};
+use crate::utils::nmea::DEFAULT_BARO_TALKER;
+use crate::utils::signalk::Delta;
+use crate::utils::sim_clock;
+use crate::utils::sim_rng;
+
+/// 🔹 Sensor barometer: tekanan, suhu udara, dan kelembapan mengikuti tren sinoptik,
+/// variasi harian (jam lokal dari bujur GPS), dan noise
+pub struct Baro;
+
+impl Sensor for Baro {
+    const NAME: &'static str = "baro";
+    const LABEL: &'static str = "Baro";
+    const DEFAULT_TOPIC: &'static str = "vessel/baro";
+    const DEFAULT_TALKER: &'static str = DEFAULT_BARO_TALKER;
+    const DEFAULT_N2K_SOURCE: u8 = DEFAULT_BARO_SOURCE;
+    const FAULT_FIELDS: &'static [&'static str] = &["pressure", "air_temperature", "relative_humidity", "dew_point"];
+
+    type State = BaroState;
+    type Options = BaroOptions;
+    type CreateRequest = CreateBaroRequest;
+    type UpdateRequest = UpdateBaroRequest;
+
+    fn create(req: CreateBaroRequest) -> BaroState {
+        let mut state = BaroState {
+            base_pressure: req.base_pressure,
+            base_temperature: req.base_temperature,
+            base_humidity: req.base_humidity.clamp(0.0, 100.0),
+            trend: req.trend,
+            diurnal: req.diurnal,
+            noise: req.noise.max(0.0),
+            pressure: req.base_pressure,
+            air_temperature: req.base_temperature,
+            relative_humidity: req.base_humidity,
+            dew_point: req.base_temperature,
+            pressure_tendency: 0.0,
+            elapsed_hours: 0.0,
+            is_running: req.is_running,
+            last_update: sim_clock::now(),
+            calculation_rate_ms: 100,
+        };
+        sim_rng::with_stream(Self::NAME, |rng| baro_calculate::update_baro_values(&mut state, None, rng));
+        state
+    }
+
+    fn update(baro_state: &mut BaroState, patch: UpdateBaroRequest) {
+        if let Some(pressure) = patch.base_pressure { baro_state.base_pressure = pressure; }
+        if let Some(temperature) = patch.base_temperature { baro_state.base_temperature = temperature; }
+        if let Some(humidity) = patch.base_humidity { baro_state.base_humidity = humidity.clamp(0.0, 100.0); }
+        if let Some(trend) = patch.trend {
+            baro_state.trend = trend;
+            baro_state.elapsed_hours = 0.0;
+        }
+        if let Some(diurnal) = patch.diurnal { baro_state.diurnal = diurnal; }
+        if let Some(noise) = patch.noise { baro_state.noise = noise.max(0.0); }
+        if let Some(is_running) = patch.is_running { baro_state.is_running = is_running; }
+        baro_state.last_update = sim_clock::now();
+    }
+
+    fn is_running(state: &BaroState) -> bool {
+        state.is_running
+    }
+
+    fn step(state: &mut BaroState, ctx: &SensorContext) {
+        let longitude = ctx.get::<Gps>().map(|gps| gps.truth.longitude);
+        sim_rng::with_stream(Self::NAME, |rng| baro_calculate::calculate_next_baro_state(state, longitude, rng));
+    }
+
+    fn encode_nmea(state: &BaroState, talker: &str, options: &BaroOptions, ctx: &SensorContext) -> Vec<String> {
+        let sentences = options.nmea_sentences.clone().unwrap_or_else(|| BaroSentence::ALL.to_vec());
+        let wind = ctx.get::<Anemo>();
+        let variation = ctx.get::<Gps>().map(|gps| gps.variation);
+        utils::baro_nmea::encode_baro_sentences(state, talker, &sentences, wind.as_ref(), variation)
+    }
+
+    fn encode_n2k(state: &BaroState, source: u8, _options: &BaroOptions, _ctx: &SensorContext) -> Vec<N2kMessage> {
+        utils::n2k::encode_baro_pgns(state, source)
+    }
+
+    fn signalk_delta(state: &BaroState) -> Option<Delta> {
+        Some(utils::signalk::baro_delta(state))
+    }
+
+    fn fault_values<'a>(state: &'a mut BaroState, field: &str) -> Vec<&'a mut f64> {
+        match field {
+            "pressure" => vec![&mut state.pressure],
+            "air_temperature" => vec![&mut state.air_temperature],
+            "relative_humidity" => vec![&mut state.relative_humidity],
+            "dew_point" => vec![&mut state.dew_point],
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl SensorOptions for BaroOptions {
+    fn merge(&mut self, patch: Self) {
+        self.nmea_sentences = patch.nmea_sentences.or_else(|| self.nmea_sentences.clone());
+    }
+}
@@ -0,0 +1,110 @@
+use crate::data::depth_data::{
+    CreateDepthRequest, DepthOptions, DepthSentence, DepthSource, DepthState, UpdateDepthRequest,
+};
+use crate::routes::depth_routes;
+use crate::services::gps_service::Gps;
+use crate::services::sensor::{Sensor, SensorContext, SensorOptions};
+use crate::utils;
+use crate::utils::depth_calculate;
+use crate::utils::n2k::{N2kMessage, DEFAULT_DEPTH_SOURCE};
+use crate::utils::nmea::DEFAULT_DEPTH_TALKER;
+use crate::utils::signalk::Delta;
+use crate::utils::sim_clock;
+use crate::utils::sim_rng;
+use actix_web::Scope;
+
+/// 🔹 Depth sounder: kedalaman dari grid batimetri di posisi GPS (fallback kedalaman konstan),
+/// dengan offset transduser/lunas, noise, serta mode echo palsu dan dropout
+pub struct Depth;
+
+impl Sensor for Depth {
+    const NAME: &'static str = "depth";
+    const LABEL: &'static str = "Depth";
+    const DEFAULT_TOPIC: &'static str = "vessel/depth";
+    const DEFAULT_TALKER: &'static str = DEFAULT_DEPTH_TALKER;
+    const DEFAULT_N2K_SOURCE: u8 = DEFAULT_DEPTH_SOURCE;
+    const FAULT_FIELDS: &'static [&'static str] = &["depth"];
+
+    type State = DepthState;
+    type Options = DepthOptions;
+    type CreateRequest = CreateDepthRequest;
+    type UpdateRequest = UpdateDepthRequest;
+
+    fn create(req: CreateDepthRequest) -> DepthState {
+        let mut state = DepthState {
+            constant_depth: req.constant_depth.max(0.0),
+            transducer_offset: req.transducer_offset,
+            keel_offset: req.keel_offset,
+            noise: req.noise.max(0.0),
+            max_range: req.max_range.max(1.0),
+            echo_mode: req.echo_mode,
+            water_depth: req.constant_depth,
+            depth_source: DepthSource::Constant,
+            depth_below_transducer: None,
+            depth_below_surface: None,
+            depth_below_keel: None,
+            false_echo: false,
+            grid: None,
+            is_running: req.is_running,
+            last_update: sim_clock::now(),
+            bathymetry: None,
+        };
+        sim_rng::with_stream(Self::NAME, |rng| depth_calculate::calculate_next_depth_state(&mut state, None, rng));
+        state
+    }
+
+    fn update(depth_state: &mut DepthState, patch: UpdateDepthRequest) {
+        if let Some(depth) = patch.constant_depth { depth_state.constant_depth = depth.max(0.0); }
+        if let Some(offset) = patch.transducer_offset { depth_state.transducer_offset = offset; }
+        if let Some(offset) = patch.keel_offset { depth_state.keel_offset = offset; }
+        if let Some(noise) = patch.noise { depth_state.noise = noise.max(0.0); }
+        if let Some(range) = patch.max_range { depth_state.max_range = range.max(1.0); }
+        if let Some(mode) = patch.echo_mode { depth_state.echo_mode = mode; }
+        if let Some(is_running) = patch.is_running { depth_state.is_running = is_running; }
+        depth_state.last_update = sim_clock::now();
+    }
+
+    fn is_running(state: &DepthState) -> bool {
+        state.is_running
+    }
+
+    fn step(state: &mut DepthState, ctx: &SensorContext) {
+        // Kedalaman fisik mengikuti posisi sebenarnya, bukan posisi terukur yang mengandung error GNSS
+        let position = ctx.get::<Gps>().map(|gps| (gps.truth.latitude, gps.truth.longitude));
+        sim_rng::with_stream(Self::NAME, |rng| depth_calculate::calculate_next_depth_state(state, position, rng));
+    }
+
+    fn encode_nmea(state: &DepthState, talker: &str, options: &DepthOptions, _ctx: &SensorContext) -> Vec<String> {
+        let sentences = options.nmea_sentences.clone().unwrap_or_else(|| DepthSentence::ALL.to_vec());
+        utils::depth_nmea::encode_depth_sentences(state, talker, &sentences)
+    }
+
+    fn encode_n2k(state: &DepthState, source: u8, _options: &DepthOptions, _ctx: &SensorContext) -> Vec<N2kMessage> {
+        utils::n2k::encode_depth_pgns(state, source)
+    }
+
+    fn signalk_delta(state: &DepthState) -> Option<Delta> {
+        Some(utils::signalk::depth_delta(state))
+    }
+
+    fn fault_values<'a>(state: &'a mut DepthState, field: &str) -> Vec<&'a mut f64> {
+        match field {
+            // Ketiga kedalaman terukur diganggu bersama agar tetap konsisten
+            "depth" => [&mut state.depth_below_transducer, &mut state.depth_below_surface, &mut state.depth_below_keel]
+                .into_iter()
+                .filter_map(Option::as_mut)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn routes(scope: Scope) -> Scope {
+        depth_routes::grid(scope)
+    }
+}
+
+impl SensorOptions for DepthOptions {
+    fn merge(&mut self, patch: Self) {
+        self.nmea_sentences = patch.nmea_sentences.or_else(|| self.nmea_sentences.clone());
+    }
+}
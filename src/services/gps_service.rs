@@ -1,88 +1,183 @@
-use crate::data::gps_data::{SharedGpsConfig, SharedGpsState};
-use crate::utils::mqtt_manager::{MqttManager, MqttCommand};
+use crate::data::gps_data::{CreateGpsRequest, GnssError, GpsOptions, GpsOverrides, GpsSentence, GpsState, GpsTruth, UpdateGpsRequest};
+use crate::data::sensor_data::DataSource;
+use crate::data::track_data::{SharedTrack, TrackPoint};
+use crate::routes::gps_routes;
+use crate::services::gyro_service::Gyro;
+use crate::services::sensor::{Sensor, SensorContext, SensorOptions};
+use crate::services::speedlog_service::SpeedLog;
 use crate::utils;
-use crate::utils::net::Clients;
-use std::sync::Arc;
-use std::time::Duration;
-use std::thread;
-use tokio::sync::mpsc;
-use tokio::select;
-use tokio::time::sleep;
-
-const CALCULATION_INTERVAL_MS: u64 = 100;
-
-/// 🔹 Thread perhitungan GPS (lokal, non-async)
-pub fn start_gps_calculation_thread(state: SharedGpsState) {
-    let state_clone = Arc::clone(&state);
-    thread::spawn(move || {
-        loop {
-            thread::sleep(Duration::from_millis(CALCULATION_INTERVAL_MS));
-            let mut guard = state_clone.write().unwrap();
-            if let Some(ref mut gps_state) = *guard {
-                if gps_state.is_running {
-                    utils::gps_calculate::calculate_next_gps_state(gps_state);
-                }
+use crate::utils::gnss_error;
+use crate::utils::gnss_sky;
+use crate::utils::gps_calculate;
+use crate::utils::n2k::{N2kMessage, DEFAULT_GPS_SOURCE};
+use crate::utils::nmea_input;
+use crate::utils::nmea::DEFAULT_GPS_TALKER;
+use crate::utils::signalk::Delta;
+use crate::utils::speedlog_calculate;
+use crate::utils::track_recorder;
+use crate::utils::sim_clock;
+use crate::utils::sim_rng;
+use actix_web::Scope;
+
+/// 🔹 Sensor GPS: posisi bergerak sesuai SOG/COG (dead reckoning).
+/// Jika model kapal ada, posisi/SOG/COG diambil dari model tersebut; jika tidak dan speed log
+/// berjalan, SOG/COG dihitung dari STW, heading gyro, leeway, dan arus. Setiap langkah
+/// direkam ke track (dengan desimasi) untuk diekspor. Selama log NMEA diputar, state diisi dari log.
+/// Nilai yang dilaporkan membawa error GNSS (bias, noise, multipath) sesuai kualitas fix;
+/// posisi sebenarnya disimpan di `truth`.
+pub struct Gps;
+
+impl Sensor for Gps {
+    const NAME: &'static str = "gps";
+    const LABEL: &'static str = "GPS";
+    const DEFAULT_TOPIC: &'static str = "vessel/gps";
+    const DEFAULT_TALKER: &'static str = DEFAULT_GPS_TALKER;
+    const DEFAULT_N2K_SOURCE: u8 = DEFAULT_GPS_SOURCE;
+    const FAULT_FIELDS: &'static [&'static str] = &["latitude", "longitude", "sog", "cog", "hdop"];
+
+    type State = GpsState;
+    type Options = GpsOptions;
+    type CreateRequest = CreateGpsRequest;
+    type UpdateRequest = UpdateGpsRequest;
+
+    fn create(req: CreateGpsRequest) -> GpsState {
+        let initial_last_update = sim_clock::now();
+        let initial_variation = gps_calculate::calculate_magnetic_variation(req.latitude, req.longitude, &initial_last_update);
+
+        GpsState {
+            latitude: req.latitude, longitude: req.longitude,
+            sog: req.sog, cog: req.cog,
+            is_running: req.is_running,
+            variation: initial_variation,
+            fix_quality: req.fix_quality,
+            hdop: req.hdop.max(0.0),
+            error_model: req.error_model,
+            constellation: req.constellation,
+            sky: None,
+            last_update: initial_last_update,
+            calculation_rate_ms: 100,
+            track: SharedTrack::default(),
+            source: DataSource::Model,
+            overrides: GpsOverrides::default(),
+            truth: GpsTruth { latitude: req.latitude, longitude: req.longitude, sog: req.sog, cog: req.cog },
+            error: GnssError::default(),
+        }
+    }
+
+    fn update(gps_state: &mut GpsState, patch: UpdateGpsRequest) {
+        // Selama diputar dari log NMEA, field yang di-PATCH tetap menimpa data log
+        if gps_state.source == DataSource::NmeaLog {
+            let overrides = &mut gps_state.overrides;
+            overrides.latitude = patch.latitude.or(overrides.latitude);
+            overrides.longitude = patch.longitude.or(overrides.longitude);
+            overrides.sog = patch.sog.or(overrides.sog);
+            overrides.cog = patch.cog.or(overrides.cog);
+        }
+        let truth = &mut gps_state.truth;
+        if let Some(lat) = patch.latitude { truth.latitude = lat; }
+        if let Some(lon) = patch.longitude { truth.longitude = lon; }
+        if let Some(sog) = patch.sog { truth.sog = sog; }
+        if let Some(cog) = patch.cog { truth.cog = cog; }
+        if patch.latitude.is_some() || patch.longitude.is_some() || patch.sog.is_some() || patch.cog.is_some() {
+            gps_state.report_truth();
+        }
+        if let Some(quality) = patch.fix_quality { gps_state.fix_quality = quality; }
+        if let Some(hdop) = patch.hdop { gps_state.hdop = hdop.max(0.0); }
+        if let Some(model) = patch.error_model { gps_state.error_model = model; }
+        if let Some(constellation) = patch.constellation { gps_state.constellation = constellation; }
+        if let Some(is_running) = patch.is_running { gps_state.is_running = is_running; }
+        gps_state.last_update = sim_clock::now();
+    }
+
+    fn check_update(state: &GpsState, patch: &UpdateGpsRequest, ctx: &SensorContext) -> Result<(), String> {
+        let moves = patch.latitude.is_some() || patch.longitude.is_some() || patch.sog.is_some() || patch.cog.is_some();
+        if moves && state.source == DataSource::Model && ctx.vessel_state().is_some() {
+            return Err(nmea_input::VESSEL_OWNS_MOTION.to_string());
+        }
+        Ok(())
+    }
+
+    fn is_running(state: &GpsState) -> bool {
+        state.is_running
+    }
+
+    fn step(state: &mut GpsState, ctx: &SensorContext) {
+        let vessel = ctx.vessel_state();
+        if state.source == DataSource::NmeaLog {
+            // Posisi dan gerak diisi oleh pemutar log NMEA
+        } else if let Some(vessel) = &vessel {
+            gps_calculate::sample_vessel(state, vessel);
+        } else {
+            if let Some(log) = ctx.get::<SpeedLog>().filter(|log| log.is_running) {
+                let heading = ctx.get::<Gyro>().map(|gyro| gyro.yaw).unwrap_or(log.heading);
+                (state.truth.sog, state.truth.cog) = speedlog_calculate::ground_velocity(&log, heading);
             }
+            gps_calculate::calculate_next_gps_state(state);
         }
-    });
-}
 
-/// 🔹 Thread publikasi GPS ke MQTT + WebSocket
-pub fn start_gps_publication_thread(
-    config_state: SharedGpsConfig,
-    data_state: SharedGpsState,
-    ws_clients: Clients,
-    mqtt_manager: Arc<MqttManager>,
-    mut command_rx: mpsc::Receiver<MqttCommand>,
-) {
-    tokio::spawn(async move {
-        loop {
-            // snapshot config
-            let (update_rate, topic_prefix) = {
-                let cfg = config_state.read().unwrap();
-                let ur = cfg.update_rate.unwrap_or(1000);
-                // ambil topic pertama atau default
-                let tp = cfg.topics.first().cloned().unwrap_or_else(|| "vessel/gps".to_string());
-                (ur, tp)
-            };
-
-            select! {
-                Some(cmd) = command_rx.recv() => {
-                    match cmd {
-                        MqttCommand::Reconnect => {
-                            tracing::info!("[GPS Service]: Reconnect requested.");
-                        }
-                        MqttCommand::Stop => {
-                            tracing::info!("[GPS Service]: Stop requested. Exiting publication loop.");
-                            break;
-                        }
-                    }
-                }
-
-                _ = sleep(Duration::from_millis(update_rate)) => {
-                    let data_opt = { data_state.read().unwrap().clone() };
-
-                    if let Some(gps_state) = data_opt {
-                        if gps_state.is_running {
-                            let payload = match serde_json::to_string(&gps_state) {
-                                Ok(p) => p,
-                                Err(e) => { eprintln!("[GPS Service]: JSON serialize error: {}", e); continue; }
-                            };
-                            let topic = format!("{}/data", topic_prefix);
-
-                            if let Err(e) = mqtt_manager.publish_message(&[topic.clone()], payload.clone()).await {
-                                eprintln!("[GPS Service]: MQTT publish error to {}: {:?}", topic, e);
-                            }
-
-                            let msg = serde_json::json!({ "type": "gps_update", "data": gps_state });
-                            let json = msg.to_string();
-                            utils::net::broadcast_ws_message(&ws_clients, json).await.ok();
-                        }
-                    }
-                }
+        let gyro = ctx.get::<Gyro>();
+        let heading = gyro.as_ref().map(|gyro| gyro.yaw).or(vessel.map(|vessel| vessel.heading));
+
+        sim_rng::with_stream(Self::NAME, |rng| {
+            // Satelit dari posisi sebenarnya; mask sektor mengikuti haluan (atau COG tanpa gyro/model kapal)
+            state.sky = state.constellation.enabled.then(|| {
+                let truth = state.truth;
+                gnss_sky::compute_sky(&state.constellation, truth.latitude, truth.longitude, heading.unwrap_or(truth.cog), &state.last_update, rng)
+            });
+            if let Some(sky) = &state.sky {
+                state.hdop = sky.hdop;
             }
+            // Data log NMEA sudah berasal dari penerima nyata, jadi tanpa error tambahan
+            if state.source == DataSource::Model {
+                gnss_error::apply_gnss_errors(state, rng);
+            } else {
+                (state.error.north, state.error.east) = (0.0, 0.0);
+            }
+        });
+
+        track_recorder::record(&state.track, TrackPoint {
+            time: state.last_update,
+            latitude: state.latitude,
+            longitude: state.longitude,
+            sog: state.sog,
+            cog: state.cog,
+            heading,
+            roll: gyro.as_ref().map(|gyro| gyro.roll),
+            pitch: gyro.as_ref().map(|gyro| gyro.pitch),
+        });
+    }
+
+    fn encode_nmea(state: &GpsState, talker: &str, options: &GpsOptions, _ctx: &SensorContext) -> Vec<String> {
+        let sentences = options.nmea_sentences.clone().unwrap_or_else(|| GpsSentence::ALL.to_vec());
+        utils::gps_nmea::encode_gps_sentences(state, talker, &sentences)
+    }
+
+    fn encode_n2k(state: &GpsState, source: u8, _options: &GpsOptions, _ctx: &SensorContext) -> Vec<N2kMessage> {
+        utils::n2k::encode_gps_pgns(state, source)
+    }
+
+    fn signalk_delta(state: &GpsState) -> Option<Delta> {
+        Some(utils::signalk::gps_delta(state))
+    }
+
+    fn fault_values<'a>(state: &'a mut GpsState, field: &str) -> Vec<&'a mut f64> {
+        match field {
+            "latitude" => vec![&mut state.latitude],
+            "longitude" => vec![&mut state.longitude],
+            "sog" => vec![&mut state.sog],
+            "cog" => vec![&mut state.cog],
+            "hdop" => vec![&mut state.hdop],
+            _ => Vec::new(),
         }
+    }
+
+    fn routes(scope: Scope) -> Scope {
+        gps_routes::init(scope)
+    }
+}
 
-        tracing::info!("[GPS Service]: Publication thread exited.");
-    });
+impl SensorOptions for GpsOptions {
+    fn merge(&mut self, patch: Self) {
+        self.nmea_sentences = patch.nmea_sentences.or_else(|| self.nmea_sentences.clone());
+    }
 }
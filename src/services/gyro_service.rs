@@ -1,87 +1,124 @@
-use crate::data::gyro_data::{SharedGyroConfig, SharedGyroState};
-use crate::utils::mqtt_manager::{MqttManager, MqttCommand};
+use crate::data::gyro_data::{
+    CreateGyroRequest, GyroOptions, GyroOverrides, GyroSentence, GyroState, UpdateGyroRequest,
+};
+use crate::data::sensor_data::DataSource;
+use crate::services::gps_service::Gps;
+use crate::services::sensor::{Sensor, SensorContext, SensorOptions};
 use crate::utils;
-use crate::utils::net::Clients;
-use std::sync::Arc;
-use std::time::Duration;
-use std::thread;
-use tokio::sync::mpsc;
-use tokio::time::sleep;
-use tokio::select;
+use crate::utils::gps_calculate;
+use crate::utils::n2k::{N2kMessage, DEFAULT_GYRO_SOURCE};
+use crate::utils::nmea::DEFAULT_GYRO_TALKER;
+use crate::utils::nmea_input;
+use crate::utils::signalk::Delta;
+use crate::utils::sim_clock;
+use crate::utils::sim_rng;
 
-const CALCULATION_INTERVAL_MS: u64 = 100;
+/// 🔹 Sensor gyro: heading berputar sesuai yaw_rate (atau mengikuti model kapal jika ada),
+/// roll/pitch mengikuti gelombang. Selama log NMEA diputar, heading/ROT diisi dari log.
+pub struct Gyro;
 
-/// 🔹 Thread kalkulasi Gyro (lokal, non-async)
-pub fn start_gyro_calculation_thread(state: SharedGyroState) {
-    let state_clone = Arc::clone(&state);
-    thread::spawn(move || {
-        loop {
-            thread::sleep(Duration::from_millis(CALCULATION_INTERVAL_MS));
-            let mut guard = state_clone.write().unwrap();
-            if let Some(ref mut gyro_state) = *guard {
-                if gyro_state.is_running {
-                    utils::gyro_calculate::calculate_next_gyro_state(gyro_state);
-                }
-            }
-        }
-    });
+/// Variasi magnetik di posisi GPS saat ini (untuk HDG dan PGN 127250), `None` jika GPS belum dibuat.
+fn magnetic_variation(state: &GyroState, ctx: &SensorContext) -> Option<f64> {
+    ctx.get::<Gps>()
+        .map(|gps| gps_calculate::calculate_magnetic_variation(gps.truth.latitude, gps.truth.longitude, &state.last_update))
 }
 
-/// 🔹 Thread publikasi Gyro ke MQTT + WebSocket
-pub fn start_gyro_publication_thread(
-    config_state: SharedGyroConfig,
-    data_state: SharedGyroState,
-    ws_clients: Clients,
-    mqtt_manager: Arc<MqttManager>,
-    mut command_rx: mpsc::Receiver<MqttCommand>,
-) {
-    tokio::spawn(async move {
-        loop {
-            // snapshot config
-            let (update_rate, topic_prefix) = {
-                let cfg = config_state.read().unwrap();
-                let ur = cfg.update_rate.unwrap_or(1000);
-                let tp = cfg.topics.first().cloned().unwrap_or_else(|| "vessel/gyro".to_string());
-                (ur, tp)
-            };
+impl Sensor for Gyro {
+    const NAME: &'static str = "gyro";
+    const LABEL: &'static str = "Gyro";
+    const DEFAULT_TOPIC: &'static str = "vessel/gyro";
+    const DEFAULT_TALKER: &'static str = DEFAULT_GYRO_TALKER;
+    const DEFAULT_N2K_SOURCE: u8 = DEFAULT_GYRO_SOURCE;
+    const FAULT_FIELDS: &'static [&'static str] = &["yaw", "pitch", "roll", "yaw_rate"];
+
+    type State = GyroState;
+    type Options = GyroOptions;
+    type CreateRequest = CreateGyroRequest;
+    type UpdateRequest = UpdateGyroRequest;
+
+    fn create(req: CreateGyroRequest) -> GyroState {
+        GyroState {
+            yaw: req.yaw,
+            pitch: req.pitch,
+            roll: req.roll,
+            yaw_rate: req.yaw_rate,
+            is_running: req.is_running,
+            last_update: sim_clock::now(),
+            calculation_rate_ms: 100,
+            source: DataSource::Model,
+            overrides: GyroOverrides::default(),
+        }
+    }
+
+    fn update(gyro_state: &mut GyroState, patch: UpdateGyroRequest) {
+        // Selama diputar dari log NMEA, field yang di-PATCH tetap menimpa data log
+        if gyro_state.source == DataSource::NmeaLog {
+            let overrides = &mut gyro_state.overrides;
+            overrides.yaw = patch.yaw.or(overrides.yaw);
+            overrides.pitch = patch.pitch.or(overrides.pitch);
+            overrides.roll = patch.roll.or(overrides.roll);
+            overrides.yaw_rate = patch.yaw_rate.or(overrides.yaw_rate);
+        }
+        if let Some(yaw) = patch.yaw { gyro_state.yaw = yaw; }
+        if let Some(pitch) = patch.pitch { gyro_state.pitch = pitch; }
+        if let Some(roll) = patch.roll { gyro_state.roll = roll; }
+        if let Some(yaw_rate) = patch.yaw_rate { gyro_state.yaw_rate = yaw_rate; }
+        if let Some(is_running) = patch.is_running { gyro_state.is_running = is_running; }
+        gyro_state.last_update = sim_clock::now();
+    }
+
+    fn check_update(state: &GyroState, patch: &UpdateGyroRequest, ctx: &SensorContext) -> Result<(), String> {
+        let turns = patch.yaw.is_some() || patch.yaw_rate.is_some();
+        if turns && state.source == DataSource::Model && ctx.vessel_state().is_some() {
+            return Err(nmea_input::VESSEL_OWNS_MOTION.to_string());
+        }
+        Ok(())
+    }
+
+    fn is_running(state: &GyroState) -> bool {
+        state.is_running
+    }
 
-            select! {
-                Some(cmd) = command_rx.recv() => {
-                    match cmd {
-                        MqttCommand::Reconnect => {
-                            tracing::info!("[Gyro Service]: Reconnect requested.");
-                        }
-                        MqttCommand::Stop => {
-                            tracing::info!("[Gyro Service]: Stop requested. Exiting publication loop.");
-                            break;
-                        }
-                    }
-                }
+    fn step(state: &mut GyroState, ctx: &SensorContext) {
+        // Heading dan ROT diisi oleh pemutar log NMEA
+        if state.source == DataSource::NmeaLog {
+            return;
+        }
+        match ctx.vessel_state() {
+            Some(vessel) => sim_rng::with_stream(Self::NAME, |rng| utils::gyro_calculate::sample_vessel(state, &vessel, rng)),
+            None => sim_rng::with_stream(Self::NAME, |rng| utils::gyro_calculate::calculate_next_gyro_state(state, rng)),
+        }
+    }
 
-                _ = sleep(Duration::from_millis(update_rate)) => {
-                    let data_opt = { data_state.read().unwrap().clone() };
+    fn encode_nmea(state: &GyroState, talker: &str, options: &GyroOptions, ctx: &SensorContext) -> Vec<String> {
+        let sentences = options.nmea_sentences.clone().unwrap_or_else(|| GyroSentence::ALL.to_vec());
+        let deviation = options.deviation.unwrap_or(0.0);
+        utils::gyro_nmea::encode_gyro_sentences(state, talker, &sentences, deviation, magnetic_variation(state, ctx))
+    }
 
-                    if let Some(gyro_state) = data_opt {
-                        if gyro_state.is_running {
-                            let payload = match serde_json::to_string(&gyro_state) {
-                                Ok(p) => p,
-                                Err(e) => { eprintln!("[Gyro Service]: JSON serialize error: {}", e); continue; }
-                            };
-                            let topic = format!("{}/data", topic_prefix);
+    fn encode_n2k(state: &GyroState, source: u8, options: &GyroOptions, ctx: &SensorContext) -> Vec<N2kMessage> {
+        let deviation = options.deviation.unwrap_or(0.0);
+        utils::n2k::encode_gyro_pgns(state, source, deviation, magnetic_variation(state, ctx))
+    }
 
-                            if let Err(e) = mqtt_manager.publish_message(&[topic.clone()], payload.clone()).await {
-                                eprintln!("[Gyro Service]: MQTT publish error to {}: {:?}", topic, e);
-                            }
+    fn signalk_delta(state: &GyroState) -> Option<Delta> {
+        Some(utils::signalk::gyro_delta(state))
+    }
 
-                            let msg = serde_json::json!({ "type": "gyro_update", "data": gyro_state });
-                            let json = msg.to_string();
-                            utils::net::broadcast_ws_message(&ws_clients, json).await.ok();
-                        }
-                    }
-                }
-            }
+    fn fault_values<'a>(state: &'a mut GyroState, field: &str) -> Vec<&'a mut f64> {
+        match field {
+            "yaw" => vec![&mut state.yaw],
+            "pitch" => vec![&mut state.pitch],
+            "roll" => vec![&mut state.roll],
+            "yaw_rate" => vec![&mut state.yaw_rate],
+            _ => Vec::new(),
         }
+    }
+}
 
-        tracing::info!("[Gyro Service]: Publication thread exited.");
-    });
+impl SensorOptions for GyroOptions {
+    fn merge(&mut self, patch: Self) {
+        self.nmea_sentences = patch.nmea_sentences.or_else(|| self.nmea_sentences.clone());
+        self.deviation = patch.deviation.or(self.deviation);
+    }
 }
@@ -1,5 +1,11 @@
+pub mod sensor;
+pub mod registry;
 pub mod gps_service;
-// pub mod anemo_service;
-// pub mod baro_service;
+pub mod anemo_service;
+pub mod baro_service;
 pub mod gyro_service;
-// pub mod thermal_service;
+pub mod thermal_service;
+pub mod depth_service;
+pub mod speedlog_service;
+pub mod vessel_service;
+pub mod route_service;
@@ -0,0 +1,311 @@
+use crate::data::fault_data::{FaultKind, SharedFaults};
+use crate::data::recording_data::{MqttMessage, RecordedMessage};
+use crate::data::sensor_data::{SensorConfig, SharedSensorConfig, SharedSensorState};
+use crate::routes::sensor_routes;
+use crate::services::sensor::{Sensor, SensorContext};
+use crate::utils::fault_injection;
+use crate::utils::mqtt_manager::{self, MqttCommand, MqttManager, MqttServiceConfig, MqttState};
+use crate::utils::net::{OutputFrame, Outputs};
+use crate::utils::sim_clock;
+use crate::utils::sim_rng;
+use crate::utils::vessel_calculate;
+use actix_web::web;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// State, config, fault, dan koneksi MQTT milik satu sensor; dipakai bersama oleh loop dan controller.
+pub struct SensorHandle<S: Sensor> {
+    pub config: SharedSensorConfig<S::Options>,
+    pub state: SharedSensorState<S::State>,
+    pub mqtt: Arc<MqttManager>,
+    /// Fault yang diterapkan pada output sensor, diatur lewat `/api/{NAME}/faults`
+    pub faults: SharedFaults,
+}
+
+/// Operasi registry yang tidak bergantung pada tipe sensor.
+trait RegisteredSensor: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn step(&self, ctx: &SensorContext);
+    fn publication(&self, ctx: &SensorContext) -> Option<Publication>;
+    fn start(&self, outputs: &Outputs);
+    fn configure_routes(&self, cfg: &mut web::ServiceConfig);
+    fn mqtt(&self) -> &Arc<MqttManager>;
+}
+
+struct Registered<S: Sensor> {
+    handle: Arc<SensorHandle<S>>,
+    command_rx: Mutex<Option<mpsc::Receiver<MqttCommand>>>,
+    /// Waktu simulasi publikasi berikutnya
+    next_publication: Mutex<Option<DateTime<Utc>>>,
+    /// State terakhir yang dipublikasikan (setelah fault), sumber nilai untuk fault freeze
+    last_published: Mutex<Option<S::State>>,
+    /// Stream random fault (`{NAME}/faults`), terpisah agar noise sensor tidak berubah
+    fault_stream: String,
+}
+
+/// Satu tick publikasi satu sensor yang sudah di-encode oleh thread kalkulasi.
+struct Publication {
+    name: &'static str,
+    label: &'static str,
+    /// Waktu simulasi saat publikasi di-encode
+    time: DateTime<Utc>,
+    mqtt: Arc<MqttManager>,
+    messages: Vec<MqttMessage>,
+    frame: OutputFrame,
+}
+
+/// Daftar sensor aktif. Setiap sensor yang didaftarkan otomatis mendapat
+/// kalkulasi, publikasi (MQTT, WebSocket, TCP, UDP, CAN), dan scope REST `/api/{NAME}`.
+#[derive(Default)]
+pub struct SensorRegistry {
+    context: SensorContext,
+    sensors: Vec<Arc<dyn RegisteredSensor>>,
+}
+
+impl SensorRegistry {
+    pub fn register<S: Sensor>(mut self) -> Self {
+        let (command_tx, command_rx) = mpsc::channel::<MqttCommand>(10);
+        let handle = Arc::new(SensorHandle::<S> {
+            config: Arc::new(RwLock::new(SensorConfig::default())),
+            state: Arc::new(RwLock::new(None)),
+            mqtt: Arc::new(MqttManager::new(S::LABEL, command_tx)),
+            faults: SharedFaults::default(),
+        });
+
+        self.context.insert::<S>(handle.state.clone());
+        self.sensors.push(Arc::new(Registered {
+            handle,
+            command_rx: Mutex::new(Some(command_rx)),
+            next_publication: Mutex::new(None),
+            last_published: Mutex::new(None),
+            fault_stream: format!("{}/faults", S::NAME),
+        }));
+        self
+    }
+
+    pub fn context(&self) -> SensorContext {
+        self.context.clone()
+    }
+
+    /// Nama semua sensor sesuai urutan pendaftaran.
+    pub fn names(&self) -> Vec<&'static str> {
+        self.sensors.iter().map(|sensor| sensor.name()).collect()
+    }
+
+    /// Menjalankan satu thread kalkulasi: setiap langkah jam simulasi memajukan model kapal
+    /// lebih dulu, lalu semua sensor (berurutan sesuai pendaftaran), lalu meng-encode publikasi
+    /// yang jatuh tempo menurut waktu simulasi. Satu task mengirim publikasi tersebut berurutan,
+    /// sehingga seed, jam, dan skenario yang sama menghasilkan output yang identik.
+    ///
+    /// Selama jam berjalan, jumlah langkah per tick mengikuti waktu nyata (lihat `sim_clock::due_steps`),
+    /// jadi saat request API jatuh di antara langkah bisa berbeda antar run. Output byte-identik hanya
+    /// dijamin dalam mode langkah: jam di-pause (`PATCH /api/clock`) dan dimajukan lewat `POST /api/clock/step`.
+    pub fn start(&self, outputs: Outputs) {
+        let (publication_tx, publication_rx) = mpsc::unbounded_channel::<Publication>();
+        let sensors = self.sensors.clone();
+        let ctx = self.context.clone();
+        let replay_outputs = outputs.clone();
+        thread::spawn(move || {
+            let interval = Duration::from_millis(sim_clock::TICK_MS);
+            let mut next_tick = Instant::now();
+            loop {
+                // Tidur hanya sisa interval, agar waktu kalkulasi tidak menambah langkah per tick
+                next_tick += interval;
+                let now = Instant::now();
+                if next_tick < now {
+                    next_tick = now;
+                }
+                thread::sleep(next_tick - now);
+                for _ in 0..sim_clock::due_steps() {
+                    sim_clock::advance();
+                    if let Some(ref mut vessel) = *ctx.vessel().write().unwrap() {
+                        if vessel.is_running {
+                            vessel_calculate::calculate_next_vessel_state(vessel, sim_clock::STEP_MS);
+                        }
+                    }
+                    for sensor in &sensors {
+                        sensor.step(&ctx);
+                    }
+                    // Selama replay, publikasi live dilewati
+                    if replay_outputs.is_replaying() {
+                        continue;
+                    }
+                    for sensor in &sensors {
+                        if let Some(publication) = sensor.publication(&ctx) {
+                            let _ = publication_tx.send(publication);
+                        }
+                    }
+                }
+            }
+        });
+
+        for sensor in &self.sensors {
+            sensor.start(&outputs);
+        }
+        tokio::spawn(run_publisher(publication_rx, outputs));
+    }
+    /// Koneksi MQTT per nama sensor, untuk replay rekaman ke broker yang sama.
+    pub fn mqtt_managers(&self) -> HashMap<&'static str, Arc<MqttManager>> {
+        self.sensors.iter().map(|sensor| (sensor.name(), sensor.mqtt().clone())).collect()
+    }
+
+    pub fn configure_routes(&self, cfg: &mut web::ServiceConfig) {
+        for sensor in &self.sensors {
+            sensor.configure_routes(cfg);
+        }
+    }
+
+    /// Menghentikan loop MQTT semua sensor.
+    pub async fn stop(&self) {
+        for sensor in &self.sensors {
+            sensor.mqtt().send_command(MqttCommand::Stop).await;
+        }
+    }
+}
+
+impl<S: Sensor> RegisteredSensor for Registered<S> {
+    fn name(&self) -> &'static str {
+        S::NAME
+    }
+
+    fn step(&self, ctx: &SensorContext) {
+        let mut guard = self.handle.state.write().unwrap();
+        if let Some(ref mut state) = *guard {
+            if S::is_running(state) {
+                S::step(state, ctx);
+            }
+        }
+    }
+
+    /// Publikasi jika sudah jatuh tempo: setiap `update_rate` ms waktu simulasi. Jadwal diulang
+    /// dari waktu sekarang jika tertinggal atau jika jam simulasi dimundurkan.
+    /// Fault aktif diterapkan ke salinan state dan ke kalimat NMEA hasil encode.
+    fn publication(&self, ctx: &SensorContext) -> Option<Publication> {
+        // snapshot config
+        let (update_rate, topic_prefix, talker, n2k_source, options) = {
+            let cfg = self.handle.config.read().unwrap();
+            let ur = cfg.update_rate.unwrap_or(1000);
+            // ambil topic pertama atau default
+            let tp = cfg.topics.as_ref().and_then(|t| t.first()).cloned().unwrap_or_else(|| S::DEFAULT_TOPIC.to_string());
+            let talker = cfg.nmea_talker.clone().unwrap_or_else(|| S::DEFAULT_TALKER.to_string());
+            let n2k_source = cfg.n2k_source_address.unwrap_or(S::DEFAULT_N2K_SOURCE);
+            (ur, tp, talker, n2k_source, cfg.options.clone())
+        };
+
+        let mut state = self.handle.state.read().unwrap().clone()?;
+        if !S::is_running(&state) {
+            return None;
+        }
+
+        let now = sim_clock::now();
+        let rate = chrono::Duration::milliseconds(update_rate as i64);
+        {
+            let mut next = self.next_publication.lock().unwrap();
+            // Jadwal lebih dari satu interval di depan = jam dimundurkan: publikasi sekarang lalu ulang jadwal
+            let rewound = next.is_some_and(|due| due - now > rate);
+            if next.is_some_and(|due| now < due) && !rewound {
+                return None;
+            }
+            *next = Some(match *next {
+                Some(due) if !rewound && now - due < rate => due + rate,
+                _ => now + rate,
+            });
+        }
+
+        let faults = {
+            let mut schedule = self.handle.faults.write().unwrap();
+            sim_rng::with_stream(&self.fault_stream, |rng| fault_injection::due_faults(&mut schedule, now, rng))
+        };
+        if faults.iter().any(|fault| fault.kind == FaultKind::Silence) {
+            return None;
+        }
+        {
+            let mut last_published = self.last_published.lock().unwrap();
+            fault_injection::apply_value_faults::<S>(&mut state, last_published.as_ref(), &faults);
+            *last_published = Some(state.clone());
+        }
+
+        let payload = match serde_json::to_string(&state) {
+            Ok(p) => p,
+            Err(e) => { eprintln!("[{} Service]: JSON serialize error: {}", S::LABEL, e); return None; }
+        };
+        let topic = format!("{}/data", topic_prefix);
+        let nmea_topic = format!("{}/nmea", topic_prefix);
+        let mut sentences = S::encode_nmea(&state, &talker, &options, ctx);
+        fault_injection::apply_nmea_faults(&mut sentences, &faults);
+        let msg = serde_json::json!({ "type": format!("{}_update", S::NAME), "data": state });
+        let frame = OutputFrame {
+            json: msg.to_string(),
+            n2k: S::encode_n2k(&state, n2k_source, &options, ctx),
+            signalk: S::signalk_delta(&state),
+            nmea: sentences,
+        };
+
+        let mut messages = vec![MqttMessage { topic, payload }];
+        messages.extend(frame.nmea.iter().map(|s| MqttMessage { topic: nmea_topic.clone(), payload: s.clone() }));
+        Some(Publication { name: S::NAME, label: S::LABEL, time: now, mqtt: self.handle.mqtt.clone(), messages, frame })
+    }
+
+    fn start(&self, outputs: &Outputs) {
+        let Some(command_rx) = self.command_rx.lock().unwrap().take() else { return };
+
+        // Koneksi MQTT dibangun dari config sensor masing-masing
+        let config_source = self.handle.config.clone();
+        mqtt_manager::start_service_manager(
+            self.handle.mqtt.clone(),
+            move || {
+                let cfg = config_source.read().unwrap();
+                MqttServiceConfig::from_broker(S::LABEL, cfg.ip.clone(), cfg.port, cfg.username.clone(), cfg.password.clone())
+            },
+            command_rx,
+        );
+        mqtt_manager::start_status_broadcast(S::NAME, self.handle.mqtt.clone(), outputs.ws_clients.clone());
+    }
+
+    fn configure_routes(&self, cfg: &mut web::ServiceConfig) {
+        cfg.service(sensor_routes::scope(self.handle.clone()));
+    }
+
+    fn mqtt(&self) -> &Arc<MqttManager> {
+        &self.handle.mqtt
+    }
+}
+
+/// 🔹 Pengirim publikasi ke MQTT + WebSocket (JSON & Signal K) + TCP + UDP (NMEA) + CAN (NMEA 2000),
+/// sesuai urutan dari thread kalkulasi. Setiap publikasi ikut direkam jika recorder aktif.
+async fn run_publisher(mut publications: mpsc::UnboundedReceiver<Publication>, outputs: Outputs) {
+    while let Some(publication) = publications.recv().await {
+        if outputs.is_replaying() {
+            continue;
+        }
+        let Publication { name, label, time, mqtt, messages, frame } = publication;
+
+        if outputs.recorder.is_recording() {
+            // Waktu simulasi, agar jeda replay dan TAG block `c:` cocok dengan waktu di kalimat
+            outputs.recorder.record(&RecordedMessage {
+                time,
+                sensor: name.to_string(),
+                mqtt: messages.clone(),
+                json: frame.json.clone(),
+                nmea: frame.nmea.clone(),
+                n2k: frame.n2k.clone(),
+                signalk: frame.signalk.as_ref().and_then(|delta| serde_json::to_string(delta).ok()),
+            });
+        }
+
+        // Saat broker belum terhubung, pesan di-buffer oleh MqttManager
+        if mqtt.state() != MqttState::Disconnected {
+            for message in messages {
+                if let Err(e) = mqtt.publish_message(std::slice::from_ref(&message.topic), message.payload).await {
+                    eprintln!("[{} Service]: MQTT publish error to {}: {:?}", label, message.topic, e);
+                }
+            }
+        }
+
+        outputs.publish(frame).await;
+    }
+}
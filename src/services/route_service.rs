@@ -0,0 +1,231 @@
+use crate::data::route_data::{
+    CreateRouteRequest, RouteOptions, RoutePoint, RouteSentence, RouteState, UpdateRouteRequest, Waypoint,
+};
+use crate::routes::route_routes;
+use crate::services::gps_service::Gps;
+use crate::services::sensor::{Sensor, SensorContext, SensorOptions};
+use crate::utils;
+use crate::utils::n2k::DEFAULT_ROUTE_SOURCE;
+use crate::utils::navigation;
+use crate::utils::nmea::{self, DEFAULT_ROUTE_TALKER};
+use crate::utils::route_calculate;
+use crate::utils::signalk::Delta;
+use crate::utils::vessel_calculate;
+use crate::utils::sim_clock;
+use actix_web::Scope;
+
+/// 🔹 Rute waypoint: autopilot mengemudikan model kapal (atau COG/SOG GPS) sepanjang leg
+/// great circle / rhumb line dan melaporkan progres (XTE, DTW, BTW)
+pub struct Route;
+
+/// Panjang maksimum nama waypoint, agar RMB/APB/BWC tetap di bawah 82 karakter
+const MAX_WAYPOINT_NAME_LEN: usize = 10;
+
+/// Kecepatan leg maksimum (knot), juga untuk kecepatan hasil timestamp track
+pub const MAX_LEG_SPEED: f64 = 50.0;
+
+/// Nama dibersihkan dari karakter yang merusak kalimat NMEA; nama kosong diganti `WPnnn`
+/// sesuai urutan; bujur dinormalisasi ke -180..180 dan kecepatan dibatasi 0..`MAX_LEG_SPEED`.
+fn normalize_waypoints(waypoints: Vec<Waypoint>) -> Vec<Waypoint> {
+    waypoints
+        .into_iter()
+        .enumerate()
+        .map(|(i, mut waypoint)| {
+            waypoint.name = nmea::sanitize_field(&waypoint.name, MAX_WAYPOINT_NAME_LEN);
+            if waypoint.name.is_empty() {
+                waypoint.name = format!("WP{:03}", i + 1);
+            }
+            waypoint.latitude = waypoint.latitude.clamp(-90.0, 90.0);
+            if !(-180.0..=180.0).contains(&waypoint.longitude) {
+                waypoint.longitude = (waypoint.longitude + 180.0).rem_euclid(360.0) - 180.0;
+            }
+            waypoint.speed = waypoint.speed.clamp(0.0, MAX_LEG_SPEED);
+            waypoint
+        })
+        .collect()
+}
+
+/// Memulai leg baru dari posisi kapal saat ini pada langkah berikutnya.
+fn restart_leg(state: &mut RouteState) {
+    state.leg_start = None;
+    state.completed = state.waypoints.is_empty();
+    state.arrival_circle_entered = false;
+    state.perpendicular_passed = false;
+}
+
+/// Mengganti waypoint (mis. hasil impor). Dengan `start_at_first`, waypoint pertama menjadi
+/// awal leg dan rute dimulai dari waypoint kedua.
+pub fn replace_waypoints(state: &mut RouteState, waypoints: Vec<Waypoint>, start_at_first: bool) {
+    Route::update(state, UpdateRouteRequest { waypoints: Some(waypoints), ..Default::default() });
+    if start_at_first && state.waypoints.len() > 1 {
+        let first = &state.waypoints[0];
+        state.leg_start = Some(RoutePoint {
+            name: first.name.clone(),
+            latitude: first.latitude,
+            longitude: first.longitude,
+        });
+        state.active_index = 1;
+    }
+}
+
+/// Memindahkan kapal ke waypoint pertama dengan haluan sepanjang leg pertama:
+/// model kapal jika ada, jika tidak langsung GPS.
+pub fn reposition_to_start(ctx: &SensorContext, waypoints: &[Waypoint]) {
+    let [first, second, ..] = waypoints else { return };
+    let (_, course) =
+        navigation::leg_distance_bearing(second.leg, first.latitude, first.longitude, second.latitude, second.longitude);
+
+    let vessel = ctx.vessel();
+    let mut guard = vessel.write().unwrap();
+    if let Some(vessel) = guard.as_mut() {
+        vessel.latitude = first.latitude;
+        vessel.longitude = first.longitude;
+        vessel.heading = course;
+        vessel.stw = second.speed;
+        vessel_calculate::update_ground_velocity(vessel);
+        return;
+    }
+    drop(guard);
+
+    let Some(gps) = ctx.shared::<Gps>() else { return };
+    let mut guard = gps.write().unwrap();
+    if let Some(gps) = guard.as_mut() {
+        gps.truth.latitude = first.latitude;
+        gps.truth.longitude = first.longitude;
+        gps.truth.cog = course;
+        gps.truth.sog = second.speed;
+        gps.report_truth();
+    }
+}
+
+impl Sensor for Route {
+    const NAME: &'static str = "route";
+    const LABEL: &'static str = "Route";
+    const DEFAULT_TOPIC: &'static str = "vessel/route";
+    const DEFAULT_TALKER: &'static str = DEFAULT_ROUTE_TALKER;
+    const DEFAULT_N2K_SOURCE: u8 = DEFAULT_ROUTE_SOURCE;
+    const FAULT_FIELDS: &'static [&'static str] = &["xte", "dtw", "btw", "bod", "desired_course", "vmg"];
+
+    type State = RouteState;
+    type Options = RouteOptions;
+    type CreateRequest = CreateRouteRequest;
+    type UpdateRequest = UpdateRouteRequest;
+
+    fn create(req: CreateRouteRequest) -> RouteState {
+        let mut state = RouteState {
+            waypoints: normalize_waypoints(req.waypoints),
+            arrival_radius: req.arrival_radius.max(0.0),
+            turn_rate: req.turn_rate.max(0.01),
+            looping: req.looping,
+            active_index: 0,
+            leg_start: None,
+            completed: false,
+            xte: 0.0,
+            dtw: 0.0,
+            btw: 0.0,
+            bod: 0.0,
+            desired_course: 0.0,
+            vmg: 0.0,
+            arrival_circle_entered: false,
+            perpendicular_passed: false,
+            is_running: req.is_running,
+            last_update: sim_clock::now(),
+            calculation_rate_ms: 100,
+        };
+        restart_leg(&mut state);
+        state
+    }
+
+    fn update(route_state: &mut RouteState, patch: UpdateRouteRequest) {
+        if let Some(waypoints) = patch.waypoints {
+            route_state.waypoints = normalize_waypoints(waypoints);
+            route_state.active_index = 0;
+            restart_leg(route_state);
+        }
+        if let Some(radius) = patch.arrival_radius { route_state.arrival_radius = radius.max(0.0); }
+        if let Some(turn_rate) = patch.turn_rate { route_state.turn_rate = turn_rate.max(0.01); }
+        if let Some(looping) = patch.looping { route_state.looping = looping; }
+        if let Some(index) = patch.active_index {
+            route_state.active_index = index.min(route_state.waypoints.len().saturating_sub(1));
+            restart_leg(route_state);
+        }
+        if let Some(is_running) = patch.is_running { route_state.is_running = is_running; }
+        route_state.last_update = sim_clock::now();
+    }
+
+    fn is_running(state: &RouteState) -> bool {
+        state.is_running
+    }
+
+    fn step(state: &mut RouteState, ctx: &SensorContext) {
+        route_calculate::calculate_next_route_state(state, ctx);
+    }
+
+    fn encode_nmea(state: &RouteState, talker: &str, options: &RouteOptions, ctx: &SensorContext) -> Vec<String> {
+        let sentences = options.nmea_sentences.clone().unwrap_or_else(|| RouteSentence::ALL.to_vec());
+        let gps = ctx.get::<Gps>();
+        let great_circle = gps.as_ref().zip(state.active_waypoint()).map(|(gps, wp)| {
+            navigation::great_circle(gps.latitude, gps.longitude, wp.latitude, wp.longitude)
+        });
+        let variation = gps.map(|gps| gps.variation);
+        utils::route_nmea::encode_route_sentences(state, talker, &sentences, great_circle, variation)
+    }
+
+    fn signalk_delta(state: &RouteState) -> Option<Delta> {
+        utils::signalk::route_delta(state)
+    }
+
+    fn fault_values<'a>(state: &'a mut RouteState, field: &str) -> Vec<&'a mut f64> {
+        match field {
+            "xte" => vec![&mut state.xte],
+            "dtw" => vec![&mut state.dtw],
+            "btw" => vec![&mut state.btw],
+            "bod" => vec![&mut state.bod],
+            "desired_course" => vec![&mut state.desired_course],
+            "vmg" => vec![&mut state.vmg],
+            _ => Vec::new(),
+        }
+    }
+
+    fn routes(scope: Scope) -> Scope {
+        route_routes::import(scope)
+    }
+}
+
+impl SensorOptions for RouteOptions {
+    fn merge(&mut self, patch: Self) {
+        self.nmea_sentences = patch.nmea_sentences.or_else(|| self.nmea_sentences.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::route_data::LegType;
+
+    fn waypoint(name: &str, latitude: f64, longitude: f64, speed: f64) -> Waypoint {
+        Waypoint { name: name.to_string(), latitude, longitude, speed, leg: LegType::default() }
+    }
+
+    #[test]
+    fn normalize_waypoints_wraps_longitude_and_clamps_speed() {
+        let waypoints = normalize_waypoints(vec![
+            waypoint("A", 95.0, 190.0, -3.0),
+            waypoint("B", -10.0, -200.0, 5000.0),
+            waypoint("C", 10.0, 180.0, 12.0),
+        ]);
+        let values: Vec<_> = waypoints.iter().map(|w| (w.latitude, w.longitude, w.speed)).collect();
+        assert_eq!(values, [(90.0, -170.0, 0.0), (-10.0, 160.0, MAX_LEG_SPEED), (10.0, 180.0, 12.0)]);
+    }
+
+    #[test]
+    fn normalize_waypoints_sanitizes_names() {
+        let waypoints = normalize_waypoints(vec![
+            waypoint("Pelabuhan Tanjung Priok", 0.0, 0.0, 5.0),
+            waypoint("WP,1*$", 0.0, 0.0, 5.0),
+            waypoint("  ", 0.0, 0.0, 5.0),
+        ]);
+        let names: Vec<_> = waypoints.iter().map(|w| w.name.as_str()).collect();
+        assert_eq!(names, ["Pelabuhan", "WP1", "WP003"]);
+    }
+}
@@ -0,0 +1,117 @@
+use crate::data::sensor_data::SharedSensorState;
+use crate::data::vessel_data::{SharedVesselState, VesselState};
+use crate::utils::n2k::N2kMessage;
+use actix_web::Scope;
+use crate::utils::signalk::Delta;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Satu jenis sensor simulasi. Registry membangkitkan kalkulasi, publikasi,
+/// dan scope REST `/api/{NAME}` dari implementasi ini, jadi sensor baru cukup satu modul.
+pub trait Sensor: Send + Sync + 'static {
+    /// Nama pendek: path REST, key status MQTT, dan tipe pesan WebSocket (`{NAME}_update`)
+    const NAME: &'static str;
+    /// Nama tampilan untuk log dan pesan respons
+    const LABEL: &'static str;
+    const DEFAULT_TOPIC: &'static str;
+    const DEFAULT_TALKER: &'static str;
+    const DEFAULT_N2K_SOURCE: u8;
+
+    type State: Clone + Serialize + Send + Sync + 'static;
+    type Options: SensorOptions;
+    type CreateRequest: DeserializeOwned + 'static;
+    type UpdateRequest: DeserializeOwned + 'static;
+
+    /// State awal dari request `POST /api/{NAME}`.
+    fn create(req: Self::CreateRequest) -> Self::State;
+
+    /// Menerapkan request `PATCH /api/{NAME}` (field None = nilai lama).
+    fn update(state: &mut Self::State, req: Self::UpdateRequest);
+
+    /// Menolak request PATCH yang tidak bisa diterapkan, mis. field yang dikendalikan model kapal.
+    fn check_update(_state: &Self::State, _req: &Self::UpdateRequest, _ctx: &SensorContext) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn is_running(state: &Self::State) -> bool;
+
+    /// Satu langkah kalkulasi; state sensor lain bisa dibaca lewat `ctx`.
+    fn step(state: &mut Self::State, ctx: &SensorContext);
+
+    /// Kalimat NMEA 0183 untuk satu tick publikasi.
+    fn encode_nmea(state: &Self::State, talker: &str, options: &Self::Options, ctx: &SensorContext) -> Vec<String>;
+
+    /// Pesan PGN NMEA 2000 untuk satu tick publikasi.
+    fn encode_n2k(_state: &Self::State, _source: u8, _options: &Self::Options, _ctx: &SensorContext) -> Vec<N2kMessage> {
+        Vec::new()
+    }
+
+    /// Delta Signal K, `None` jika sensor tidak punya path Signal K.
+    fn signalk_delta(_state: &Self::State) -> Option<Delta> {
+        None
+    }
+
+    /// Field numerik yang bisa diganggu lewat `/api/{NAME}/faults`; yang pertama adalah field utama.
+    const FAULT_FIELDS: &'static [&'static str] = &[];
+
+    /// Nilai field `field` di salinan state yang akan dipublikasikan (bisa lebih dari satu,
+    /// mis. semua channel); kosong jika field tidak dikenal atau sedang tidak bernilai.
+    fn fault_values<'a>(_state: &'a mut Self::State, _field: &str) -> Vec<&'a mut f64> {
+        Vec::new()
+    }
+
+    /// Route tambahan di dalam scope `/api/{NAME}` (mis. CRUD per channel).
+    fn routes(scope: Scope) -> Scope {
+        scope
+    }
+}
+
+/// Opsi khusus sensor yang di-flatten ke `SensorConfig`.
+pub trait SensorOptions: Clone + Default + Serialize + DeserializeOwned + Send + Sync + 'static {
+    /// Menerapkan patch dari request config (field None = nilai lama).
+    fn merge(&mut self, patch: Self);
+
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Akses ke state semua sensor yang terdaftar, diindeks per tipe sensor,
+/// dan ke model kapal yang menjadi sumber kebenaran bersama.
+#[derive(Clone, Default)]
+pub struct SensorContext {
+    states: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+    vessel: SharedVesselState,
+}
+
+impl SensorContext {
+    pub(crate) fn insert<S: Sensor>(&mut self, state: SharedSensorState<S::State>) {
+        self.states.insert(TypeId::of::<S>(), Arc::new(state));
+    }
+
+    /// State bersama milik sensor `S`, `None` jika sensor tidak terdaftar.
+    pub fn shared<S: Sensor>(&self) -> Option<SharedSensorState<S::State>> {
+        self.states
+            .get(&TypeId::of::<S>())
+            .and_then(|state| state.downcast_ref::<SharedSensorState<S::State>>())
+            .cloned()
+    }
+
+    /// Salinan state sensor `S` saat ini, `None` jika belum dibuat.
+    pub fn get<S: Sensor>(&self) -> Option<S::State> {
+        self.shared::<S>()?.read().unwrap().clone()
+    }
+
+    /// State bersama model kapal.
+    pub fn vessel(&self) -> SharedVesselState {
+        self.vessel.clone()
+    }
+
+    /// Salinan model kapal saat ini, `None` jika belum dibuat (sensor berjalan mandiri).
+    pub fn vessel_state(&self) -> Option<VesselState> {
+        self.vessel.read().unwrap().clone()
+    }
+}
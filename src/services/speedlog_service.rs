@@ -0,0 +1,108 @@
+use crate::data::speedlog_data::{
+    CreateSpeedLogRequest, SpeedLogOptions, SpeedLogSentence, SpeedLogState, UpdateSpeedLogRequest,
+};
+use crate::services::gps_service::Gps;
+use crate::services::gyro_service::Gyro;
+use crate::services::sensor::{Sensor, SensorContext, SensorOptions};
+use crate::utils;
+use crate::utils::n2k::{N2kMessage, DEFAULT_SPEEDLOG_SOURCE};
+use crate::utils::nmea::DEFAULT_SPEEDLOG_TALKER;
+use crate::utils::signalk::Delta;
+use crate::utils::speedlog_calculate;
+use crate::utils::sim_clock;
+
+/// 🔹 Speed log: kecepatan terhadap air sepanjang heading gyro, ditambah leeway dan arus
+/// (set/drift). Selama berjalan, SOG/COG GPS dihitung dari model ini.
+/// Jika model kapal ada, STW, heading, leeway, dan arus diambil dari model kapal.
+pub struct SpeedLog;
+
+impl Sensor for SpeedLog {
+    const NAME: &'static str = "speedlog";
+    const LABEL: &'static str = "Speed Log";
+    const DEFAULT_TOPIC: &'static str = "vessel/speedlog";
+    const DEFAULT_TALKER: &'static str = DEFAULT_SPEEDLOG_TALKER;
+    const DEFAULT_N2K_SOURCE: u8 = DEFAULT_SPEEDLOG_SOURCE;
+    const FAULT_FIELDS: &'static [&'static str] = &["stw", "heading", "longitudinal_water_speed", "transverse_water_speed", "longitudinal_ground_speed", "transverse_ground_speed"];
+
+    type State = SpeedLogState;
+    type Options = SpeedLogOptions;
+    type CreateRequest = CreateSpeedLogRequest;
+    type UpdateRequest = UpdateSpeedLogRequest;
+
+    fn create(req: CreateSpeedLogRequest) -> SpeedLogState {
+        let mut state = SpeedLogState {
+            stw: req.stw.max(0.0),
+            heading: req.heading.rem_euclid(360.0),
+            leeway: req.leeway,
+            current_set: req.current_set.rem_euclid(360.0),
+            current_drift: req.current_drift.max(0.0),
+            sog: 0.0,
+            cog: 0.0,
+            longitudinal_water_speed: 0.0,
+            transverse_water_speed: 0.0,
+            longitudinal_ground_speed: 0.0,
+            transverse_ground_speed: 0.0,
+            is_running: req.is_running,
+            last_update: sim_clock::now(),
+        };
+        speedlog_calculate::calculate_next_speedlog_state(&mut state, None);
+        state
+    }
+
+    fn update(log_state: &mut SpeedLogState, patch: UpdateSpeedLogRequest) {
+        if let Some(stw) = patch.stw { log_state.stw = stw.max(0.0); }
+        if let Some(heading) = patch.heading { log_state.heading = heading.rem_euclid(360.0); }
+        if let Some(leeway) = patch.leeway { log_state.leeway = leeway; }
+        if let Some(set) = patch.current_set { log_state.current_set = set.rem_euclid(360.0); }
+        if let Some(drift) = patch.current_drift { log_state.current_drift = drift.max(0.0); }
+        if let Some(is_running) = patch.is_running { log_state.is_running = is_running; }
+        speedlog_calculate::calculate_next_speedlog_state(log_state, None);
+    }
+
+    fn is_running(state: &SpeedLogState) -> bool {
+        state.is_running
+    }
+
+    fn step(state: &mut SpeedLogState, ctx: &SensorContext) {
+        let heading = match ctx.vessel_state() {
+            Some(vessel) => {
+                speedlog_calculate::sample_vessel(state, &vessel);
+                None
+            }
+            None => ctx.get::<Gyro>().map(|gyro| gyro.yaw),
+        };
+        speedlog_calculate::calculate_next_speedlog_state(state, heading);
+    }
+
+    fn encode_nmea(state: &SpeedLogState, talker: &str, options: &SpeedLogOptions, ctx: &SensorContext) -> Vec<String> {
+        let sentences = options.nmea_sentences.clone().unwrap_or_else(|| SpeedLogSentence::ALL.to_vec());
+        let variation = ctx.get::<Gps>().map(|gps| gps.variation);
+        utils::speedlog_nmea::encode_speedlog_sentences(state, talker, &sentences, variation)
+    }
+
+    fn encode_n2k(state: &SpeedLogState, source: u8, _options: &SpeedLogOptions, _ctx: &SensorContext) -> Vec<N2kMessage> {
+        utils::n2k::encode_speedlog_pgns(state, source)
+    }
+
+    fn signalk_delta(state: &SpeedLogState) -> Option<Delta> {
+        Some(utils::signalk::speedlog_delta(state))
+    }
+
+    fn fault_values<'a>(state: &'a mut SpeedLogState, field: &str) -> Vec<&'a mut f64> {
+        match field {
+            "stw" => vec![&mut state.stw],
+            "heading" => vec![&mut state.heading],
+            "longitudinal_water_speed" => vec![&mut state.longitudinal_water_speed],
+            "transverse_water_speed" => vec![&mut state.transverse_water_speed],
+            "longitudinal_ground_speed" => vec![&mut state.longitudinal_ground_speed],
+            "transverse_ground_speed" => vec![&mut state.transverse_ground_speed],
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl SensorOptions for SpeedLogOptions {
+    fn merge(&mut self, patch: Self) {
+        self.nmea_sentences = patch.nmea_sentences.or_else(|| self.nmea_sentences.clone());
+    }
+}
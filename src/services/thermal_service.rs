@@ -0,0 +1,145 @@
+use crate::data::thermal_data::{
+    CreateThermalChannelRequest, CreateThermalRequest, ThermalChannel, ThermalKind, ThermalOptions, ThermalSentence,
+    ThermalState, UpdateThermalChannelRequest, UpdateThermalRequest,
+};
+use crate::routes::thermal_routes;
+use crate::services::sensor::{Sensor, SensorContext, SensorOptions};
+use crate::utils;
+use crate::utils::n2k::{N2kMessage, DEFAULT_THERMAL_SOURCE};
+use crate::utils::nmea::DEFAULT_THERMAL_TALKER;
+use crate::utils::signalk::Delta;
+use crate::utils::sim_clock;
+use crate::utils::sim_rng;
+use actix_web::Scope;
+
+/// 🔹 Sensor suhu multi-channel (air laut, ruang mesin, pendingin, gas buang, kabin, ...)
+pub struct Thermal;
+
+/// Nama channel dipakai sebagai ID transduser XDR dan segmen path Signal K.
+pub fn is_valid_channel_name(name: &str) -> bool {
+    !name.is_empty() && name.len() <= 16 && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+pub fn new_channel(req: CreateThermalChannelRequest) -> ThermalChannel {
+    let temperature = req.temperature.unwrap_or(req.setpoint);
+    ThermalChannel {
+        name: req.name,
+        kind: req.kind,
+        setpoint: req.setpoint,
+        time_constant: req.time_constant.max(0.1),
+        noise: req.noise.max(0.0),
+        drift: req.drift,
+        drift_offset: 0.0,
+        temperature,
+        value: temperature,
+    }
+}
+
+/// Menerapkan patch channel; menghentikan drift juga menghapus offset-nya
+/// sehingga suhu kembali ke setpoint mengikuti konstanta waktu.
+pub fn update_channel(channel: &mut ThermalChannel, patch: UpdateThermalChannelRequest) {
+    if let Some(kind) = patch.kind { channel.kind = kind; }
+    if let Some(setpoint) = patch.setpoint { channel.setpoint = setpoint; }
+    if let Some(time_constant) = patch.time_constant { channel.time_constant = time_constant.max(0.1); }
+    if let Some(noise) = patch.noise { channel.noise = noise.max(0.0); }
+    if let Some(drift) = patch.drift {
+        if drift.is_none() {
+            channel.drift_offset = 0.0;
+        }
+        channel.drift = drift;
+    }
+}
+
+fn default_channels() -> Vec<CreateThermalChannelRequest> {
+    let channel = |name: &str, kind, setpoint, time_constant| CreateThermalChannelRequest {
+        name: name.to_string(),
+        kind,
+        setpoint,
+        time_constant,
+        noise: 0.1,
+        temperature: None,
+        drift: None,
+    };
+    vec![
+        channel("SeaWater", ThermalKind::SeaWater, 28.0, 600.0),
+        channel("Coolant", ThermalKind::EngineCoolant, 85.0, 120.0),
+        channel("Exhaust", ThermalKind::Exhaust, 350.0, 30.0),
+        channel("Cabin", ThermalKind::Cabin, 24.0, 900.0),
+    ]
+}
+
+impl Sensor for Thermal {
+    const NAME: &'static str = "thermal";
+    const LABEL: &'static str = "Thermal";
+    const DEFAULT_TOPIC: &'static str = "vessel/thermal";
+    const DEFAULT_TALKER: &'static str = DEFAULT_THERMAL_TALKER;
+    const DEFAULT_N2K_SOURCE: u8 = DEFAULT_THERMAL_SOURCE;
+    const FAULT_FIELDS: &'static [&'static str] = &["temperature"];
+
+    type State = ThermalState;
+    type Options = ThermalOptions;
+    type CreateRequest = CreateThermalRequest;
+    type UpdateRequest = UpdateThermalRequest;
+
+    /// Channel dengan nama tidak valid atau duplikat dilewati.
+    fn create(req: CreateThermalRequest) -> ThermalState {
+        let requested = if req.channels.is_empty() { default_channels() } else { req.channels };
+        let mut channels: Vec<ThermalChannel> = Vec::new();
+        for channel in requested {
+            if is_valid_channel_name(&channel.name) && !channels.iter().any(|c| c.name == channel.name) {
+                channels.push(new_channel(channel));
+            }
+        }
+
+        ThermalState {
+            channels,
+            is_running: req.is_running,
+            last_update: sim_clock::now(),
+            calculation_rate_ms: 100,
+        }
+    }
+
+    fn update(thermal_state: &mut ThermalState, patch: UpdateThermalRequest) {
+        if let Some(is_running) = patch.is_running { thermal_state.is_running = is_running; }
+        thermal_state.last_update = sim_clock::now();
+    }
+
+    fn is_running(state: &ThermalState) -> bool {
+        state.is_running
+    }
+
+    fn step(state: &mut ThermalState, _ctx: &SensorContext) {
+        sim_rng::with_stream(Self::NAME, |rng| utils::thermal_calculate::calculate_next_thermal_state(state, rng));
+    }
+
+    fn encode_nmea(state: &ThermalState, talker: &str, options: &ThermalOptions, _ctx: &SensorContext) -> Vec<String> {
+        let sentences = options.nmea_sentences.clone().unwrap_or_else(|| ThermalSentence::ALL.to_vec());
+        utils::thermal_nmea::encode_thermal_sentences(state, talker, &sentences)
+    }
+
+    fn encode_n2k(state: &ThermalState, source: u8, _options: &ThermalOptions, _ctx: &SensorContext) -> Vec<N2kMessage> {
+        utils::n2k::encode_thermal_pgns(state, source)
+    }
+
+    fn signalk_delta(state: &ThermalState) -> Option<Delta> {
+        Some(utils::signalk::thermal_delta(state))
+    }
+
+    fn fault_values<'a>(state: &'a mut ThermalState, field: &str) -> Vec<&'a mut f64> {
+        match field {
+            // Semua channel sekaligus
+            "temperature" => state.channels.iter_mut().map(|channel| &mut channel.temperature).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn routes(scope: Scope) -> Scope {
+        thermal_routes::channels(scope)
+    }
+}
+
+impl SensorOptions for ThermalOptions {
+    fn merge(&mut self, patch: Self) {
+        self.nmea_sentences = patch.nmea_sentences.or_else(|| self.nmea_sentences.clone());
+    }
+}
@@ -0,0 +1,58 @@
+use crate::data::vessel_data::{CreateVesselRequest, Steering, UpdateVesselRequest, VesselState};
+use crate::utils::vessel_calculate;
+use crate::utils::sim_clock;
+
+/// Sudut kemudi dibatasi ±35°.
+fn clamp_steering(steering: Steering) -> Steering {
+    match steering {
+        Steering::Rudder { angle } => Steering::Rudder { angle: angle.clamp(-35.0, 35.0) },
+        Steering::Heading { heading } => Steering::Heading { heading: heading.rem_euclid(360.0) },
+        turn_rate => turn_rate,
+    }
+}
+
+/// 🔹 Model kinematika kapal bersama: kemudi/laju belok → heading, heading + STW + arus → posisi.
+/// Selama model ada, gyro, GPS, dan speed log hanya mengambil sampel dari model ini.
+pub fn create_vessel(req: CreateVesselRequest) -> VesselState {
+    let mut state = VesselState {
+        latitude: req.latitude.clamp(-90.0, 90.0),
+        longitude: req.longitude,
+        heading: req.heading.rem_euclid(360.0),
+        rate_of_turn: match req.steering {
+            Steering::TurnRate { rate } => rate,
+            Steering::Rudder { .. } | Steering::Heading { .. } => 0.0,
+        },
+        steering: clamp_steering(req.steering),
+        length: req.length.max(1.0),
+        nomoto_k: req.nomoto_k.max(0.0),
+        nomoto_t: req.nomoto_t.max(0.01),
+        stw: req.stw.max(0.0),
+        leeway: req.leeway,
+        current_set: req.current_set.rem_euclid(360.0),
+        current_drift: req.current_drift.max(0.0),
+        sog: 0.0,
+        cog: 0.0,
+        is_running: req.is_running,
+        last_update: sim_clock::now(),
+    };
+    vessel_calculate::update_ground_velocity(&mut state);
+    state
+}
+
+/// Menerapkan patch; posisi dan heading bisa diubah langsung (reposisi).
+pub fn update_vessel(vessel: &mut VesselState, patch: UpdateVesselRequest) {
+    if let Some(lat) = patch.latitude { vessel.latitude = lat.clamp(-90.0, 90.0); }
+    if let Some(lon) = patch.longitude { vessel.longitude = lon; }
+    if let Some(heading) = patch.heading { vessel.heading = heading.rem_euclid(360.0); }
+    if let Some(steering) = patch.steering { vessel.steering = clamp_steering(steering); }
+    if let Some(length) = patch.length { vessel.length = length.max(1.0); }
+    if let Some(k) = patch.nomoto_k { vessel.nomoto_k = k.max(0.0); }
+    if let Some(t) = patch.nomoto_t { vessel.nomoto_t = t.max(0.01); }
+    if let Some(stw) = patch.stw { vessel.stw = stw.max(0.0); }
+    if let Some(leeway) = patch.leeway { vessel.leeway = leeway; }
+    if let Some(set) = patch.current_set { vessel.current_set = set.rem_euclid(360.0); }
+    if let Some(drift) = patch.current_drift { vessel.current_drift = drift.max(0.0); }
+    if let Some(is_running) = patch.is_running { vessel.is_running = is_running; }
+    vessel_calculate::update_ground_velocity(vessel);
+    vessel.last_update = sim_clock::now();
+}
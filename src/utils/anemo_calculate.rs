@@ -0,0 +1,58 @@
+use crate::data::anemo_data::AnemoState;
+use crate::data::gps_data::GpsState;
+use crate::data::gyro_data::GyroState;
+use rand_distr::{Distribution, StandardNormal};
+use crate::utils::sim_clock;
+use crate::utils::sim_rng::SimRng;
+
+/// Konstanta waktu hembusan (detik) dan pergeseran arah angin (detik)
+const GUST_TIME_CONSTANT: f64 = 5.0;
+const SHIFT_TIME_CONSTANT: f64 = 30.0;
+
+fn normalize_angle(angle: f64) -> f64 {
+    angle.rem_euclid(360.0)
+}
+
+/// Satu langkah proses Ornstein-Uhlenbeck dengan standar deviasi stasioner `sigma`.
+fn ornstein_uhlenbeck(value: f64, sigma: f64, tau: f64, dt: f64, rng: &mut SimRng) -> f64 {
+    let noise: f64 = StandardNormal.sample(rng);
+    value - value * dt / tau + sigma * (2.0 * dt / tau).sqrt() * noise
+}
+
+/// Angin semu dari angin sebenarnya dan gerak kapal.
+/// Mengembalikan (kecepatan, arah asal angin true) dalam satuan yang sama dengan input.
+pub fn apparent_wind(wind_speed: f64, wind_direction: f64, sog: f64, cog: f64) -> (f64, f64) {
+    // Vektor udara (ke arah mana angin bertiup) dikurangi vektor kapal, komponen (east, north)
+    let (wd, cg) = (wind_direction.to_radians(), cog.to_radians());
+    let air_east = -wind_speed * wd.sin() - sog * cg.sin();
+    let air_north = -wind_speed * wd.cos() - sog * cg.cos();
+
+    let speed = air_east.hypot(air_north);
+    let direction = normalize_angle((-air_east).atan2(-air_north).to_degrees());
+    (speed, direction)
+}
+
+/// Menghitung ulang sudut angin relatif haluan dan angin semu.
+/// Tanpa GPS kapal dianggap diam; tanpa gyro heading diambil dari COG.
+pub fn update_relative_wind(state: &mut AnemoState, gps: Option<&GpsState>, gyro: Option<&GyroState>) {
+    // Angin semu mengikuti gerak sebenarnya, bukan SOG/COG terukur yang mengandung error GNSS
+    let (sog, cog) = gps.map(|g| (g.truth.sog, g.truth.cog)).unwrap_or((0.0, 0.0));
+    let heading = gyro.map(|g| g.yaw).unwrap_or(cog);
+
+    let (apparent_speed, apparent_direction) = apparent_wind(state.wind_speed, state.wind_direction, sog, cog);
+    state.true_wind_angle = normalize_angle(state.wind_direction - heading);
+    state.apparent_wind_speed = apparent_speed;
+    state.apparent_wind_angle = normalize_angle(apparent_direction - heading);
+}
+
+pub fn calculate_next_anemo_state(state: &mut AnemoState, gps: Option<&GpsState>, gyro: Option<&GyroState>, rng: &mut SimRng) {
+    let dt_seconds = state.calculation_rate_ms as f64 / 1000.0;
+
+    state.gust_offset = ornstein_uhlenbeck(state.gust_offset, state.gust_amplitude, GUST_TIME_CONSTANT, dt_seconds, rng);
+    state.shift_offset = ornstein_uhlenbeck(state.shift_offset, state.direction_shift, SHIFT_TIME_CONSTANT, dt_seconds, rng);
+
+    state.wind_speed = (state.true_wind_speed + state.gust_offset).max(0.0);
+    state.wind_direction = normalize_angle(state.true_wind_direction + state.shift_offset);
+    update_relative_wind(state, gps, gyro);
+    state.last_update = sim_clock::now();
+}
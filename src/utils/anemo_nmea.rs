@@ -0,0 +1,69 @@
+use crate::data::anemo_data::{AnemoSentence, AnemoState};
+use crate::utils::nmea::{self, format_angle};
+
+const KNOTS_TO_MPS: f64 = 0.514444;
+const KNOTS_TO_KMH: f64 = 1.852;
+
+/// Menghasilkan kalimat NMEA untuk setiap jenis di `sentences`, sesuai urutannya.
+/// `variation` dipakai untuk arah magnetik di MWD; jika `None` field magnetik dikosongkan.
+pub fn encode_anemo_sentences(
+    state: &AnemoState,
+    talker: &str,
+    sentences: &[AnemoSentence],
+    variation: Option<f64>,
+) -> Vec<String> {
+    sentences
+        .iter()
+        .flat_map(|kind| match kind {
+            AnemoSentence::Mwv => vec![encode_mwv_relative(state, talker), encode_mwv_true(state, talker)],
+            AnemoSentence::Mwd => vec![encode_mwd(state, talker, variation)],
+            AnemoSentence::Vwr => vec![encode_vwr(state, talker)],
+        })
+        .collect()
+}
+
+/// MWV — Wind Speed and Angle, relatif terhadap haluan (R = angin semu)
+pub fn encode_mwv_relative(state: &AnemoState, talker: &str) -> String {
+    let fields = [
+        format_angle(state.apparent_wind_angle), "R".to_string(),
+        format!("{:.1}", state.apparent_wind_speed), "N".to_string(),
+        "A".to_string(),
+    ];
+    nmea::sentence(talker, "MWV", &fields)
+}
+
+/// MWV — Wind Speed and Angle, angin sebenarnya relatif terhadap haluan (T)
+pub fn encode_mwv_true(state: &AnemoState, talker: &str) -> String {
+    let fields = [
+        format_angle(state.true_wind_angle), "T".to_string(),
+        format!("{:.1}", state.wind_speed), "N".to_string(),
+        "A".to_string(),
+    ];
+    nmea::sentence(talker, "MWV", &fields)
+}
+
+/// MWD — Wind Direction and Speed (arah asal angin true/magnetik, knot dan m/s)
+pub fn encode_mwd(state: &AnemoState, talker: &str, variation: Option<f64>) -> String {
+    let magnetic = variation.map(|var| format_angle(state.wind_direction - var)).unwrap_or_default();
+    let fields = [
+        format_angle(state.wind_direction), "T".to_string(),
+        magnetic, "M".to_string(),
+        format!("{:.1}", state.wind_speed), "N".to_string(),
+        format!("{:.1}", state.wind_speed * KNOTS_TO_MPS), "M".to_string(),
+    ];
+    nmea::sentence(talker, "MWD", &fields)
+}
+
+/// VWR — Relative Wind Speed and Angle (0..180 derajat, L/R dari haluan)
+pub fn encode_vwr(state: &AnemoState, talker: &str) -> String {
+    let angle = state.apparent_wind_angle.rem_euclid(360.0);
+    let (angle, side) = if angle > 180.0 { (360.0 - angle, "L") } else { (angle, "R") };
+    let speed = state.apparent_wind_speed;
+    let fields = [
+        format!("{:.1}", angle), side.to_string(),
+        format!("{:.1}", speed), "N".to_string(),
+        format!("{:.1}", speed * KNOTS_TO_MPS), "M".to_string(),
+        format!("{:.1}", speed * KNOTS_TO_KMH), "K".to_string(),
+    ];
+    nmea::sentence(talker, "VWR", &fields)
+}
@@ -0,0 +1,79 @@
+use crate::data::baro_data::{BaroState, PressureTrend};
+use chrono::{DateTime, Timelike, Utc};
+use rand_distr::{Distribution, StandardNormal};
+use std::f64::consts::PI;
+use crate::utils::sim_clock;
+use crate::utils::sim_rng::SimRng;
+
+/// Rentang waktu untuk tendensi tekanan (standar WMO: 3 jam)
+const TENDENCY_HOURS: f64 = 3.0;
+
+/// Offset sinoptik (tekanan hPa, suhu °C, kelembapan %) pada `hours` jam sejak tren dimulai.
+fn synoptic_offsets(trend: &PressureTrend, hours: f64) -> (f64, f64, f64) {
+    match *trend {
+        PressureTrend::Steady => (0.0, 0.0, 0.0),
+        PressureTrend::Linear { rate } => (rate * hours / TENDENCY_HOURS, 0.0, 0.0),
+        PressureTrend::PassingLow { depth, duration_hours } => {
+            let centre = duration_hours / 2.0;
+            let width = (duration_hours / 4.0).max(0.1);
+            let x = (hours - centre) / width;
+            // Lembah tekanan (hujan di sekitar pusat), lalu udara dingin & kering di belakang front
+            let dip = (-x * x).exp();
+            let behind_front = 0.5 * (1.0 + x.tanh());
+            (
+                -depth * dip,
+                -0.25 * depth * behind_front,
+                2.0 * depth * dip - 0.5 * depth * behind_front,
+            )
+        }
+    }
+}
+
+/// Jam matahari lokal (0..24) dari waktu UTC dan bujur.
+fn local_solar_hour(time: &DateTime<Utc>, longitude: f64) -> f64 {
+    (time.num_seconds_from_midnight() as f64 / 3600.0 + longitude / 15.0).rem_euclid(24.0)
+}
+
+/// Offset harian (tekanan hPa, suhu °C, kelembapan %) pada jam matahari lokal `hour`.
+/// Tekanan: pasang atmosfer semi-diurnal (puncak 10:00 & 22:00); suhu puncak 14:00.
+fn diurnal_offsets(hour: f64) -> (f64, f64, f64) {
+    let semi_diurnal = (2.0 * PI * (hour - 10.0) / 12.0).cos();
+    let diurnal = (2.0 * PI * (hour - 14.0) / 24.0).cos();
+    (0.8 * semi_diurnal, 2.5 * diurnal, -8.0 * diurnal)
+}
+
+/// Titik embun (°C) dengan rumus Magnus.
+fn dew_point(temperature: f64, humidity: f64) -> f64 {
+    const B: f64 = 17.62;
+    const C: f64 = 243.12;
+    let gamma = (humidity.max(1.0) / 100.0).ln() + B * temperature / (C + temperature);
+    C * gamma / (B - gamma)
+}
+
+/// Menghitung nilai terukur dari nilai dasar, tren, variasi harian, dan noise.
+/// `longitude` dari GPS menentukan jam lokal untuk variasi harian (default 0°).
+pub fn update_baro_values(state: &mut BaroState, longitude: Option<f64>, rng: &mut SimRng) {
+    let now = sim_clock::now();
+    let hour = local_solar_hour(&now, longitude.unwrap_or(0.0));
+
+    let (sp, st, sh) = synoptic_offsets(&state.trend, state.elapsed_hours);
+    let (past_sp, _, _) = synoptic_offsets(&state.trend, state.elapsed_hours - TENDENCY_HOURS);
+    let ((dp, dt, dh), past_dp) = if state.diurnal {
+        (diurnal_offsets(hour), diurnal_offsets(hour - TENDENCY_HOURS).0)
+    } else {
+        ((0.0, 0.0, 0.0), 0.0)
+    };
+
+    let mut noise = || -> f64 { StandardNormal.sample(&mut *rng) };
+    state.pressure = state.base_pressure + sp + dp + state.noise * noise();
+    state.air_temperature = state.base_temperature + st + dt + state.noise * noise();
+    state.relative_humidity = (state.base_humidity + sh + dh + 5.0 * state.noise * noise()).clamp(0.0, 100.0);
+    state.dew_point = dew_point(state.air_temperature, state.relative_humidity);
+    state.pressure_tendency = (sp + dp) - (past_sp + past_dp);
+    state.last_update = now;
+}
+
+pub fn calculate_next_baro_state(state: &mut BaroState, longitude: Option<f64>, rng: &mut SimRng) {
+    state.elapsed_hours += state.calculation_rate_ms as f64 / 3_600_000.0;
+    update_baro_values(state, longitude, rng);
+}
@@ -0,0 +1,61 @@
+use crate::data::anemo_data::AnemoState;
+use crate::data::baro_data::{BaroSentence, BaroState};
+use crate::utils::nmea::{self, format_angle};
+
+const HPA_TO_INHG: f64 = 0.0295300;
+const KNOTS_TO_MPS: f64 = 0.514444;
+
+/// Menghasilkan kalimat NMEA untuk setiap jenis di `sentences`, sesuai urutannya.
+/// Field angin di MDA diisi dari anemometer jika ada (`variation` untuk arah magnetik).
+pub fn encode_baro_sentences(
+    state: &BaroState,
+    talker: &str,
+    sentences: &[BaroSentence],
+    wind: Option<&AnemoState>,
+    variation: Option<f64>,
+) -> Vec<String> {
+    sentences
+        .iter()
+        .map(|kind| match kind {
+            BaroSentence::Xdr => encode_xdr(state, talker),
+            BaroSentence::Mda => encode_mda(state, talker, wind, variation),
+        })
+        .collect()
+}
+
+/// XDR — Transducer Measurements: tekanan (bar), suhu udara, kelembapan relatif, titik embun
+pub fn encode_xdr(state: &BaroState, talker: &str) -> String {
+    let fields = [
+        "P".to_string(), format!("{:.4}", state.pressure / 1000.0), "B".to_string(), "Barometer".to_string(),
+        "C".to_string(), format!("{:.1}", state.air_temperature), "C".to_string(), "TempAir".to_string(),
+        "H".to_string(), format!("{:.1}", state.relative_humidity), "P".to_string(), "Humidity".to_string(),
+        "C".to_string(), format!("{:.1}", state.dew_point), "C".to_string(), "DewPoint".to_string(),
+    ];
+    nmea::sentence(talker, "XDR", &fields)
+}
+
+/// MDA — Meteorological Composite. Suhu air dan kelembapan absolut dikosongkan.
+pub fn encode_mda(state: &BaroState, talker: &str, wind: Option<&AnemoState>, variation: Option<f64>) -> String {
+    let mut fields = vec![
+        format!("{:.2}", state.pressure * HPA_TO_INHG), "I".to_string(),
+        format!("{:.4}", state.pressure / 1000.0), "B".to_string(),
+        format!("{:.1}", state.air_temperature), "C".to_string(),
+        String::new(), "C".to_string(),
+        format!("{:.1}", state.relative_humidity), String::new(),
+        format!("{:.1}", state.dew_point), "C".to_string(),
+    ];
+
+    match wind {
+        Some(wind) => {
+            let magnetic = variation.map(|var| format_angle(wind.wind_direction - var)).unwrap_or_default();
+            fields.extend([
+                format_angle(wind.wind_direction), "T".to_string(),
+                magnetic, "M".to_string(),
+                format!("{:.1}", wind.wind_speed), "N".to_string(),
+                format!("{:.1}", wind.wind_speed * KNOTS_TO_MPS), "M".to_string(),
+            ]);
+        }
+        None => fields.extend(["", "T", "", "M", "", "N", "", "M"].map(String::from)),
+    }
+    nmea::sentence(talker, "MDA", &fields)
+}
@@ -0,0 +1,287 @@
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use tiff::decoder::{Decoder, DecodingResult};
+use tiff::tags::Tag;
+use tiff::ColorType;
+
+/// Batas jumlah sel grid (±200 MB sebagai f64) agar header rusak tidak menghabiskan memori
+const MAX_CELLS: usize = 25_000_000;
+
+/// Grid batimetri dalam koordinat geografis (derajat), baris pertama = paling utara.
+/// Sel tanpa data disimpan sebagai NaN.
+#[derive(Debug)]
+pub struct BathymetryGrid {
+    path: String,
+    ncols: usize,
+    nrows: usize,
+    /// Pusat sel kolom pertama (bujur) dan baris pertama (lintang)
+    origin_lon: f64,
+    origin_lat: f64,
+    cell_lon: f64,
+    cell_lat: f64,
+    values: Vec<f64>,
+    /// true: nilai grid = kedalaman (positif ke bawah); false: elevasi (negatif di bawah permukaan laut)
+    positive_down: bool,
+}
+
+/// Ringkasan grid yang dimuat, untuk respons API dan state sensor.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct GridInfo {
+    pub path: String,
+    pub ncols: usize,
+    pub nrows: usize,
+    pub west: f64,
+    pub east: f64,
+    pub south: f64,
+    pub north: f64,
+    pub cell_size_lon: f64,
+    pub cell_size_lat: f64,
+    pub positive_down: bool,
+}
+
+impl BathymetryGrid {
+    /// Memuat grid dari file: `.tif`/`.tiff` sebagai GeoTIFF satu band, selain itu ESRI ASCII grid (`.asc`).
+    pub fn load(path: &str, positive_down: bool) -> Result<Self, String> {
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase())
+            .unwrap_or_default();
+
+        let mut grid = match extension.as_str() {
+            "tif" | "tiff" => Self::from_geotiff(path)?,
+            _ => {
+                let text = std::fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {}", path, e))?;
+                Self::from_ascii(&text)?
+            }
+        };
+        grid.path = path.to_string();
+        grid.positive_down = positive_down;
+        Ok(grid)
+    }
+
+    /// ESRI ASCII grid: header `ncols`, `nrows`, `xllcorner|xllcenter`, `yllcorner|yllcenter`,
+    /// `cellsize` (atau `dx`/`dy`), `nodata_value` opsional, lalu nilai baris demi baris dari utara.
+    pub fn from_ascii(text: &str) -> Result<Self, String> {
+        let mut tokens = text.split_whitespace().peekable();
+        let mut header = std::collections::HashMap::new();
+        while let Some(token) = tokens.peek() {
+            if token.parse::<f64>().is_ok() {
+                break;
+            }
+            let key = tokens.next().unwrap().to_ascii_lowercase();
+            let value: f64 = tokens
+                .next()
+                .and_then(|v| v.parse().ok())
+                .ok_or_else(|| format!("Invalid header value for {}", key))?;
+            header.insert(key, value);
+        }
+
+        let get = |key: &str| header.get(key).copied();
+        let ncols = get("ncols").ok_or("Missing ncols")?;
+        let nrows = get("nrows").ok_or("Missing nrows")?;
+        let cell_lon = get("cellsize").or(get("dx")).ok_or("Missing cellsize")?;
+        let cell_lat = get("cellsize").or(get("dy")).ok_or("Missing cellsize")?;
+        let origin_lon = match (get("xllcenter"), get("xllcorner")) {
+            (Some(center), _) => center,
+            (None, Some(corner)) => corner + cell_lon / 2.0,
+            _ => return Err("Missing xllcorner/xllcenter".to_string()),
+        };
+        let south_centre = match (get("yllcenter"), get("yllcorner")) {
+            (Some(center), _) => center,
+            (None, Some(corner)) => corner + cell_lat / 2.0,
+            _ => return Err("Missing yllcorner/yllcenter".to_string()),
+        };
+        let nodata = get("nodata_value");
+
+        let values: Vec<f64> = tokens
+            .map(|t| t.parse::<f64>().map_err(|_| format!("Invalid grid value: {}", t)))
+            .map(|v| v.map(|v| if Some(v) == nodata { f64::NAN } else { v }))
+            .collect::<Result<_, _>>()?;
+
+        Self::new(ncols, nrows, origin_lon, south_centre + (nrows - 1.0) * cell_lat, cell_lon, cell_lat, values)
+    }
+
+    /// GeoTIFF satu band dengan ModelPixelScale + ModelTiepoint (CRS geografis, derajat).
+    fn from_geotiff(path: &str) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| format!("Cannot open {}: {}", path, e))?;
+        let mut decoder = Decoder::new(BufReader::new(file)).map_err(|e| format!("Invalid TIFF: {}", e))?;
+        let tiff_err = |e: tiff::TiffError| format!("Invalid GeoTIFF: {}", e);
+
+        let (width, height) = decoder.dimensions().map_err(tiff_err)?;
+        match decoder.colortype().map_err(tiff_err)? {
+            ColorType::Gray(_) => {}
+            other => return Err(format!("GeoTIFF must have a single band, found {:?}", other)),
+        }
+        let scale = decoder.get_tag_f64_vec(Tag::ModelPixelScaleTag).map_err(tiff_err)?;
+        let tiepoint = decoder.get_tag_f64_vec(Tag::ModelTiepointTag).map_err(tiff_err)?;
+        if scale.len() < 2 || tiepoint.len() < 6 {
+            return Err("GeoTIFF is missing pixel scale or tiepoint".to_string());
+        }
+        let nodata = decoder
+            .get_tag_ascii_string(Tag::GdalNodata)
+            .ok()
+            .and_then(|s| s.trim_matches(char::from(0)).trim().parse::<f64>().ok());
+
+        let values: Vec<f64> = match decoder.read_image().map_err(tiff_err)? {
+            DecodingResult::U8(v) => v.into_iter().map(f64::from).collect(),
+            DecodingResult::U16(v) => v.into_iter().map(f64::from).collect(),
+            DecodingResult::U32(v) => v.into_iter().map(f64::from).collect(),
+            DecodingResult::I8(v) => v.into_iter().map(f64::from).collect(),
+            DecodingResult::I16(v) => v.into_iter().map(f64::from).collect(),
+            DecodingResult::I32(v) => v.into_iter().map(f64::from).collect(),
+            DecodingResult::F32(v) => v.into_iter().map(f64::from).collect(),
+            DecodingResult::F64(v) => v,
+            _ => return Err("Unsupported GeoTIFF sample format".to_string()),
+        };
+        let values = values
+            .into_iter()
+            .map(|v| if Some(v) == nodata { f64::NAN } else { v })
+            .collect();
+
+        // Tiepoint (i, j, k, x, y, z): piksel (i, j) berada di koordinat (x, y); pixel-is-area
+        let (cell_lon, cell_lat) = (scale[0], scale[1]);
+        let west = tiepoint[3] - tiepoint[0] * cell_lon;
+        let north = tiepoint[4] + tiepoint[1] * cell_lat;
+        Self::new(
+            width as f64,
+            height as f64,
+            west + cell_lon / 2.0,
+            north - cell_lat / 2.0,
+            cell_lon,
+            cell_lat,
+            values,
+        )
+    }
+
+    /// Dimensi dari header (float) divalidasi di sini: harus bilangan bulat positif
+    /// dan jumlah sel tidak boleh melebihi `MAX_CELLS`.
+    fn new(
+        ncols: f64,
+        nrows: f64,
+        origin_lon: f64,
+        origin_lat: f64,
+        cell_lon: f64,
+        cell_lat: f64,
+        values: Vec<f64>,
+    ) -> Result<Self, String> {
+        let dimension = |value: f64| {
+            (value.is_finite() && value >= 1.0 && value.fract() == 0.0 && value <= MAX_CELLS as f64).then_some(value as usize)
+        };
+        let (Some(ncols), Some(nrows)) = (dimension(ncols), dimension(nrows)) else {
+            return Err("Grid dimensions must be positive integers".to_string());
+        };
+        if !(cell_lon.is_finite() && cell_lat.is_finite() && cell_lon > 0.0 && cell_lat > 0.0) {
+            return Err("Grid cell size must be positive".to_string());
+        }
+        if !(origin_lon.is_finite() && origin_lat.is_finite()) {
+            return Err("Grid origin must be finite".to_string());
+        }
+        let cells = ncols.checked_mul(nrows).filter(|&cells| cells <= MAX_CELLS).ok_or_else(|| {
+            format!("Grid of {} x {} cells exceeds the limit of {} cells", ncols, nrows, MAX_CELLS)
+        })?;
+        if values.len() < cells {
+            return Err(format!("Grid has {} values, expected {}", values.len(), cells));
+        }
+        Ok(Self {
+            path: String::new(),
+            ncols,
+            nrows,
+            origin_lon,
+            origin_lat,
+            cell_lon,
+            cell_lat,
+            values,
+            positive_down: false,
+        })
+    }
+
+    pub fn info(&self) -> GridInfo {
+        GridInfo {
+            path: self.path.clone(),
+            ncols: self.ncols,
+            nrows: self.nrows,
+            west: self.origin_lon - self.cell_lon / 2.0,
+            east: self.origin_lon + (self.ncols as f64 - 0.5) * self.cell_lon,
+            south: self.origin_lat - (self.nrows as f64 - 0.5) * self.cell_lat,
+            north: self.origin_lat + self.cell_lat / 2.0,
+            cell_size_lon: self.cell_lon,
+            cell_size_lat: self.cell_lat,
+            positive_down: self.positive_down,
+        }
+    }
+
+    fn value(&self, col: usize, row: usize) -> f64 {
+        self.values[row * self.ncols + col]
+    }
+
+    /// Kedalaman air (m, ≥ 0) di posisi dengan interpolasi bilinear.
+    /// `None` jika posisi di luar grid atau semua sel di sekitarnya tanpa data.
+    pub fn depth_at(&self, lat: f64, lon: f64) -> Option<f64> {
+        let fx = (lon - self.origin_lon) / self.cell_lon;
+        let fy = (self.origin_lat - lat) / self.cell_lat;
+        if fx < -0.5 || fy < -0.5 || fx > self.ncols as f64 - 0.5 || fy > self.nrows as f64 - 0.5 {
+            return None;
+        }
+
+        let fx = fx.clamp(0.0, (self.ncols - 1) as f64);
+        let fy = fy.clamp(0.0, (self.nrows - 1) as f64);
+        let (c0, r0) = (fx.floor() as usize, fy.floor() as usize);
+        let (c1, r1) = ((c0 + 1).min(self.ncols - 1), (r0 + 1).min(self.nrows - 1));
+        let (tx, ty) = (fx - c0 as f64, fy - r0 as f64);
+
+        // Sel tanpa data diabaikan, bobot sisanya dinormalisasi
+        let corners = [
+            (self.value(c0, r0), (1.0 - tx) * (1.0 - ty)),
+            (self.value(c1, r0), tx * (1.0 - ty)),
+            (self.value(c0, r1), (1.0 - tx) * ty),
+            (self.value(c1, r1), tx * ty),
+        ];
+        let (sum, weight) = corners
+            .iter()
+            .filter(|(v, _)| !v.is_nan())
+            .fold((0.0, 0.0), |(s, w), (v, wt)| (s + v * wt, w + wt));
+        if weight <= 0.0 {
+            return None;
+        }
+
+        let value = sum / weight;
+        let depth = if self.positive_down { value } else { -value };
+        Some(depth.max(0.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRID: &str = "ncols 3\nnrows 2\nxllcorner 10.0\nyllcorner 50.0\ncellsize 1.0\nnodata_value -9999\n\
+                        -10 -20 -30\n-40 -9999 -60\n";
+
+    #[test]
+    fn ascii_grid_interpolates_elevation_as_depth() {
+        let grid = BathymetryGrid::from_ascii(GRID).unwrap();
+        let info = grid.info();
+        assert_eq!((info.west, info.east, info.south, info.north), (10.0, 13.0, 50.0, 52.0));
+        assert_eq!(grid.depth_at(51.5, 10.5), Some(10.0));
+        assert_eq!(grid.depth_at(51.5, 11.0), Some(15.0));
+        // Sel tanpa data diabaikan
+        assert_eq!(grid.depth_at(50.5, 11.0), Some(40.0));
+        assert_eq!(grid.depth_at(50.5, 11.5), None);
+        assert_eq!(grid.depth_at(49.0, 10.5), None);
+    }
+
+    #[test]
+    fn ascii_grid_rejects_bad_dimensions() {
+        let header = |ncols: &str, nrows: &str| {
+            format!("ncols {}\nnrows {}\nxllcorner 0\nyllcorner 0\ncellsize 1\n1 2 3 4\n", ncols, nrows)
+        };
+        for (ncols, nrows) in [("0", "2"), ("-2", "2"), ("2.5", "2"), ("NaN", "2"), ("inf", "2"), ("1e12", "1e12"), ("100000", "100000")] {
+            assert!(BathymetryGrid::from_ascii(&header(ncols, nrows)).is_err(), "{} x {}", ncols, nrows);
+        }
+        assert!(BathymetryGrid::from_ascii(&header("3", "2")).is_err());
+        assert!(BathymetryGrid::from_ascii(&header("2", "2")).is_ok());
+    }
+}
@@ -0,0 +1,135 @@
+use crate::data::output_data::CanOutputConfig;
+use crate::utils::n2k::N2kMessage;
+use socket::CanSocket;
+use std::sync::{Arc, RwLock};
+
+pub type SharedCanOutput = Arc<CanOutput>;
+
+/// SocketCAN hanya ada di Linux; di OS lain output CAN tidak bisa diaktifkan.
+#[cfg(target_os = "linux")]
+mod socket {
+    use crate::utils::n2k::CanFrame;
+    use std::ffi::CString;
+    use std::io;
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+    /// Socket CAN_RAW yang terikat ke satu interface (mis. `vcan0`).
+    pub struct CanSocket {
+        fd: OwnedFd,
+    }
+
+    impl CanSocket {
+        pub fn open(interface: &str) -> io::Result<Self> {
+            let name = CString::new(interface).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "invalid interface name"))?;
+            // SAFETY: if_nametoindex hanya membaca string C yang valid
+            let ifindex = unsafe { libc::if_nametoindex(name.as_ptr()) };
+            if ifindex == 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            // SAFETY: pemanggilan socket(2) biasa; fd yang valid langsung dibungkus OwnedFd
+            let raw = unsafe { libc::socket(libc::PF_CAN, libc::SOCK_RAW | libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC, libc::CAN_RAW) };
+            if raw < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            // SAFETY: `raw` adalah fd baru yang valid dari socket(2) dan belum dimiliki pihak lain
+            let fd = unsafe { OwnedFd::from_raw_fd(raw) };
+
+            // SAFETY: sockaddr_can di-zero lalu diisi family dan ifindex, sesuai bind(2) untuk CAN_RAW
+            let result = unsafe {
+                let mut addr: libc::sockaddr_can = std::mem::zeroed();
+                addr.can_family = libc::AF_CAN as libc::sa_family_t;
+                addr.can_ifindex = ifindex as libc::c_int;
+                libc::bind(
+                    fd.as_raw_fd(),
+                    &addr as *const libc::sockaddr_can as *const libc::sockaddr,
+                    std::mem::size_of::<libc::sockaddr_can>() as libc::socklen_t,
+                )
+            };
+            if result < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(Self { fd })
+        }
+
+        pub fn write_frame(&self, frame: &CanFrame) -> io::Result<()> {
+            // SAFETY: can_frame adalah struct POD; field privat (padding) tetap nol
+            let mut raw: libc::can_frame = unsafe { std::mem::zeroed() };
+            raw.can_id = frame.id | libc::CAN_EFF_FLAG;
+            raw.can_dlc = frame.data.len() as u8;
+            raw.data[..frame.data.len()].copy_from_slice(&frame.data);
+
+            let size = std::mem::size_of::<libc::can_frame>();
+            // SAFETY: menulis tepat satu can_frame dari memori yang valid
+            let written = unsafe { libc::write(self.fd.as_raw_fd(), &raw as *const libc::can_frame as *const libc::c_void, size) };
+            if written < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod socket {
+    use crate::utils::n2k::CanFrame;
+    use std::io;
+
+    pub struct CanSocket;
+
+    impl CanSocket {
+        pub fn open(_interface: &str) -> io::Result<Self> {
+            Err(io::Error::new(io::ErrorKind::Unsupported, "SocketCAN is only available on Linux"))
+        }
+
+        pub fn write_frame(&self, _frame: &CanFrame) -> io::Result<()> {
+            Ok(())
+        }
+    }
+}
+
+struct ActiveCan {
+    config: CanOutputConfig,
+    socket: CanSocket,
+}
+
+/// Output NMEA 2000 ke interface SocketCAN Linux (`vcan0` untuk pengujian tanpa hardware).
+#[derive(Default)]
+pub struct CanOutput {
+    active: RwLock<Option<Arc<ActiveCan>>>,
+}
+
+impl CanOutput {
+    pub fn config(&self) -> Option<CanOutputConfig> {
+        self.active.read().unwrap().as_ref().map(|a| a.config.clone())
+    }
+
+    /// Membuka socket baru sesuai config; config lama tetap dipakai jika gagal.
+    pub fn configure(&self, config: CanOutputConfig) -> Result<(), String> {
+        let socket = CanSocket::open(&config.interface)
+            .map_err(|e| format!("Cannot open CAN interface {}: {}", config.interface, e))?;
+        println!("[CAN Output]: Sending NMEA 2000 to {}", config.interface);
+        *self.active.write().unwrap() = Some(Arc::new(ActiveCan { config, socket }));
+        Ok(())
+    }
+
+    pub fn clear(&self) -> bool {
+        self.active.write().unwrap().take().is_some()
+    }
+
+    /// Kirim semua frame dari pesan-pesan PGN. Frame dibuang jika antrian TX interface penuh.
+    pub fn send(&self, messages: &[N2kMessage]) {
+        let Some(active) = self.active.read().unwrap().clone() else { return };
+        if !active.config.enabled {
+            return;
+        }
+
+        for frame in messages.iter().flat_map(N2kMessage::frames) {
+            if let Err(e) = active.socket.write_frame(&frame) {
+                eprintln!("[CAN Output]: Write error on {}: {}", active.config.interface, e);
+                break;
+            }
+        }
+    }
+}
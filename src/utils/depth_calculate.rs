@@ -0,0 +1,51 @@
+use crate::data::depth_data::{DepthSource, DepthState, EchoMode};
+use rand::Rng;
+use rand_distr::{Distribution, StandardNormal};
+use crate::utils::sim_clock;
+use crate::utils::sim_rng::SimRng;
+
+/// Kedalaman air sebenarnya dari grid di posisi kapal, atau `constant_depth` jika tidak tersedia.
+fn water_depth(state: &DepthState, position: Option<(f64, f64)>) -> (f64, DepthSource) {
+    let from_grid = match (&state.bathymetry, position) {
+        (Some(grid), Some((lat, lon))) => grid.depth_at(lat, lon),
+        _ => None,
+    };
+    match from_grid {
+        Some(depth) => (depth, DepthSource::Grid),
+        None => (state.constant_depth.max(0.0), DepthSource::Constant),
+    }
+}
+
+/// Menghitung sampel kedalaman baru. `position` (lintang, bujur) dari GPS.
+pub fn calculate_next_depth_state(state: &mut DepthState, position: Option<(f64, f64)>, rng: &mut SimRng) {
+    let (depth, source) = water_depth(state, position);
+    state.water_depth = depth;
+    state.depth_source = source;
+    state.false_echo = false;
+
+    let below_transducer = (depth - state.transducer_offset).max(0.0);
+    let echo = match state.echo_mode {
+        EchoMode::Normal => Some(below_transducer),
+        EchoMode::Dropout { probability } => {
+            (!rng.random_bool(probability.clamp(0.0, 1.0))).then_some(below_transducer)
+        }
+        EchoMode::FalseEcho { probability } => {
+            if rng.random_bool(probability.clamp(0.0, 1.0)) {
+                state.false_echo = true;
+                Some(below_transducer * rng.random_range(0.2..0.8))
+            } else {
+                Some(below_transducer)
+            }
+        }
+    };
+
+    let noise: f64 = StandardNormal.sample(rng);
+    let measured = echo
+        .map(|d| (d + state.noise * noise).max(0.0))
+        .filter(|d| *d <= state.max_range);
+
+    state.depth_below_transducer = measured;
+    state.depth_below_surface = measured.map(|d| d + state.transducer_offset);
+    state.depth_below_keel = measured.map(|d| d - state.keel_offset);
+    state.last_update = sim_clock::now();
+}
@@ -0,0 +1,45 @@
+use crate::data::depth_data::{DepthSentence, DepthState};
+use crate::utils::nmea;
+
+const METERS_TO_FEET: f64 = 3.28084;
+const METERS_TO_FATHOMS: f64 = 0.546807;
+
+/// Menghasilkan kalimat NMEA untuk setiap jenis di `sentences`, sesuai urutannya.
+/// Saat tidak ada echo, field kedalaman dikosongkan.
+pub fn encode_depth_sentences(state: &DepthState, talker: &str, sentences: &[DepthSentence]) -> Vec<String> {
+    sentences
+        .iter()
+        .map(|kind| match kind {
+            DepthSentence::Dbt => encode_depth_units(state.depth_below_transducer, talker, "DBT"),
+            DepthSentence::Dpt => encode_dpt(state, talker),
+            DepthSentence::Dbs => encode_depth_units(state.depth_below_surface, talker, "DBS"),
+        })
+        .collect()
+}
+
+fn format_depth(depth: Option<f64>, factor: f64) -> String {
+    depth.map(|d| format!("{:.1}", d * factor)).unwrap_or_default()
+}
+
+/// DBT / DBS — kedalaman dalam feet, meter, dan fathom
+fn encode_depth_units(depth: Option<f64>, talker: &str, kind: &str) -> String {
+    let fields = [
+        format_depth(depth, METERS_TO_FEET),
+        "f".to_string(),
+        format_depth(depth, 1.0),
+        "M".to_string(),
+        format_depth(depth, METERS_TO_FATHOMS),
+        "F".to_string(),
+    ];
+    nmea::sentence(talker, kind, &fields)
+}
+
+/// DPT — kedalaman di bawah transduser (m), offset positif = transduser ke garis air, jangkauan maksimum
+pub fn encode_dpt(state: &DepthState, talker: &str) -> String {
+    let fields = [
+        format_depth(state.depth_below_transducer, 1.0),
+        format!("{:.1}", state.transducer_offset),
+        format!("{:.0}", state.max_range),
+    ];
+    nmea::sentence(talker, "DPT", &fields)
+}
@@ -0,0 +1,224 @@
+use crate::data::fault_data::{Fault, FaultKind, FaultSchedule};
+use crate::services::sensor::Sensor;
+use crate::utils::nmea;
+use crate::utils::sim_rng::SimRng;
+use chrono::{DateTime, Utc};
+use rand::Rng;
+
+/// Field status per jenis kalimat NMEA 0183: (indeks field setelah address, nilai tidak valid).
+const STATUS_FIELDS: &[(&str, &[(usize, &str)])] = &[
+    ("RMC", &[(2, "V")]),
+    ("GLL", &[(6, "V")]),
+    ("GGA", &[(6, "0")]),
+    ("MWV", &[(5, "V")]),
+    ("ROT", &[(2, "V")]),
+    ("THS", &[(2, "V")]),
+    ("VBW", &[(3, "V"), (6, "V")]),
+    ("RMB", &[(1, "V")]),
+    ("APB", &[(1, "V"), (2, "V")]),
+    ("XTE", &[(1, "V"), (2, "V")]),
+];
+
+/// Fault yang aktif pada `now` dan lolos undian `probability` untuk tick publikasi ini;
+/// fault yang sudah berakhir dibuang dari jadwal. Arah lonjakan `spike` diundi per tick.
+pub fn due_faults(schedule: &mut FaultSchedule, now: DateTime<Utc>, rng: &mut SimRng) -> Vec<Fault> {
+    schedule.faults.retain(|fault| now < fault.end);
+    let mut due = Vec::new();
+    for fault in schedule.faults.iter().filter(|fault| fault.is_active(now)) {
+        if fault.probability.is_some_and(|probability| !rng.random_bool(probability)) {
+            continue;
+        }
+        let mut fault = fault.clone();
+        if fault.kind == FaultKind::Spike && rng.random_bool(0.5) {
+            fault.magnitude = fault.magnitude.map(|magnitude| -magnitude);
+        }
+        due.push(fault);
+    }
+    due
+}
+
+/// Field yang diganggu satu fault: field yang diminta, atau field utama sensor
+/// (freeze tanpa field membekukan semua field).
+fn target_fields<S: Sensor>(fault: &Fault) -> Vec<&str> {
+    match (&fault.field, fault.kind) {
+        (Some(field), _) => vec![field.as_str()],
+        (None, FaultKind::Freeze) => S::FAULT_FIELDS.to_vec(),
+        (None, _) => S::FAULT_FIELDS.first().copied().into_iter().collect(),
+    }
+}
+
+/// Menerapkan fault nilai (freeze, spike, bias, NaN) ke salinan state yang akan dipublikasikan.
+/// Freeze menyalin nilai dari `previous`, yaitu state publikasi sebelumnya.
+pub fn apply_value_faults<S: Sensor>(state: &mut S::State, previous: Option<&S::State>, faults: &[Fault]) {
+    let mut previous = previous.cloned();
+    for fault in faults.iter().filter(|fault| fault.kind.uses_field()) {
+        for field in target_fields::<S>(fault) {
+            match fault.kind {
+                FaultKind::Freeze => {
+                    let Some(previous) = previous.as_mut() else { continue };
+                    for (value, frozen) in S::fault_values(state, field).into_iter().zip(S::fault_values(previous, field)) {
+                        *value = *frozen;
+                    }
+                }
+                FaultKind::Spike | FaultKind::Bias => {
+                    let magnitude = fault.magnitude.unwrap_or_default();
+                    S::fault_values(state, field).into_iter().for_each(|value| *value += magnitude);
+                }
+                FaultKind::Nan => S::fault_values(state, field).into_iter().for_each(|value| *value = f64::NAN),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Menerapkan fault output (status tidak valid, checksum salah) ke kalimat NMEA 0183.
+pub fn apply_nmea_faults(sentences: &mut [String], faults: &[Fault]) {
+    if faults.iter().any(|fault| fault.kind == FaultKind::InvalidStatus) {
+        sentences.iter_mut().for_each(|sentence| *sentence = invalidate_status(sentence));
+    }
+    if faults.iter().any(|fault| fault.kind == FaultKind::BadChecksum) {
+        sentences.iter_mut().for_each(|sentence| *sentence = corrupt_checksum(sentence));
+    }
+}
+
+/// Mengganti flag status kalimat (lihat `STATUS_FIELDS`) dengan nilai tidak valid; checksum dihitung ulang.
+pub fn invalidate_status(sentence: &str) -> String {
+    let Some((body, _)) = sentence.strip_prefix('$').and_then(|line| line.rsplit_once('*')) else {
+        return sentence.to_string();
+    };
+    let mut fields: Vec<String> = body.split(',').map(str::to_string).collect();
+    if fields[0].len() != 5 {
+        return sentence.to_string();
+    }
+    let (talker, kind) = fields[0].split_at(2);
+    let (talker, kind) = (talker.to_string(), kind.to_string());
+    let Some((_, status)) = STATUS_FIELDS.iter().find(|(status_kind, _)| *status_kind == kind) else {
+        return sentence.to_string();
+    };
+    for &(index, value) in status.iter() {
+        if let Some(field) = fields.get_mut(index) {
+            *field = value.to_string();
+        }
+    }
+    nmea::sentence(&talker, &kind, &fields[1..])
+}
+
+/// Membalik semua bit checksum sehingga penerima menolak kalimat.
+pub fn corrupt_checksum(sentence: &str) -> String {
+    match sentence.rsplit_once('*').map(|(body, hex)| (body, u8::from_str_radix(hex.trim(), 16))) {
+        Some((body, Ok(checksum))) => format!("{}*{:02X}", body, !checksum),
+        _ => sentence.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::gps_data::{CreateGpsRequest, GpsState};
+    use crate::services::gps_service::Gps;
+    use chrono::Duration;
+    use rand::SeedableRng;
+
+    fn gps_state() -> GpsState {
+        let req: CreateGpsRequest = serde_json::from_value(serde_json::json!({
+            "latitude": 60.0, "longitude": 24.0, "sog": 5.0, "cog": 90.0, "is_running": true
+        }))
+        .unwrap();
+        Gps::create(req)
+    }
+
+    fn fault(kind: FaultKind, field: Option<&str>, magnitude: Option<f64>, start: DateTime<Utc>) -> Fault {
+        Fault {
+            id: 0,
+            kind,
+            field: field.map(str::to_string),
+            magnitude,
+            start,
+            end: start + Duration::seconds(10),
+            probability: None,
+        }
+    }
+
+    #[test]
+    fn due_faults_follow_schedule_and_drop_expired() {
+        let now = Utc::now();
+        let mut schedule = FaultSchedule {
+            next_id: 3,
+            faults: vec![
+                fault(FaultKind::Bias, None, Some(1.0), now - Duration::seconds(20)),
+                fault(FaultKind::Silence, None, None, now),
+                fault(FaultKind::Nan, None, None, now + Duration::seconds(5)),
+            ],
+        };
+        let mut rng = SimRng::seed_from_u64(1);
+        let due = due_faults(&mut schedule, now, &mut rng);
+        assert_eq!(due.iter().map(|fault| fault.kind).collect::<Vec<_>>(), [FaultKind::Silence]);
+        // Fault yang sudah berakhir dibuang, yang belum mulai tetap dijadwalkan
+        assert_eq!(schedule.faults.len(), 2);
+    }
+
+    #[test]
+    fn spike_direction_and_probability_come_from_the_stream() {
+        let now = Utc::now();
+        let mut spike = fault(FaultKind::Spike, None, Some(5.0), now);
+        spike.probability = Some(0.5);
+        let schedule = FaultSchedule { next_id: 1, faults: vec![spike] };
+        let draw = |seed| {
+            let mut rng = SimRng::seed_from_u64(seed);
+            (0..50)
+                .map(|_| due_faults(&mut schedule.clone(), now, &mut rng).first().and_then(|fault| fault.magnitude))
+                .collect::<Vec<_>>()
+        };
+        let run = draw(7);
+        assert_eq!(draw(7), run);
+        assert!(run.contains(&None) && run.contains(&Some(5.0)) && run.contains(&Some(-5.0)));
+    }
+
+    #[test]
+    fn value_faults_change_only_targeted_fields() {
+        let now = Utc::now();
+        let previous = gps_state();
+        let mut state = gps_state();
+        state.latitude = 61.0;
+        state.sog = 6.0;
+        state.cog = 100.0;
+
+        let faults = [
+            fault(FaultKind::Bias, Some("sog"), Some(2.0), now),
+            fault(FaultKind::Nan, Some("cog"), None, now),
+            fault(FaultKind::BadChecksum, None, None, now),
+        ];
+        apply_value_faults::<Gps>(&mut state, Some(&previous), &faults);
+        assert_eq!(state.latitude, 61.0);
+        assert_eq!(state.sog, 8.0);
+        assert!(state.cog.is_nan());
+
+        // Freeze tanpa field membekukan semua field fault ke nilai publikasi sebelumnya
+        apply_value_faults::<Gps>(&mut state, Some(&previous), &[fault(FaultKind::Freeze, None, None, now)]);
+        assert_eq!((state.latitude, state.sog, state.cog), (previous.latitude, previous.sog, previous.cog));
+    }
+
+    #[test]
+    fn invalid_status_rewrites_flag_with_valid_checksum() {
+        let fields = ["120000", "A", "6000.000", "N", "02400.000", "E", "5.0", "90.0", "010524", "", ""];
+        let rmc = nmea::sentence("GP", "RMC", &fields.map(str::to_string));
+        let invalid = invalidate_status(&rmc);
+        let parsed = nmea::parse_sentence(&invalid).unwrap();
+        assert_eq!(parsed.fields[1], "V");
+        assert_eq!(parsed.fields[2], "6000.000");
+
+        // Kalimat tanpa field status tidak diubah
+        let hdt = nmea::sentence("HE", "HDT", &["90.0".to_string(), "T".to_string()]);
+        assert_eq!(invalidate_status(&hdt), hdt);
+    }
+
+    #[test]
+    fn corrupt_checksum_is_rejected() {
+        let hdt = nmea::sentence("HE", "HDT", &["90.0".to_string(), "T".to_string()]);
+        let mut sentences = [hdt.clone()];
+        apply_nmea_faults(&mut sentences, &[fault(FaultKind::BadChecksum, None, None, Utc::now())]);
+        assert_ne!(sentences[0], hdt);
+        assert_eq!(sentences[0].split_once('*').map(|(body, _)| body), hdt.split_once('*').map(|(body, _)| body));
+        assert!(nmea::parse_sentence(&sentences[0]).is_err());
+    }
+}
@@ -0,0 +1,183 @@
+use crate::data::gps_data::{FixQuality, GpsState};
+use crate::utils::sim_rng::SimRng;
+use rand::Rng;
+use rand_distr::{Distribution, StandardNormal};
+
+const EARTH_RADIUS: f64 = 6_371_000.0;
+
+/// Skala semua sigma model error terhadap fix 3D.
+fn error_scale(quality: FixQuality) -> f64 {
+    match quality {
+        FixQuality::NoFix | FixQuality::Fix3d => 1.0,
+        FixQuality::Fix2d => 2.0,
+        FixQuality::Dgps => 0.3,
+        FixQuality::Rtk => 0.01,
+    }
+}
+
+/// Satu langkah Gauss-Markov orde satu (diskretisasi eksak) dengan standar deviasi stasioner `sigma`.
+fn gauss_markov(value: f64, sigma: f64, tau: f64, dt: f64, rng: &mut SimRng) -> f64 {
+    let phi = (-dt / tau.max(dt)).exp();
+    let noise: f64 = StandardNormal.sample(rng);
+    value * phi + sigma * (1.0 - phi * phi).sqrt() * noise
+}
+
+/// Menghitung nilai terukur dari `state.truth`: bias Gauss-Markov + noise putih × HDOP +
+/// lompatan multipath untuk posisi, dan noise vektor kecepatan untuk SOG/COG (sehingga noise
+/// COG membesar saat SOG kecil). Tanpa fix, nilai terukur terakhir dipertahankan.
+pub fn apply_gnss_errors(state: &mut GpsState, rng: &mut SimRng) {
+    let fix = state.fix();
+    if !fix.has_fix() {
+        return;
+    }
+    let model = &state.error_model;
+    if !model.enabled {
+        state.error = Default::default();
+        state.report_truth();
+        return;
+    }
+
+    let dt = state.calculation_rate_ms as f64 / 1000.0;
+    let scale = error_scale(fix);
+    let error = &mut state.error;
+
+    error.bias_north = gauss_markov(error.bias_north, model.bias_sigma, model.bias_time_constant, dt, rng);
+    error.bias_east = gauss_markov(error.bias_east, model.bias_sigma, model.bias_time_constant, dt, rng);
+
+    if error.multipath_remaining > 0.0 {
+        error.multipath_remaining -= dt;
+        if error.multipath_remaining <= 0.0 {
+            (error.multipath_north, error.multipath_east, error.multipath_remaining) = (0.0, 0.0, 0.0);
+        }
+    } else if rng.random_bool((model.multipath_probability * dt).clamp(0.0, 1.0)) {
+        let direction = rng.random_range(0.0..std::f64::consts::TAU);
+        let magnitude = model.multipath_magnitude * rng.random_range(0.5..1.5);
+        error.multipath_north = magnitude * direction.cos();
+        error.multipath_east = magnitude * direction.sin();
+        error.multipath_remaining = model.multipath_duration;
+    }
+
+    // Bias dan multipath disimpan dalam skala fix 3D, jadi perubahan kualitas fix langsung berlaku
+    let noise_sigma = model.noise_sigma * state.hdop.max(0.0);
+    let noise_north: f64 = StandardNormal.sample(&mut *rng);
+    let noise_east: f64 = StandardNormal.sample(&mut *rng);
+    error.north = scale * (error.bias_north + error.multipath_north + noise_sigma * noise_north);
+    error.east = scale * (error.bias_east + error.multipath_east + noise_sigma * noise_east);
+
+    let truth = state.truth;
+    state.latitude = truth.latitude + (error.north / EARTH_RADIUS).to_degrees();
+    state.longitude = truth.longitude + (error.east / (EARTH_RADIUS * truth.latitude.to_radians().cos().max(1e-6))).to_degrees();
+
+    // Kecepatan sebagai vektor (north, east) agar COG acak saat kapal diam
+    let velocity_sigma = model.velocity_sigma * scale;
+    let cog = truth.cog.to_radians();
+    let noise_north: f64 = StandardNormal.sample(&mut *rng);
+    let noise_east: f64 = StandardNormal.sample(&mut *rng);
+    let velocity_north = truth.sog * cog.cos() + velocity_sigma * noise_north;
+    let velocity_east = truth.sog * cog.sin() + velocity_sigma * noise_east;
+    state.sog = velocity_north.hypot(velocity_east);
+    state.cog = velocity_east.atan2(velocity_north).to_degrees().rem_euclid(360.0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::gps_data::CreateGpsRequest;
+    use crate::services::gps_service::Gps;
+    use crate::services::sensor::Sensor;
+    use crate::utils::navigation::angle_diff;
+    use rand::SeedableRng;
+
+    fn gps_state(fix_quality: &str, sog: f64) -> GpsState {
+        let req: CreateGpsRequest = serde_json::from_value(serde_json::json!({
+            "latitude": 60.0, "longitude": 24.0, "sog": sog, "cog": 45.0,
+            "fix_quality": fix_quality, "is_running": true
+        }))
+        .unwrap();
+        let mut state = Gps::create(req);
+        state.error_model.enabled = true;
+        state
+    }
+
+    /// Posisi terukur (lintang, bujur) setiap langkah
+    fn run(state: &mut GpsState, seed: u64, steps: usize) -> Vec<(f64, f64)> {
+        let mut rng = SimRng::seed_from_u64(seed);
+        (0..steps)
+            .map(|_| {
+                apply_gnss_errors(state, &mut rng);
+                (state.latitude, state.longitude)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn gauss_markov_keeps_stationary_sigma() {
+        let mut rng = SimRng::seed_from_u64(3);
+        let mut value = 0.0;
+        let samples: Vec<f64> = (0..20_000)
+            .map(|_| {
+                value = gauss_markov(value, 2.0, 5.0, 1.0, &mut rng);
+                value
+            })
+            .collect();
+        let variance = samples.iter().map(|v| v * v).sum::<f64>() / samples.len() as f64;
+        assert!((variance.sqrt() - 2.0).abs() < 0.2, "sigma {}", variance.sqrt());
+    }
+
+    #[test]
+    fn error_is_deterministic_and_bounded() {
+        let first = run(&mut gps_state("3d", 5.0), 11, 600);
+        assert_eq!(run(&mut gps_state("3d", 5.0), 11, 600), first);
+        assert_ne!(run(&mut gps_state("3d", 5.0), 12, 600), first);
+
+        let mut state = gps_state("3d", 5.0);
+        state.error_model.multipath_probability = 0.0;
+        run(&mut state, 11, 600);
+        let north = (state.latitude - state.truth.latitude).to_radians() * EARTH_RADIUS;
+        assert!(north.abs() < 15.0, "north error {} m", north);
+        assert!((state.error.north - north).abs() < 1e-6);
+    }
+
+    #[test]
+    fn better_fix_has_smaller_error() {
+        let spread = |fix_quality: &str| {
+            let mut state = gps_state(fix_quality, 5.0);
+            state.error_model.multipath_probability = 0.0;
+            let positions = run(&mut state, 5, 2_000);
+            let truth = state.truth.latitude;
+            positions.iter().map(|(lat, _)| (lat - truth).abs()).fold(0.0, f64::max)
+        };
+        assert!(spread("rtk") < spread("dgps"));
+        assert!(spread("dgps") < spread("3d"));
+    }
+
+    #[test]
+    fn disabled_model_or_no_fix_reports_truth_or_holds() {
+        let mut state = gps_state("3d", 5.0);
+        run(&mut state, 1, 10);
+        state.error_model.enabled = false;
+        run(&mut state, 1, 1);
+        assert_eq!((state.latitude, state.longitude, state.sog, state.cog), (60.0, 24.0, 5.0, 45.0));
+
+        let mut state = gps_state("no_fix", 5.0);
+        state.latitude = 1.0;
+        run(&mut state, 1, 10);
+        assert_eq!(state.latitude, 1.0);
+    }
+
+    #[test]
+    fn course_is_noisy_only_when_nearly_stationary() {
+        let cog_spread = |sog: f64| {
+            let mut state = gps_state("3d", sog);
+            let mut rng = SimRng::seed_from_u64(9);
+            (0..200)
+                .map(|_| {
+                    apply_gnss_errors(&mut state, &mut rng);
+                    angle_diff(state.cog, 45.0).abs()
+                })
+                .fold(0.0, f64::max)
+        };
+        assert!(cog_spread(10.0) < 2.0);
+        assert!(cog_spread(0.0) > 90.0);
+    }
+}
@@ -0,0 +1,268 @@
+use crate::data::gps_data::{ConstellationConfig, GnssSystem, SatelliteView, SkyMask, SkyView};
+use crate::utils::navigation::angle_diff;
+use crate::utils::sim_rng::SimRng;
+use chrono::{DateTime, Utc};
+use rand_distr::{Distribution, StandardNormal};
+use std::f64::consts::TAU;
+
+const EARTH_RADIUS_KM: f64 = 6371.0;
+/// Kecepatan rotasi bumi (rad/detik)
+const EARTH_ROTATION: f64 = 7.292_115e-5;
+/// C/N0 minimum agar satelit dipakai untuk fix (dB-Hz)
+const MIN_USED_SNR: f64 = 25.0;
+/// Batas jumlah satelit per fix, seperti kebanyakan penerima
+const MAX_USED: usize = 32;
+/// DOP yang dilaporkan jika geometri tidak cukup untuk fix
+const DOP_UNAVAILABLE: f64 = 99.9;
+
+/// Konstelasi Walker: `planes` bidang orbit × `per_plane` satelit pada orbit lingkaran.
+struct Orbit {
+    planes: usize,
+    per_plane: usize,
+    inclination: f64,
+    radius_km: f64,
+    period_s: f64,
+}
+
+fn orbit(system: GnssSystem) -> Orbit {
+    match system {
+        GnssSystem::Gps => Orbit { planes: 6, per_plane: 4, inclination: 55.0, radius_km: 26_560.0, period_s: 43_082.0 },
+        GnssSystem::Glonass => Orbit { planes: 3, per_plane: 8, inclination: 64.8, radius_km: 25_508.0, period_s: 40_544.0 },
+        GnssSystem::Galileo => Orbit { planes: 3, per_plane: 8, inclination: 56.0, radius_km: 29_600.0, period_s: 50_680.0 },
+        GnssSystem::Beidou => Orbit { planes: 3, per_plane: 8, inclination: 55.0, radius_km: 27_906.0, period_s: 46_380.0 },
+    }
+}
+
+/// Posisi ECEF (km) satelit ke-`index` pada waktu `t` (detik sejak epoch Unix).
+fn satellite_ecef(orbit: &Orbit, index: usize, t: f64) -> [f64; 3] {
+    let (plane, slot) = (index / orbit.per_plane, index % orbit.per_plane);
+    let raan = TAU * plane as f64 / orbit.planes as f64;
+    // Pergeseran fase antar bidang agar satelit tidak sejajar
+    let phase = TAU * (slot as f64 / orbit.per_plane as f64 + plane as f64 / (orbit.planes * orbit.per_plane) as f64);
+    let u = phase + TAU * (t / orbit.period_s).fract();
+    let i = orbit.inclination.to_radians();
+
+    let x = orbit.radius_km * (u.cos() * raan.cos() - u.sin() * i.cos() * raan.sin());
+    let y = orbit.radius_km * (u.cos() * raan.sin() + u.sin() * i.cos() * raan.cos());
+    let z = orbit.radius_km * u.sin() * i.sin();
+
+    // Inersial → ECEF dengan rotasi bumi
+    let theta = (EARTH_ROTATION * t).rem_euclid(TAU);
+    [x * theta.cos() + y * theta.sin(), -x * theta.sin() + y * theta.cos(), z]
+}
+
+/// Elevasi dan azimuth (derajat) satelit dari posisi penerima, beserta vektor satuan ENU.
+fn look_angles(latitude: f64, longitude: f64, satellite: [f64; 3]) -> (f64, f64, [f64; 3]) {
+    let (phi, lambda) = (latitude.to_radians(), longitude.to_radians());
+    let receiver = [
+        EARTH_RADIUS_KM * phi.cos() * lambda.cos(),
+        EARTH_RADIUS_KM * phi.cos() * lambda.sin(),
+        EARTH_RADIUS_KM * phi.sin(),
+    ];
+    let [dx, dy, dz] = [satellite[0] - receiver[0], satellite[1] - receiver[1], satellite[2] - receiver[2]];
+    let east = -lambda.sin() * dx + lambda.cos() * dy;
+    let north = -phi.sin() * lambda.cos() * dx - phi.sin() * lambda.sin() * dy + phi.cos() * dz;
+    let up = phi.cos() * lambda.cos() * dx + phi.cos() * lambda.sin() * dy + phi.sin() * dz;
+    let range = (east * east + north * north + up * up).sqrt();
+
+    let elevation = up.atan2(east.hypot(north)).to_degrees();
+    let azimuth = east.atan2(north).to_degrees().rem_euclid(360.0);
+    (elevation, azimuth, [east / range, north / range, up / range])
+}
+
+/// Apakah satelit pada bearing relatif `relative` dan `elevation` terhalang salah satu mask.
+fn is_masked(masks: &[SkyMask], relative: f64, elevation: f64) -> bool {
+    masks.iter().any(|mask| {
+        let width = (mask.to - mask.from).rem_euclid(360.0);
+        (relative - mask.from).rem_euclid(360.0) <= width && elevation < mask.max_elevation
+    })
+}
+
+/// Invers matriks n×n (Gauss-Jordan); `None` jika singular.
+fn invert(mut m: Vec<Vec<f64>>) -> Option<Vec<Vec<f64>>> {
+    let n = m.len();
+    let mut inv: Vec<Vec<f64>> = (0..n).map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect()).collect();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&a, &b| m[a][col].abs().total_cmp(&m[b][col].abs()))?;
+        if m[pivot][col].abs() < 1e-9 {
+            return None;
+        }
+        m.swap(col, pivot);
+        inv.swap(col, pivot);
+        let p = m[col][col];
+        for j in 0..n {
+            m[col][j] /= p;
+            inv[col][j] /= p;
+        }
+        for row in 0..n {
+            if row != col {
+                let factor = m[row][col];
+                for j in 0..n {
+                    m[row][j] -= factor * m[col][j];
+                    inv[row][j] -= factor * inv[col][j];
+                }
+            }
+        }
+    }
+    Some(inv)
+}
+
+/// HDOP, VDOP, PDOP dari vektor satuan ENU satelit yang dipakai (satu bias jam penerima).
+/// Dengan 3 satelit dihitung solusi 2D (tinggi tetap), sehingga VDOP tidak tersedia.
+fn dilution(lines_of_sight: &[[f64; 3]]) -> (f64, f64, f64) {
+    let unavailable = (DOP_UNAVAILABLE, DOP_UNAVAILABLE, DOP_UNAVAILABLE);
+    if lines_of_sight.len() < 3 {
+        return unavailable;
+    }
+    let three_d = lines_of_sight.len() >= 4;
+    let rows: Vec<Vec<f64>> = lines_of_sight
+        .iter()
+        .map(|[e, n, u]| if three_d { vec![-e, -n, -u, 1.0] } else { vec![-e, -n, 1.0] })
+        .collect();
+    let size = if three_d { 4 } else { 3 };
+    let normal: Vec<Vec<f64>> = (0..size)
+        .map(|i| (0..size).map(|j| rows.iter().map(|row| row[i] * row[j]).sum()).collect())
+        .collect();
+
+    let Some(q) = invert(normal) else { return unavailable };
+    let hdop = (q[0][0] + q[1][1]).sqrt();
+    if !three_d {
+        return (hdop.min(DOP_UNAVAILABLE), DOP_UNAVAILABLE, DOP_UNAVAILABLE);
+    }
+    let (vdop, pdop) = (q[2][2].sqrt(), (q[0][0] + q[1][1] + q[2][2]).sqrt());
+    (hdop.min(DOP_UNAVAILABLE), vdop.min(DOP_UNAVAILABLE), pdop.min(DOP_UNAVAILABLE))
+}
+
+/// Menghitung satelit terlihat dari posisi sebenarnya pada waktu simulasi `time`.
+/// `heading` (derajat true) memutar mask sektor yang relatif terhadap haluan kapal.
+pub fn compute_sky(
+    config: &ConstellationConfig,
+    latitude: f64,
+    longitude: f64,
+    heading: f64,
+    time: &DateTime<Utc>,
+    rng: &mut SimRng,
+) -> SkyView {
+    let t = time.timestamp_millis() as f64 / 1000.0;
+    let mut systems = config.systems.clone();
+    systems.sort();
+    systems.dedup();
+
+    let mut satellites = Vec::new();
+    let mut lines_of_sight = Vec::new();
+    for system in systems {
+        let orbit = orbit(system);
+        for index in 0..orbit.planes * orbit.per_plane {
+            let (elevation, azimuth, los) = look_angles(latitude, longitude, satellite_ecef(&orbit, index, t));
+            if elevation < 0.0 {
+                continue;
+            }
+            let masked = is_masked(&config.masks, angle_diff(azimuth, heading).rem_euclid(360.0), elevation);
+            let noise: f64 = StandardNormal.sample(&mut *rng);
+            let snr = (!masked).then(|| (30.0 + 18.0 * elevation.to_radians().sin() + 1.5 * noise).clamp(0.0, 99.0).round());
+            let used = snr.is_some_and(|snr| snr >= MIN_USED_SNR)
+                && elevation >= config.elevation_mask
+                && lines_of_sight.len() < MAX_USED;
+            if used {
+                lines_of_sight.push(los);
+            }
+            satellites.push(SatelliteView {
+                system,
+                prn: index as u8 + 1,
+                elevation: elevation.round(),
+                azimuth: azimuth.round().rem_euclid(360.0),
+                snr,
+                used,
+            });
+        }
+    }
+
+    let (hdop, vdop, pdop) = dilution(&lines_of_sight);
+    SkyView { satellites, used: lines_of_sight.len() as u8, hdop, vdop, pdop }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn unit(elevation: f64, azimuth: f64) -> [f64; 3] {
+        let (e, a) = (elevation.to_radians(), azimuth.to_radians());
+        [e.cos() * a.sin(), e.cos() * a.cos(), e.sin()]
+    }
+
+    fn sky(config: &ConstellationConfig, heading: f64) -> SkyView {
+        let time = DateTime::parse_from_rfc3339("2024-05-01T12:00:00Z").unwrap().to_utc();
+        compute_sky(config, 60.0, 24.0, heading, &time, &mut SimRng::seed_from_u64(1))
+    }
+
+    #[test]
+    fn look_angles_of_satellite_overhead() {
+        let (elevation, _, los) = look_angles(0.0, 0.0, [26_560.0, 0.0, 0.0]);
+        assert!((elevation - 90.0).abs() < 1e-9);
+        assert!((los[2] - 1.0).abs() < 1e-9);
+
+        let (elevation, azimuth, _) = look_angles(0.0, 0.0, [EARTH_RADIUS_KM, 0.0, 1_000.0]);
+        assert!(elevation.abs() < 1e-9);
+        assert!(azimuth.abs() < 1e-9);
+    }
+
+    #[test]
+    fn dilution_needs_geometry() {
+        let unavailable = (DOP_UNAVAILABLE, DOP_UNAVAILABLE, DOP_UNAVAILABLE);
+        assert_eq!(dilution(&[unit(90.0, 0.0), unit(30.0, 0.0)]), unavailable);
+        // Semua satelit di arah yang sama: matriks singular
+        assert_eq!(dilution(&[unit(45.0, 90.0); 5]), unavailable);
+
+        // Tiga satelit: solusi 2D, VDOP/PDOP tidak tersedia
+        let (hdop, vdop, pdop) = dilution(&[unit(10.0, 0.0), unit(10.0, 120.0), unit(10.0, 240.0)]);
+        assert!(hdop < DOP_UNAVAILABLE);
+        assert_eq!((vdop, pdop), (DOP_UNAVAILABLE, DOP_UNAVAILABLE));
+    }
+
+    #[test]
+    fn dilution_of_spread_constellation() {
+        let good = [unit(90.0, 0.0), unit(15.0, 0.0), unit(15.0, 120.0), unit(15.0, 240.0)];
+        let (hdop, vdop, pdop) = dilution(&good);
+        assert!((pdop * pdop - hdop * hdop - vdop * vdop).abs() < 1e-9);
+        assert!(hdop > 0.5 && hdop < 2.0, "hdop {}", hdop);
+
+        // Satelit berkumpul di satu sisi langit: DOP jauh lebih buruk
+        let clustered = [unit(70.0, 0.0), unit(50.0, 10.0), unit(40.0, 350.0), unit(60.0, 20.0)];
+        assert!(dilution(&clustered).0 > 2.0 * hdop);
+    }
+
+    #[test]
+    fn sector_mask_wraps_through_north() {
+        let masks = [SkyMask { from: 300.0, to: 60.0, max_elevation: 30.0 }];
+        assert!(is_masked(&masks, 330.0, 10.0));
+        assert!(is_masked(&masks, 30.0, 29.0));
+        assert!(!is_masked(&masks, 30.0, 31.0));
+        assert!(!is_masked(&masks, 180.0, 10.0));
+    }
+
+    #[test]
+    fn compute_sky_is_consistent_and_respects_masks() {
+        let config = ConstellationConfig { enabled: true, ..Default::default() };
+        let open = sky(&config, 0.0);
+        assert_eq!(open.used as usize, open.satellites.iter().filter(|sat| sat.used).count());
+        assert!(open.used >= 4 && open.used as usize <= MAX_USED);
+        assert!(open.satellites.iter().all(|sat| sat.elevation >= 0.0));
+        assert!(open.satellites.iter().filter(|sat| sat.used).all(|sat| sat.elevation >= config.elevation_mask.round()));
+        assert!(open.hdop < DOP_UNAVAILABLE);
+        assert_eq!(sky(&config, 0.0).satellites.len(), open.satellites.len());
+
+        // Mask di atas seluruh langit: tidak ada satelit yang diterima
+        let blocked = ConstellationConfig {
+            masks: vec![
+                SkyMask { from: 0.0, to: 180.0, max_elevation: 91.0 },
+                SkyMask { from: 180.0, to: 0.0, max_elevation: 91.0 },
+            ],
+            ..config.clone()
+        };
+        let blocked = sky(&blocked, 0.0);
+        assert_eq!(blocked.used, 0);
+        assert!(blocked.satellites.iter().all(|sat| sat.snr.is_none()));
+        assert_eq!(blocked.hdop, DOP_UNAVAILABLE);
+    }
+}
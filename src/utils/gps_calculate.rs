@@ -1,22 +1,14 @@
 use crate::data::gps_data::GpsState;
+use crate::data::vessel_data::VesselState;
+use crate::utils::vessel_calculate;
+use crate::utils::sim_clock;
 use chrono::{Datelike, Utc}; // Menggunakan trait Datelike dari chrono
-use std::f64::consts::PI;
 use time::{Date, Month}; // Mengimpor Month dari time
 use uom::si::angle::degree;
 use uom::si::f32::*;
 use uom::si::length::meter;
 use world_magnetic_model::GeomagneticField;
 
-const EARTH_RADIUS: f64 = 6_371_000.0;
-
-fn deg_to_rad(deg: f64) -> f64 {
-    deg * PI / 180.0
-}
-
-fn rad_to_deg(rad: f64) -> f64 {
-    rad * 180.0 / PI
-}
-
 // DIUBAH: Dibuat `pub` agar bisa diakses controller. Logika tanggal diperbaiki.
 pub fn calculate_magnetic_variation(lat: f64, lon: f64, date_time: &chrono::DateTime<Utc>) -> f64 {
     // Konversi u32 month dari chrono ke enum Month dari time
@@ -42,26 +34,23 @@ pub fn calculate_magnetic_variation(lat: f64, lon: f64, date_time: &chrono::Date
     }
 }
 
+/// Dead reckoning posisi sebenarnya (`state.truth`) dari SOG/COG sebenarnya.
 pub fn calculate_next_gps_state(state: &mut GpsState) {
     let dt_seconds = state.calculation_rate_ms as f64 / 1000.0;
-    let speed_mps = state.sog * 0.514444;
-    let distance = speed_mps * dt_seconds;
+    let truth = &mut state.truth;
+    let distance = truth.sog * 0.514444 * dt_seconds;
 
-    let lat_rad = deg_to_rad(state.latitude);
-    let lon_rad = deg_to_rad(state.longitude);
-    let course_rad = deg_to_rad(state.cog);
-    let angular_distance = distance / EARTH_RADIUS;
-
-    let new_lat_rad = (lat_rad.sin() * angular_distance.cos()
-        + lat_rad.cos() * angular_distance.sin() * course_rad.cos())
-    .asin();
-
-    let new_lon_rad = lon_rad
-        + (course_rad.sin() * angular_distance.sin() * lat_rad.cos())
-            .atan2(angular_distance.cos() - lat_rad.sin() * new_lat_rad.sin());
+    (truth.latitude, truth.longitude) = vessel_calculate::destination(truth.latitude, truth.longitude, truth.cog, distance);
+    state.last_update = sim_clock::now();
+    state.variation = calculate_magnetic_variation(truth.latitude, truth.longitude, &state.last_update);
+}
 
-    state.latitude = rad_to_deg(new_lat_rad).clamp(-90.0, 90.0);
-    state.longitude = (rad_to_deg(new_lon_rad) + 180.0).rem_euclid(360.0) - 180.0;
-    state.last_update = Utc::now();
-    state.variation = calculate_magnetic_variation(state.latitude, state.longitude, &state.last_update);
-}
\ No newline at end of file
+/// Mengambil posisi, SOG, dan COG sebenarnya dari model kapal.
+pub fn sample_vessel(state: &mut GpsState, vessel: &VesselState) {
+    state.truth.latitude = vessel.latitude;
+    state.truth.longitude = vessel.longitude;
+    state.truth.sog = vessel.sog;
+    state.truth.cog = vessel.cog;
+    state.last_update = sim_clock::now();
+    state.variation = calculate_magnetic_variation(vessel.latitude, vessel.longitude, &state.last_update);
+}
@@ -0,0 +1,233 @@
+use crate::data::gps_data::{FixQuality, GnssSystem, GpsSentence, GpsState, SatelliteView, SkyView};
+use crate::utils::nmea::{self, format_angle, format_date, format_latitude, format_longitude, format_time};
+
+const KNOTS_TO_KMH: f64 = 1.852;
+
+/// Menghasilkan kalimat NMEA untuk setiap jenis di `sentences`, sesuai urutannya.
+/// GSA/GSV (satu set per sistem satelit) hanya dikirim jika simulasi konstelasi aktif.
+pub fn encode_gps_sentences(state: &GpsState, talker: &str, sentences: &[GpsSentence]) -> Vec<String> {
+    sentences
+        .iter()
+        .flat_map(|kind| match kind {
+            GpsSentence::Rmc => vec![encode_rmc(state, talker)],
+            GpsSentence::Gga => vec![encode_gga(state, talker)],
+            GpsSentence::Vtg => vec![encode_vtg(state, talker)],
+            GpsSentence::Gll => vec![encode_gll(state, talker)],
+            GpsSentence::Zda => vec![encode_zda(state, talker)],
+            GpsSentence::Gsa => encode_gsa(state),
+            GpsSentence::Gsv => encode_gsv(state),
+        })
+        .collect()
+}
+
+/// Mode indicator NMEA 2.3+ (RMC, VTG, GLL).
+fn mode_indicator(quality: FixQuality) -> &'static str {
+    match quality {
+        FixQuality::NoFix => "N",
+        FixQuality::Fix2d | FixQuality::Fix3d => "A",
+        FixQuality::Dgps => "D",
+        FixQuality::Rtk => "R",
+    }
+}
+
+/// Field status RMC/GLL: A = data valid, V = tidak ada fix.
+fn status(quality: FixQuality) -> &'static str {
+    if quality.has_fix() { "A" } else { "V" }
+}
+
+/// Field fix quality GGA.
+fn gga_quality(quality: FixQuality) -> &'static str {
+    match quality {
+        FixQuality::NoFix => "0",
+        FixQuality::Fix2d | FixQuality::Fix3d => "1",
+        FixQuality::Dgps => "2",
+        FixQuality::Rtk => "4",
+    }
+}
+
+/// Posisi sebagai field lintang/bujur; kosong jika tidak ada fix.
+fn position_fields(state: &GpsState) -> [String; 4] {
+    if !state.fix().has_fix() {
+        return Default::default();
+    }
+    let [lat, ns] = format_latitude(state.latitude);
+    let [lon, ew] = format_longitude(state.longitude);
+    [lat, ns, lon, ew]
+}
+
+/// Variasi magnetik sebagai pasangan field `x.x,E|W` (variation positif = East).
+fn variation_fields(variation: f64) -> [String; 2] {
+    let direction = if variation < 0.0 { "W" } else { "E" };
+    [format!("{:.1}", variation.abs()), direction.to_string()]
+}
+
+/// RMC — Recommended Minimum Specific GNSS Data
+pub fn encode_rmc(state: &GpsState, talker: &str) -> String {
+    let [lat, ns, lon, ew] = position_fields(state);
+    let [var, var_ew] = variation_fields(state.variation);
+    let fix = state.fix();
+    let has_fix = fix.has_fix();
+    let fields = [
+        format_time(&state.last_update),
+        status(fix).to_string(),
+        lat, ns, lon, ew,
+        if has_fix { format!("{:.1}", state.sog) } else { String::new() },
+        if has_fix { format_angle(state.cog) } else { String::new() },
+        format_date(&state.last_update),
+        var, var_ew,
+        mode_indicator(fix).to_string(),
+    ];
+    nmea::sentence(talker, "RMC", &fields)
+}
+
+/// GGA — Global Positioning System Fix Data
+pub fn encode_gga(state: &GpsState, talker: &str) -> String {
+    let [lat, ns, lon, ew] = position_fields(state);
+    let fix = state.fix();
+    let has_fix = fix.has_fix();
+    let fields = [
+        format_time(&state.last_update),
+        lat, ns, lon, ew,
+        gga_quality(fix).to_string(),
+        format!("{:02}", state.satellites_used()),
+        if has_fix { format!("{:.1}", state.hdop) } else { String::new() },
+        "0.0".to_string(), "M".to_string(), // altitude antena
+        "0.0".to_string(), "M".to_string(), // geoidal separation
+        String::new(), String::new(),       // umur & ID stasiun DGPS
+    ];
+    nmea::sentence(talker, "GGA", &fields)
+}
+
+/// VTG — Course Over Ground and Ground Speed
+pub fn encode_vtg(state: &GpsState, talker: &str) -> String {
+    let fix = state.fix();
+    let value = |text: String| if fix.has_fix() { text } else { String::new() };
+    let fields = [
+        value(format_angle(state.cog)), "T".to_string(),
+        value(format_angle(state.cog - state.variation)), "M".to_string(),
+        value(format!("{:.1}", state.sog)), "N".to_string(),
+        value(format!("{:.1}", state.sog * KNOTS_TO_KMH)), "K".to_string(),
+        mode_indicator(fix).to_string(),
+    ];
+    nmea::sentence(talker, "VTG", &fields)
+}
+
+/// GLL — Geographic Position, Latitude/Longitude
+pub fn encode_gll(state: &GpsState, talker: &str) -> String {
+    let [lat, ns, lon, ew] = position_fields(state);
+    let fields = [
+        lat, ns, lon, ew,
+        format_time(&state.last_update),
+        status(state.fix()).to_string(),
+        mode_indicator(state.fix()).to_string(),
+    ];
+    nmea::sentence(talker, "GLL", &fields)
+}
+
+/// ZDA — Time & Date (UTC, zona lokal 00:00)
+pub fn encode_zda(state: &GpsState, talker: &str) -> String {
+    let time = &state.last_update;
+    let fields = [
+        format_time(time),
+        time.format("%d").to_string(),
+        time.format("%m").to_string(),
+        time.format("%Y").to_string(),
+        "00".to_string(),
+        "00".to_string(),
+    ];
+    nmea::sentence(talker, "ZDA", &fields)
+}
+
+/// Talker GSV per sistem satelit.
+fn system_talker(system: GnssSystem) -> &'static str {
+    match system {
+        GnssSystem::Gps => "GP",
+        GnssSystem::Glonass => "GL",
+        GnssSystem::Galileo => "GA",
+        GnssSystem::Beidou => "GB",
+    }
+}
+
+/// System ID NMEA 4.10 (field terakhir GSA).
+fn system_id(system: GnssSystem) -> u8 {
+    match system {
+        GnssSystem::Gps => 1,
+        GnssSystem::Glonass => 2,
+        GnssSystem::Galileo => 3,
+        GnssSystem::Beidou => 4,
+    }
+}
+
+/// Nomor satelit NMEA: GLONASS 65–96, sistem lain sesuai PRN.
+fn satellite_id(satellite: &SatelliteView) -> u8 {
+    match satellite.system {
+        GnssSystem::Glonass => satellite.prn + 64,
+        _ => satellite.prn,
+    }
+}
+
+fn sky_systems(state: &GpsState) -> Option<(&SkyView, Vec<GnssSystem>)> {
+    let sky = state.sky.as_ref()?;
+    let mut systems = state.constellation.systems.clone();
+    systems.sort();
+    systems.dedup();
+    Some((sky, systems))
+}
+
+/// GSA — GNSS DOP and Active Satellites, satu kalimat per sistem (talker GN jika lebih dari satu).
+pub fn encode_gsa(state: &GpsState) -> Vec<String> {
+    let Some((sky, systems)) = sky_systems(state) else { return Vec::new() };
+    let talker = match systems.as_slice() {
+        [system] => system_talker(*system),
+        _ => "GN",
+    };
+    let fix_type = match state.fix() {
+        FixQuality::NoFix => "1",
+        FixQuality::Fix2d => "2",
+        _ => "3",
+    };
+    let dop = |value: f64| format!("{:.1}", value);
+
+    systems
+        .iter()
+        .map(|&system| {
+            let mut ids: Vec<String> = sky
+                .satellites
+                .iter()
+                .filter(|satellite| satellite.system == system && satellite.used)
+                .take(12)
+                .map(|satellite| format!("{:02}", satellite_id(satellite)))
+                .collect();
+            ids.resize(12, String::new());
+
+            let mut fields = vec!["A".to_string(), fix_type.to_string()];
+            fields.extend(ids);
+            fields.extend([dop(sky.pdop), dop(sky.hdop), dop(sky.vdop), system_id(system).to_string()]);
+            nmea::sentence(talker, "GSA", &fields)
+        })
+        .collect()
+}
+
+/// GSV — GNSS Satellites in View, empat satelit per kalimat untuk setiap sistem.
+/// Satelit yang terhalang mask sektor dikirim tanpa SNR.
+pub fn encode_gsv(state: &GpsState) -> Vec<String> {
+    let Some((sky, systems)) = sky_systems(state) else { return Vec::new() };
+    let mut sentences = Vec::new();
+    for system in systems {
+        let in_view: Vec<&SatelliteView> = sky.satellites.iter().filter(|satellite| satellite.system == system).collect();
+        let total = in_view.len().div_ceil(4).max(1);
+        for number in 0..total {
+            let mut fields = vec![total.to_string(), (number + 1).to_string(), format!("{:02}", in_view.len())];
+            for satellite in in_view.iter().skip(number * 4).take(4) {
+                fields.extend([
+                    format!("{:02}", satellite_id(satellite)),
+                    format!("{:02}", satellite.elevation as i32),
+                    format!("{:03}", satellite.azimuth as i32),
+                    satellite.snr.map(|snr| format!("{:02}", snr as i32)).unwrap_or_default(),
+                ]);
+            }
+            sentences.push(nmea::sentence(system_talker(system), "GSV", &fields));
+        }
+    }
+    sentences
+}
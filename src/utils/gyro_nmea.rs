@@ -0,0 +1,67 @@
+use crate::data::gyro_data::{GyroSentence, GyroState};
+use crate::utils::nmea::{self, format_angle};
+
+/// Menghasilkan kalimat NMEA untuk setiap jenis di `sentences`, sesuai urutannya.
+/// `variation` adalah variasi magnetik di posisi GPS saat ini; jika `None` (belum ada GPS),
+/// kalimat HDG dilewati karena heading magnetik tidak bisa dihitung.
+pub fn encode_gyro_sentences(
+    state: &GyroState,
+    talker: &str,
+    sentences: &[GyroSentence],
+    deviation: f64,
+    variation: Option<f64>,
+) -> Vec<String> {
+    sentences
+        .iter()
+        .filter_map(|kind| match kind {
+            GyroSentence::Hdt => Some(encode_hdt(state, talker)),
+            GyroSentence::Ths => Some(encode_ths(state, talker)),
+            GyroSentence::Rot => Some(encode_rot(state, talker)),
+            GyroSentence::Hdg => variation.map(|var| encode_hdg(state, talker, deviation, var)),
+            GyroSentence::Xdr => Some(encode_xdr(state, talker)),
+        })
+        .collect()
+}
+
+/// Pasangan field `x.x,E|W` untuk deviasi/variasi (positif = East).
+fn magnetic_fields(value: f64) -> [String; 2] {
+    let direction = if value < 0.0 { "W" } else { "E" };
+    [format!("{:.1}", value.abs()), direction.to_string()]
+}
+
+/// HDT — Heading, True
+pub fn encode_hdt(state: &GyroState, talker: &str) -> String {
+    nmea::sentence(talker, "HDT", &[format_angle(state.yaw), "T".to_string()])
+}
+
+/// THS — True Heading and Status (A = autonomous)
+pub fn encode_ths(state: &GyroState, talker: &str) -> String {
+    nmea::sentence(talker, "THS", &[format_angle(state.yaw), "A".to_string()])
+}
+
+/// ROT — Rate of Turn, derajat per menit (negatif = haluan berbelok ke kiri)
+pub fn encode_rot(state: &GyroState, talker: &str) -> String {
+    nmea::sentence(talker, "ROT", &[format!("{:.1}", state.yaw_rate * 60.0), "A".to_string()])
+}
+
+/// HDG — Heading, Deviation & Variation.
+/// Heading sensor magnetik = heading true - variasi - deviasi.
+pub fn encode_hdg(state: &GyroState, talker: &str, deviation: f64, variation: f64) -> String {
+    let [dev, dev_ew] = magnetic_fields(deviation);
+    let [var, var_ew] = magnetic_fields(variation);
+    let fields = [
+        format_angle(state.yaw - variation - deviation),
+        dev, dev_ew,
+        var, var_ew,
+    ];
+    nmea::sentence(talker, "HDG", &fields)
+}
+
+/// XDR — Transducer Measurements untuk pitch dan roll (tipe A = sudut, satuan D = derajat)
+pub fn encode_xdr(state: &GyroState, talker: &str) -> String {
+    let fields = [
+        "A".to_string(), format!("{:.1}", state.pitch), "D".to_string(), "PTCH".to_string(),
+        "A".to_string(), format!("{:.1}", state.roll), "D".to_string(), "ROLL".to_string(),
+    ];
+    nmea::sentence(talker, "XDR", &fields)
+}
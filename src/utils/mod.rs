@@ -1,4 +1,39 @@
 pub mod net;
 pub mod gps_calculate;
 pub mod gyro_calculate;
-pub mod mqtt_manager;
\ No newline at end of file
+pub mod anemo_calculate;
+pub mod baro_calculate;
+pub mod thermal_calculate;
+pub mod depth_calculate;
+pub mod speedlog_calculate;
+pub mod vessel_calculate;
+pub mod route_calculate;
+pub mod navigation;
+pub mod mqtt_manager;
+pub mod nmea;
+pub mod gps_nmea;
+pub mod gyro_nmea;
+pub mod anemo_nmea;
+pub mod baro_nmea;
+pub mod thermal_nmea;
+pub mod depth_nmea;
+pub mod speedlog_nmea;
+pub mod route_nmea;
+pub mod route_import;
+pub mod track_recorder;
+pub mod track_export;
+pub mod nmea_input;
+pub mod udp_output;
+pub mod signalk;
+pub mod n2k;
+pub mod can_output;
+pub mod recording;
+pub mod replay;
+pub mod playback;
+pub mod nmea_log;
+pub mod sim_clock;
+pub mod bathymetry;
+pub mod sim_rng;
+pub mod gnss_error;
+pub mod gnss_sky;
+pub mod fault_injection;
@@ -1,10 +1,24 @@
+use crate::utils::net::{broadcast_ws_message, Clients};
 use rumqttc::{AsyncClient, Event, EventLoop, MqttOptions, Packet, QoS};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::Duration;
-use tokio::time::sleep;
 use tokio::select;
+use tokio::sync::{mpsc, watch};
+use tokio::time::{sleep_until, Instant};
+
+/// Backoff reconnect: dimulai dari BACKOFF_BASE, digandakan tiap kegagalan, maksimal BACKOFF_MAX
+const BACKOFF_BASE: Duration = Duration::from_millis(500);
+const BACKOFF_MAX: Duration = Duration::from_secs(30);
+/// Jumlah maksimum pesan yang disimpan selama broker tidak terhubung
+const BUFFER_CAPACITY: usize = 100;
+/// Kapasitas antrian request rumqttc, cukup untuk mengirim ulang seluruh buffer
+const CLIENT_CAPACITY: usize = BUFFER_CAPACITY + 10;
 
 /// Status koneksi MQTT
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum MqttState {
     Disconnected,
     Connecting,
@@ -18,18 +32,46 @@ pub enum MqttCommand {
     Stop,
 }
 
+/// Ringkasan status koneksi untuk endpoint `/api/{sensor}/status`
+#[derive(Debug, Clone, Serialize)]
+pub struct MqttStatus {
+    pub state: MqttState,
+    pub broker: Option<String>,
+    pub reconnect_attempts: u32,
+    pub buffered_messages: usize,
+    pub last_error: Option<String>,
+}
+
 /// Konfigurasi dasar MQTT per service
 #[derive(Clone)]
 pub struct MqttServiceConfig {
-    pub name: String,
     pub client_id: String,
-    pub topic_prefix: String,
     pub ip: String,
     pub port: u16,
-    pub username: String,
-    pub password: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
     pub keep_alive: Duration,
-    pub publish_interval: Duration,
+}
+
+impl MqttServiceConfig {
+    /// Membuat config dari field broker milik SensorConfig.
+    /// Mengembalikan `None` jika IP atau port belum diisi.
+    pub fn from_broker(
+        name: &str,
+        ip: Option<String>,
+        port: Option<u16>,
+        username: Option<String>,
+        password: Option<String>,
+    ) -> Option<Self> {
+        Some(Self {
+            client_id: format!("vessel-{}", name.to_lowercase()),
+            ip: ip?,
+            port: port?,
+            username,
+            password,
+            keep_alive: Duration::from_secs(5),
+        })
+    }
 }
 
 struct MqttConnection {
@@ -38,43 +80,106 @@ struct MqttConnection {
 }
 
 impl MqttConnection {
-    async fn connect(cfg: &MqttServiceConfig) -> Option<Self> {
+    fn connect(cfg: &MqttServiceConfig) -> Self {
         let mut mqttoptions = MqttOptions::new(&cfg.client_id, &cfg.ip, cfg.port);
-        mqttoptions.set_credentials(&cfg.username, &cfg.password);
+        if let Some(username) = &cfg.username {
+            mqttoptions.set_credentials(username, cfg.password.clone().unwrap_or_default());
+        }
         mqttoptions.set_keep_alive(cfg.keep_alive);
 
-        let (client, eventloop) = AsyncClient::new(mqttoptions, 10);
-        println!(
-            "[MQTT Manager {}]: Connected to {}:{}",
-            cfg.name, cfg.ip, cfg.port
-        );
-
-        Some(Self { client, eventloop })
-    }
-
-    async fn publish_message(
-        &self,
-        topics: &[String],
-        payload: String,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        for topic in topics {
-            self.client
-                .publish(topic, QoS::AtLeastOnce, false, payload.clone())
-                .await?;
-        }
-        Ok(())
+        let (client, eventloop) = AsyncClient::new(mqttoptions, CLIENT_CAPACITY);
+        Self { client, eventloop }
     }
 }
 
-/// MqttManager — digunakan oleh semua service (GPS, Gyro, dsb)
-#[derive(Clone)]
+/// MqttManager — satu instance per service (GPS, Gyro, dsb).
+/// Client yang aktif diganti oleh `start_service_manager` setiap kali koneksi dibangun ulang.
+/// Selama broker belum terhubung, pesan disimpan di buffer (maks. BUFFER_CAPACITY, yang terlama dibuang)
+/// lalu dikirim ulang begitu koneksi tersambung.
 pub struct MqttManager {
-    client: AsyncClient,
+    name: String,
+    client: RwLock<Option<AsyncClient>>,
+    status: RwLock<MqttStatus>,
+    state_tx: watch::Sender<MqttState>,
+    buffer: Mutex<VecDeque<(String, String)>>,
+    command_tx: mpsc::Sender<MqttCommand>,
 }
 
 impl MqttManager {
-    pub fn new(client: AsyncClient) -> Self {
-        Self { client }
+    pub fn new(name: &str, command_tx: mpsc::Sender<MqttCommand>) -> Self {
+        Self {
+            name: name.to_string(),
+            client: RwLock::new(None),
+            status: RwLock::new(MqttStatus {
+                state: MqttState::Disconnected,
+                broker: None,
+                reconnect_attempts: 0,
+                buffered_messages: 0,
+                last_error: None,
+            }),
+            state_tx: watch::channel(MqttState::Disconnected).0,
+            buffer: Mutex::new(VecDeque::with_capacity(BUFFER_CAPACITY)),
+            command_tx,
+        }
+    }
+
+    pub fn state(&self) -> MqttState {
+        self.status.read().unwrap().state
+    }
+
+    pub fn status(&self) -> MqttStatus {
+        let mut status = self.status.read().unwrap().clone();
+        status.buffered_messages = self.buffer.lock().unwrap().len();
+        status
+    }
+
+    /// Receiver yang menerima nilai baru setiap kali MqttState berubah.
+    pub fn subscribe_state(&self) -> watch::Receiver<MqttState> {
+        self.state_tx.subscribe()
+    }
+
+    fn set_state(&self, state: MqttState) {
+        self.status.write().unwrap().state = state;
+        self.state_tx.send_if_modified(|current| {
+            let changed = *current != state;
+            *current = state;
+            changed
+        });
+    }
+
+    fn buffer_message(&self, topic: &str, payload: &str) {
+        let mut buffer = self.buffer.lock().unwrap();
+        if buffer.len() >= BUFFER_CAPACITY {
+            buffer.pop_front();
+        }
+        buffer.push_back((topic.to_string(), payload.to_string()));
+    }
+
+    /// Kirim ulang semua pesan di buffer lewat client yang baru tersambung.
+    fn flush_buffer(&self) {
+        let pending: Vec<(String, String)> = self.buffer.lock().unwrap().drain(..).collect();
+        if pending.is_empty() {
+            return;
+        }
+        let Some(client) = self.client.read().unwrap().clone() else { return };
+
+        println!("[MQTT Manager {}]: Flushing {} buffered messages.", self.name, pending.len());
+        for (i, (topic, payload)) in pending.iter().enumerate() {
+            if client.try_publish(topic, QoS::AtLeastOnce, false, payload.clone()).is_err() {
+                // Antrian penuh: simpan sisanya untuk dicoba lagi pada koneksi berikutnya
+                for (topic, payload) in &pending[i..] {
+                    self.buffer_message(topic, payload);
+                }
+                break;
+            }
+        }
+    }
+
+    /// Mengirim perintah (Reconnect/Stop) ke loop manajer milik service ini.
+    pub async fn send_command(&self, cmd: MqttCommand) {
+        if self.command_tx.send(cmd).await.is_err() {
+            eprintln!("[MQTT Manager {}]: Manager loop is not running.", self.name);
+        }
     }
 
     pub async fn publish_message(
@@ -82,82 +187,228 @@ impl MqttManager {
         topics: &[String],
         payload: String,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let client = self
+            .client
+            .read()
+            .unwrap()
+            .clone()
+            .ok_or("MQTT broker is not configured")?;
+
+        if self.state() != MqttState::Connected {
+            for topic in topics {
+                self.buffer_message(topic, &payload);
+            }
+            return Ok(());
+        }
+
         for topic in topics {
-            self.client
-                .publish(topic, QoS::AtLeastOnce, false, payload.clone())
-                .await?;
+            if client.try_publish(topic, QoS::AtLeastOnce, false, payload.clone()).is_err() {
+                self.buffer_message(topic, &payload);
+            }
         }
         Ok(())
     }
 }
 
-/// Optional: loop manajerial MQTT (opsional, bisa dipakai untuk reconnect otomatis)
-#[allow(dead_code)]
-pub async fn start_service_manager(
-    cfg: MqttServiceConfig,
-    mut command_rx: tokio::sync::mpsc::Receiver<MqttCommand>,
-) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let mut state = MqttState::Disconnected;
-    let mut connection: Option<MqttConnection> = None;
-
-    loop {
-        select! {
-            Some(cmd) = command_rx.recv() => {
-                match cmd {
-                    MqttCommand::Reconnect => {
-                        println!("[MQTT Manager {}]: Reconnect command received.", cfg.name);
-                        state = MqttState::Disconnected;
-                        connection = None;
+/// Loop manajerial MQTT: membangun koneksi dari `config_source`, mem-poll event loop,
+/// dan membangun ulang koneksi saat menerima `MqttCommand::Reconnect`.
+/// Jika broker putus, koneksi dicoba ulang otomatis dengan exponential backoff + jitter.
+pub fn start_service_manager<F>(
+    manager: Arc<MqttManager>,
+    config_source: F,
+    mut command_rx: mpsc::Receiver<MqttCommand>,
+) where
+    F: Fn() -> Option<MqttServiceConfig> + Send + 'static,
+{
+    tokio::spawn(async move {
+        'manager: loop {
+            let mut connection = match config_source() {
+                Some(cfg) => {
+                    println!("[MQTT Manager {}]: Connecting to {}:{}...", manager.name, cfg.ip, cfg.port);
+                    let conn = MqttConnection::connect(&cfg);
+                    *manager.client.write().unwrap() = Some(conn.client.clone());
+                    manager.status.write().unwrap().broker = Some(format!("{}:{}", cfg.ip, cfg.port));
+                    manager.set_state(MqttState::Connecting);
+                    Some(conn)
+                }
+                None => {
+                    println!("[MQTT Manager {}]: Broker config incomplete, waiting for config.", manager.name);
+                    manager.status.write().unwrap().broker = None;
+                    manager.buffer.lock().unwrap().clear();
+                    None
+                }
+            };
+            let mut attempt: u32 = 0;
+            // Selama backoff, poll ditunda tetapi perintah Reconnect/Stop tetap dilayani
+            let mut retry_at: Option<Instant> = None;
+
+            loop {
+                select! {
+                    cmd = command_rx.recv() => {
+                        match cmd {
+                            Some(MqttCommand::Reconnect) => {
+                                println!("[MQTT Manager {}]: Reconnect command received.", manager.name);
+                                break;
+                            }
+                            Some(MqttCommand::Stop) | None => {
+                                println!("[MQTT Manager {}]: Stopping MQTT loop.", manager.name);
+                                break 'manager;
+                            }
+                        }
                     }
-                    MqttCommand::Stop => {
-                        println!("[MQTT Manager {}]: Stopping MQTT loop.", cfg.name);
-                        break;
+
+                    _ = sleep_until(retry_at.unwrap_or_else(Instant::now)), if retry_at.is_some() => {
+                        retry_at = None;
+                    }
+
+                    event = poll_connection(&mut connection), if retry_at.is_none() => {
+                        match event {
+                            Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                                attempt = 0;
+                                {
+                                    let mut status = manager.status.write().unwrap();
+                                    status.reconnect_attempts = 0;
+                                    status.last_error = None;
+                                }
+                                manager.set_state(MqttState::Connected);
+                                println!("[MQTT Manager {}]: Connected!", manager.name);
+                                manager.flush_buffer();
+                            }
+                            Ok(Event::Incoming(Packet::Disconnect)) => {
+                                eprintln!("[MQTT Manager {}]: Disconnected by broker.", manager.name);
+                                manager.set_state(MqttState::Connecting);
+                            }
+                            Ok(_) => {}
+                            Err(e) => {
+                                attempt = attempt.saturating_add(1);
+                                let delay = backoff_delay(attempt);
+                                eprintln!(
+                                    "[MQTT Manager {}]: Poll error: {} (retry #{} in {:?})",
+                                    manager.name, e, attempt, delay
+                                );
+                                {
+                                    let mut status = manager.status.write().unwrap();
+                                    status.reconnect_attempts = attempt;
+                                    status.last_error = Some(e.to_string());
+                                }
+                                manager.set_state(MqttState::Connecting);
+                                // Poll berikutnya (setelah backoff) akan mencoba koneksi ulang
+                                retry_at = Some(Instant::now() + delay);
+                            }
+                        }
                     }
                 }
             }
-            _ = sleep(cfg.publish_interval) => {}
-        }
 
-        // Jika belum terhubung, coba koneksi baru
-        if matches!(state, MqttState::Disconnected) {
-            println!("[MQTT Manager {}]: Connecting...", cfg.name);
-            match MqttConnection::connect(&cfg).await {
-                Some(conn) => {
-                    state = MqttState::Connected;
-                    connection = Some(conn);
-                    println!("[MQTT Manager {}]: Connected!", cfg.name);
-                }
-                None => {
-                    eprintln!("[MQTT Manager {}]: Connection failed.", cfg.name);
-                    continue;
-                }
+            // Lepas koneksi lama sebelum membangun yang baru
+            *manager.client.write().unwrap() = None;
+            {
+                let mut status = manager.status.write().unwrap();
+                status.reconnect_attempts = 0;
+                status.last_error = None;
             }
+            manager.set_state(MqttState::Disconnected);
         }
 
-        if let Some(mut conn) = connection.take() {
-            match conn.eventloop.poll().await {
-                Ok(Event::Incoming(Packet::Disconnect)) => {
-                    eprintln!("[MQTT Manager {}]: Disconnected.", cfg.name);
-                    state = MqttState::Disconnected;
-                }
-                Ok(_) => {}
-                Err(e) => {
-                    eprintln!("[MQTT Manager {}]: Poll error: {}", cfg.name, e);
-                    state = MqttState::Disconnected;
-                }
-            }
+        *manager.client.write().unwrap() = None;
+        manager.set_state(MqttState::Disconnected);
+        println!("[MQTT Manager {}]: Exited MQTT loop.", manager.name);
+    });
+}
+
+/// Delay exponential backoff dengan "equal jitter": nilai acak antara setengah dan penuh delay.
+fn backoff_delay(attempt: u32) -> Duration {
+    let exp = BACKOFF_BASE.saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)));
+    let capped = exp.min(BACKOFF_MAX).as_millis() as u64;
+    Duration::from_millis(rand::random_range(capped / 2..=capped))
+}
+
+/// Meneruskan setiap perubahan MqttState ke client WebSocket.
+pub fn start_status_broadcast(sensor: &'static str, manager: Arc<MqttManager>, ws_clients: Clients) {
+    let mut state_rx = manager.subscribe_state();
+    tokio::spawn(async move {
+        while state_rx.changed().await.is_ok() {
+            let msg = serde_json::json!({
+                "type": "mqtt_status",
+                "sensor": sensor,
+                "data": manager.status(),
+            });
+            broadcast_ws_message(&ws_clients, msg.to_string()).await;
+        }
+    });
+}
+
+/// Poll event loop jika ada koneksi; jika tidak, menunggu selamanya (sampai ada perintah).
+async fn poll_connection(
+    connection: &mut Option<MqttConnection>,
+) -> Result<Event, rumqttc::ConnectionError> {
+    match connection {
+        Some(conn) => conn.eventloop.poll().await,
+        None => std::future::pending().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-            // heartbeat status ke topic status
-            let topics = vec![format!("{}/status", cfg.topic_prefix)];
-            let payload = format!("{{\"status\":\"ok\",\"service\":\"{}\"}}", cfg.name);
-            if let Err(e) = conn.publish_message(&topics, payload).await {
-                eprintln!("[MQTT Manager {}]: Publish error: {}", cfg.name, e);
+    fn manager() -> (MqttManager, EventLoop) {
+        let (command_tx, _) = mpsc::channel(1);
+        let manager = MqttManager::new("Test", command_tx);
+        let cfg = MqttServiceConfig::from_broker("test", Some("127.0.0.1".into()), Some(1883), None, None).unwrap();
+        // Event loop tidak di-poll: request tertahan di antrian client (kapasitas CLIENT_CAPACITY)
+        let connection = MqttConnection::connect(&cfg);
+        *manager.client.write().unwrap() = Some(connection.client);
+        (manager, connection.eventloop)
+    }
+
+    #[test]
+    fn backoff_doubles_with_jitter_up_to_max() {
+        for (attempt, full) in [(1, 500), (2, 1_000), (4, 4_000), (7, 30_000), (40, 30_000)] {
+            for _ in 0..20 {
+                let delay = backoff_delay(attempt).as_millis() as u64;
+                assert!((full / 2..=full).contains(&delay), "attempt {}: {} ms", attempt, delay);
             }
+        }
+        assert!(backoff_delay(u32::MAX) <= BACKOFF_MAX);
+    }
+
+    #[tokio::test]
+    async fn messages_are_buffered_until_connected_and_oldest_are_dropped() {
+        let (manager, _eventloop) = manager();
+        let topics = ["a".to_string(), "b".to_string()];
+        for i in 0..60 {
+            manager.publish_message(&topics, i.to_string()).await.unwrap();
+        }
+        assert_eq!(manager.status().buffered_messages, BUFFER_CAPACITY);
+        let buffer = manager.buffer.lock().unwrap().clone();
+        assert_eq!(buffer.front(), Some(&("a".to_string(), "10".to_string())));
+        assert_eq!(buffer.back(), Some(&("b".to_string(), "59".to_string())));
+    }
 
-            connection = Some(conn);
+    #[tokio::test]
+    async fn flush_keeps_what_the_client_queue_cannot_take() {
+        let (manager, _eventloop) = manager();
+        let topics = ["a".to_string()];
+        for i in 0..BUFFER_CAPACITY {
+            manager.publish_message(&topics, i.to_string()).await.unwrap();
         }
+        manager.set_state(MqttState::Connected);
+        manager.flush_buffer();
+        assert_eq!(manager.status().buffered_messages, 0);
+
+        // Antrian client tinggal CLIENT_CAPACITY - BUFFER_CAPACITY slot; sisanya masuk buffer lagi
+        for i in 0..BUFFER_CAPACITY {
+            manager.publish_message(&topics, i.to_string()).await.unwrap();
+        }
+        assert_eq!(manager.status().buffered_messages, BUFFER_CAPACITY - (CLIENT_CAPACITY - BUFFER_CAPACITY));
     }
 
-    println!("[MQTT Manager {}]: Exited MQTT loop.", cfg.name);
-    Ok(())
+    #[tokio::test]
+    async fn publish_without_broker_is_an_error() {
+        let (command_tx, _) = mpsc::channel(1);
+        let manager = MqttManager::new("Test", command_tx);
+        assert!(manager.publish_message(&["a".to_string()], "x".into()).await.is_err());
+        assert_eq!(manager.status().buffered_messages, 0);
+    }
 }
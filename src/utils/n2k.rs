@@ -0,0 +1,554 @@
+use crate::data::anemo_data::AnemoState;
+use crate::data::baro_data::BaroState;
+use crate::data::depth_data::DepthState;
+use crate::data::gps_data::{FixQuality, GpsState};
+use crate::data::gyro_data::GyroState;
+use crate::data::speedlog_data::SpeedLogState;
+use crate::data::thermal_data::{ThermalChannel, ThermalKind, ThermalState};
+use chrono::{DateTime, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+/// Alamat sumber default di bus NMEA 2000
+pub const DEFAULT_GPS_SOURCE: u8 = 0x1C;
+pub const DEFAULT_GYRO_SOURCE: u8 = 0x1D;
+pub const DEFAULT_ANEMO_SOURCE: u8 = 0x1E;
+pub const DEFAULT_BARO_SOURCE: u8 = 0x1F;
+pub const DEFAULT_THERMAL_SOURCE: u8 = 0x20;
+pub const DEFAULT_DEPTH_SOURCE: u8 = 0x21;
+pub const DEFAULT_SPEEDLOG_SOURCE: u8 = 0x22;
+pub const DEFAULT_ROUTE_SOURCE: u8 = 0x23;
+
+const BROADCAST: u8 = 0xFF;
+const KNOTS_TO_MPS: f64 = 0.514444;
+
+/// Counter per alamat sumber: sequence ID fast-packet (3 bit) dan SID (0..252)
+#[derive(Default)]
+struct Counters {
+    sequence: u8,
+    sid: u8,
+}
+
+/// Counter dimulai ulang bersama stream noise (seed baru, simulasi dibuat), agar seed dan
+/// skenario yang sama menghasilkan byte NMEA 2000 yang sama.
+static COUNTERS: LazyLock<Mutex<HashMap<u8, Counters>>> = LazyLock::new(Mutex::default);
+
+/// Memulai ulang counter semua alamat sumber, mis. saat master seed diganti.
+pub fn reset_counters() {
+    COUNTERS.lock().unwrap().clear();
+}
+
+/// Memulai ulang counter satu alamat sumber, mis. saat simulasinya dibuat.
+pub fn reset_source(source: u8) {
+    COUNTERS.lock().unwrap().remove(&source);
+}
+
+/// Satu pesan PGN NMEA 2000 (payload lengkap, sebelum dipecah menjadi frame CAN).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct N2kMessage {
+    pub pgn: u32,
+    pub priority: u8,
+    pub source: u8,
+    pub destination: u8,
+    pub data: Vec<u8>,
+    pub timestamp: DateTime<Utc>,
+    sequence: u8,
+}
+
+/// Frame CAN 29-bit (extended) dengan payload maksimal 8 byte.
+#[derive(Debug, Clone)]
+pub struct CanFrame {
+    pub id: u32,
+    pub data: Vec<u8>,
+}
+
+impl N2kMessage {
+    fn new(pgn: u32, priority: u8, source: u8, data: Vec<u8>, timestamp: DateTime<Utc>) -> Self {
+        let sequence = if data.len() > 8 { next_sequence(source) } else { 0 };
+        Self { pgn, priority, source, destination: BROADCAST, data, timestamp, sequence }
+    }
+
+    /// CAN ID: priority(3) | DP+PF(9) | PS(8) | source(8). PF < 240 (PDU1) memakai alamat tujuan di PS.
+    pub fn can_id(&self) -> u32 {
+        let pf = (self.pgn >> 8) & 0xFF;
+        let pgn = if pf < 240 { (self.pgn & 0x3FF00) | self.destination as u32 } else { self.pgn };
+        ((self.priority as u32 & 0x07) << 26) | (pgn << 8) | self.source as u32
+    }
+
+    /// Memecah payload menjadi frame CAN; payload > 8 byte memakai framing fast-packet.
+    pub fn frames(&self) -> Vec<CanFrame> {
+        let id = self.can_id();
+        if self.data.len() <= 8 {
+            return vec![CanFrame { id, data: self.data.clone() }];
+        }
+
+        let seq = self.sequence << 5;
+        let mut frames = Vec::new();
+        // Frame 0: [seq|0, panjang total, 6 byte data]
+        let mut first = vec![seq, self.data.len() as u8];
+        first.extend_from_slice(&self.data[..6]);
+        frames.push(CanFrame { id, data: first });
+
+        // Frame berikutnya: [seq|counter, 7 byte data], sisa diisi 0xFF
+        for (i, chunk) in self.data[6..].chunks(7).enumerate() {
+            let mut data = vec![seq | ((i as u8 + 1) & 0x1F)];
+            data.extend_from_slice(chunk);
+            data.resize(8, 0xFF);
+            frames.push(CanFrame { id, data });
+        }
+        frames
+    }
+
+    /// Format Yacht Devices RAW: satu baris per frame CAN, `hh:mm:ss.sss R IIIIIIII DD DD ...`
+    pub fn to_ydraw(&self) -> Vec<String> {
+        let time = self.timestamp.format("%H:%M:%S%.3f");
+        self.frames()
+            .iter()
+            .map(|frame| {
+                let bytes: Vec<String> = frame.data.iter().map(|b| format!("{:02X}", b)).collect();
+                format!("{} R {:08X} {}", time, frame.id, bytes.join(" "))
+            })
+            .collect()
+    }
+
+    /// Format Actisense N2K ASCII: `Ahhmmss.sss SSDDP PGN DATA` dengan payload utuh.
+    pub fn to_actisense(&self) -> String {
+        let data: String = self.data.iter().map(|b| format!("{:02X}", b)).collect();
+        format!(
+            "A{} {:02X}{:02X}{:X} {:X} {}",
+            self.timestamp.format("%H%M%S%.3f"),
+            self.source,
+            self.destination,
+            self.priority & 0x07,
+            self.pgn,
+            data
+        )
+    }
+}
+
+fn next_sequence(source: u8) -> u8 {
+    let mut counters = COUNTERS.lock().unwrap();
+    let counters = counters.entry(source).or_default();
+    let sequence = counters.sequence;
+    counters.sequence = (sequence + 1) & 0x07;
+    sequence
+}
+
+fn next_sid(source: u8) -> u8 {
+    let mut counters = COUNTERS.lock().unwrap();
+    let counters = counters.entry(source).or_default();
+    let sid = counters.sid;
+    counters.sid = (sid + 1) % 253;
+    sid
+}
+
+/// Nilai yang sudah dikalikan resolusinya ke field bilangan bulat. NaN/inf (mis. dari fault
+/// injection) dikirim sebagai "not available" (nilai maksimum field), bukan 0.
+fn scaled_u16(value: f64) -> u16 {
+    if value.is_finite() { value.round() as u16 } else { u16::MAX }
+}
+
+fn scaled_i16(value: f64) -> i16 {
+    if value.is_finite() { value.round() as i16 } else { i16::MAX }
+}
+
+fn scaled_u32(value: f64) -> u32 {
+    if value.is_finite() { value.round() as u32 } else { u32::MAX }
+}
+
+fn scaled_i32(value: f64) -> i32 {
+    if value.is_finite() { value.round() as i32 } else { i32::MAX }
+}
+
+fn scaled_i64(value: f64) -> i64 {
+    if value.is_finite() { value.round() as i64 } else { i64::MAX }
+}
+
+/// Sudut 0..2π dalam satuan 1e-4 rad (u16)
+fn angle_u16(degrees: f64) -> [u8; 2] {
+    scaled_u16(degrees.rem_euclid(360.0).to_radians() / 1e-4).to_le_bytes()
+}
+
+/// Sudut bertanda -π..π dalam satuan 1e-4 rad (i16)
+fn angle_i16(degrees: f64) -> [u8; 2] {
+    let wrapped = (degrees + 180.0).rem_euclid(360.0) - 180.0;
+    scaled_i16(wrapped.to_radians() / 1e-4).to_le_bytes()
+}
+
+/// PGN 129025 — Position, Rapid Update
+pub fn pgn_129025(state: &GpsState, source: u8) -> N2kMessage {
+    let mut data = Vec::with_capacity(8);
+    data.extend_from_slice(&scaled_i32(state.latitude * 1e7).to_le_bytes());
+    data.extend_from_slice(&scaled_i32(state.longitude * 1e7).to_le_bytes());
+    N2kMessage::new(129025, 2, source, data, state.last_update)
+}
+
+/// PGN 129026 — COG & SOG, Rapid Update (referensi COG: true)
+pub fn pgn_129026(state: &GpsState, source: u8, sid: u8) -> N2kMessage {
+    let mut data = vec![sid, 0xFC];
+    data.extend_from_slice(&angle_u16(state.cog));
+    data.extend_from_slice(&scaled_u16(state.sog * KNOTS_TO_MPS / 0.01).to_le_bytes());
+    data.extend_from_slice(&[0xFF, 0xFF]);
+    N2kMessage::new(129026, 2, source, data, state.last_update)
+}
+
+/// Metode GNSS PGN 129029 (4 bit atas byte tipe/metode).
+fn gnss_method(quality: FixQuality) -> u8 {
+    match quality {
+        FixQuality::NoFix => 0,
+        FixQuality::Fix2d | FixQuality::Fix3d => 1,
+        FixQuality::Dgps => 2,
+        FixQuality::Rtk => 4,
+    }
+}
+
+/// PGN 129029 — GNSS Position Data (fast-packet, tanpa reference station).
+/// Tanpa fix, posisi dan DOP dikirim sebagai "not available".
+pub fn pgn_129029(state: &GpsState, source: u8, sid: u8) -> N2kMessage {
+    let fix = state.fix();
+    let has_fix = fix.has_fix();
+    let time = &state.last_update;
+    let days = (time.timestamp().div_euclid(86_400)) as u16;
+    let seconds = time.num_seconds_from_midnight() as f64 + time.nanosecond().min(999_999_999) as f64 / 1e9;
+
+    let mut data = Vec::with_capacity(43);
+    data.push(sid);
+    data.extend_from_slice(&days.to_le_bytes());
+    data.extend_from_slice(&((seconds * 1e4).round() as u32).to_le_bytes());
+    let (latitude, longitude) = if has_fix {
+        (scaled_i64(state.latitude * 1e16), scaled_i64(state.longitude * 1e16))
+    } else {
+        (i64::MAX, i64::MAX)
+    };
+    data.extend_from_slice(&latitude.to_le_bytes());
+    data.extend_from_slice(&longitude.to_le_bytes());
+    data.extend_from_slice(&0i64.to_le_bytes()); // altitude (1e-6 m)
+    data.push(gnss_method(fix) << 4); // GNSS type: GPS (0)
+    data.push(0xFC); // integrity: no checking
+    data.push(state.satellites_used());
+    let dop = |value: f64| if has_fix { scaled_i16(value / 0.01) } else { i16::MAX };
+    let pdop = state.sky.as_ref().map(|sky| sky.pdop).unwrap_or(state.hdop * 1.5);
+    data.extend_from_slice(&dop(state.hdop).to_le_bytes()); // HDOP (0.01)
+    data.extend_from_slice(&dop(pdop).to_le_bytes()); // PDOP (0.01)
+    data.extend_from_slice(&0i32.to_le_bytes()); // geoidal separation (0.01 m)
+    data.push(0); // jumlah reference station
+    N2kMessage::new(129029, 3, source, data, state.last_update)
+}
+
+/// PGN 127250 — Vessel Heading (referensi: true)
+pub fn pgn_127250(state: &GyroState, source: u8, sid: u8, deviation: f64, variation: Option<f64>) -> N2kMessage {
+    let mut data = vec![sid];
+    data.extend_from_slice(&angle_u16(state.yaw));
+    data.extend_from_slice(&angle_i16(deviation));
+    match variation {
+        Some(var) => data.extend_from_slice(&angle_i16(var)),
+        None => data.extend_from_slice(&0x7FFFi16.to_le_bytes()), // not available
+    }
+    data.push(0xFC);
+    N2kMessage::new(127250, 2, source, data, state.last_update)
+}
+
+/// PGN 127251 — Rate of Turn (satuan 3.125e-8 rad/s)
+pub fn pgn_127251(state: &GyroState, source: u8, sid: u8) -> N2kMessage {
+    let mut data = vec![sid];
+    data.extend_from_slice(&scaled_i32(state.yaw_rate.to_radians() / 3.125e-8).to_le_bytes());
+    data.extend_from_slice(&[0xFF, 0xFF, 0xFF]);
+    N2kMessage::new(127251, 2, source, data, state.last_update)
+}
+
+/// PGN 127257 — Attitude (yaw, pitch, roll dalam 1e-4 rad)
+pub fn pgn_127257(state: &GyroState, source: u8, sid: u8) -> N2kMessage {
+    let mut data = vec![sid];
+    data.extend_from_slice(&angle_i16(state.yaw));
+    data.extend_from_slice(&angle_i16(state.pitch));
+    data.extend_from_slice(&angle_i16(state.roll));
+    data.push(0xFF);
+    N2kMessage::new(127257, 3, source, data, state.last_update)
+}
+
+/// Referensi arah angin di PGN 130306
+const WIND_TRUE_NORTH: u8 = 0;
+const WIND_APPARENT: u8 = 2;
+const WIND_TRUE_BOAT: u8 = 3;
+
+/// PGN 130306 — Wind Data (kecepatan 0.01 m/s, sudut 1e-4 rad)
+pub fn pgn_130306(
+    speed_knots: f64,
+    angle: f64,
+    reference: u8,
+    source: u8,
+    sid: u8,
+    timestamp: DateTime<Utc>,
+) -> N2kMessage {
+    let mut data = vec![sid];
+    data.extend_from_slice(&scaled_u16(speed_knots * KNOTS_TO_MPS / 0.01).to_le_bytes());
+    data.extend_from_slice(&angle_u16(angle));
+    data.push(0xF8 | (reference & 0x07));
+    data.extend_from_slice(&[0xFF, 0xFF]);
+    N2kMessage::new(130306, 2, source, data, timestamp)
+}
+
+/// PGN 130311 — Environmental Parameters (udara luar: suhu 0.01 K, kelembapan 0.004 %, tekanan 1 hPa)
+pub fn pgn_130311(state: &BaroState, source: u8, sid: u8) -> N2kMessage {
+    const OUTSIDE: u8 = 1;
+    let mut data = vec![sid, OUTSIDE | (OUTSIDE << 6)];
+    data.extend_from_slice(&scaled_u16((state.air_temperature + 273.15) / 0.01).to_le_bytes());
+    data.extend_from_slice(&scaled_i16(state.relative_humidity / 0.004).to_le_bytes());
+    data.extend_from_slice(&scaled_u16(state.pressure).to_le_bytes());
+    N2kMessage::new(130311, 5, source, data, state.last_update)
+}
+
+/// Sumber suhu PGN 130316; jenis yang tidak ada di tabel standar memakai kode user-defined (129+)
+fn temperature_source(kind: ThermalKind) -> u8 {
+    match kind {
+        ThermalKind::SeaWater => 0,
+        ThermalKind::EngineRoom => 3,
+        ThermalKind::Cabin => 4,
+        ThermalKind::Refrigeration => 7,
+        ThermalKind::Exhaust => 14,
+        ThermalKind::EngineCoolant => 129,
+        ThermalKind::Other => 130,
+    }
+}
+
+/// PGN 130316 — Temperature, Extended Range (suhu 0.001 K, setpoint 0.1 K)
+pub fn pgn_130316(channel: &ThermalChannel, instance: u8, source: u8, sid: u8, timestamp: DateTime<Utc>) -> N2kMessage {
+    let mut data = vec![sid, instance, temperature_source(channel.kind)];
+    let kelvin = scaled_u32((channel.temperature + 273.15) / 0.001);
+    data.extend_from_slice(&kelvin.to_le_bytes()[..3]);
+    data.extend_from_slice(&scaled_u16((channel.setpoint + 273.15) / 0.1).to_le_bytes());
+    N2kMessage::new(130316, 5, source, data, timestamp)
+}
+
+/// PGN 128267 — Water Depth (di bawah transduser 0.01 m, offset 0.001 m, jangkauan 10 m)
+pub fn pgn_128267(state: &DepthState, source: u8, sid: u8) -> N2kMessage {
+    let depth = state
+        .depth_below_transducer
+        .map(|d| scaled_u32(d / 0.01))
+        .unwrap_or(u32::MAX);
+    let mut data = vec![sid];
+    data.extend_from_slice(&depth.to_le_bytes());
+    data.extend_from_slice(&scaled_i16(state.transducer_offset / 0.001).to_le_bytes());
+    data.push((state.max_range / 10.0).round().min(254.0) as u8);
+    N2kMessage::new(128267, 3, source, data, state.last_update)
+}
+
+/// PGN 128259 — Speed (terhadap air dan dasar, 0.01 m/s; sensor paddle wheel)
+pub fn pgn_128259(state: &SpeedLogState, source: u8, sid: u8) -> N2kMessage {
+    const PADDLE_WHEEL: u8 = 0;
+    let mut data = vec![sid];
+    data.extend_from_slice(&scaled_u16(state.stw * KNOTS_TO_MPS / 0.01).to_le_bytes());
+    data.extend_from_slice(&scaled_u16(state.sog * KNOTS_TO_MPS / 0.01).to_le_bytes());
+    data.push(PADDLE_WHEEL);
+    data.extend_from_slice(&[0xF0, 0xFF]);
+    N2kMessage::new(128259, 2, source, data, state.last_update)
+}
+
+/// Semua PGN GPS untuk satu tick publikasi (SID yang sama); tanpa fix hanya 129029.
+pub fn encode_gps_pgns(state: &GpsState, source: u8) -> Vec<N2kMessage> {
+    let sid = next_sid(source);
+    if !state.fix().has_fix() {
+        return vec![pgn_129029(state, source, sid)];
+    }
+    vec![
+        pgn_129025(state, source),
+        pgn_129026(state, source, sid),
+        pgn_129029(state, source, sid),
+    ]
+}
+
+/// Semua PGN gyro untuk satu tick publikasi (SID yang sama).
+pub fn encode_gyro_pgns(state: &GyroState, source: u8, deviation: f64, variation: Option<f64>) -> Vec<N2kMessage> {
+    let sid = next_sid(source);
+    vec![
+        pgn_127250(state, source, sid, deviation, variation),
+        pgn_127251(state, source, sid),
+        pgn_127257(state, source, sid),
+    ]
+}
+
+/// PGN angin untuk satu tick publikasi: angin semu, true relatif haluan, dan true dari utara.
+pub fn encode_anemo_pgns(state: &AnemoState, source: u8) -> Vec<N2kMessage> {
+    let sid = next_sid(source);
+    let time = state.last_update;
+    vec![
+        pgn_130306(state.apparent_wind_speed, state.apparent_wind_angle, WIND_APPARENT, source, sid, time),
+        pgn_130306(state.wind_speed, state.true_wind_angle, WIND_TRUE_BOAT, source, sid, time),
+        pgn_130306(state.wind_speed, state.wind_direction, WIND_TRUE_NORTH, source, sid, time),
+    ]
+}
+
+/// PGN barometer untuk satu tick publikasi.
+pub fn encode_baro_pgns(state: &BaroState, source: u8) -> Vec<N2kMessage> {
+    vec![pgn_130311(state, source, next_sid(source))]
+}
+
+/// PGN suhu untuk satu tick publikasi, instance = urutan channel.
+pub fn encode_thermal_pgns(state: &ThermalState, source: u8) -> Vec<N2kMessage> {
+    let sid = next_sid(source);
+    state
+        .channels
+        .iter()
+        .enumerate()
+        .map(|(i, channel)| pgn_130316(channel, i as u8, source, sid, state.last_update))
+        .collect()
+}
+
+/// PGN kedalaman untuk satu tick publikasi.
+pub fn encode_depth_pgns(state: &DepthState, source: u8) -> Vec<N2kMessage> {
+    vec![pgn_128267(state, source, next_sid(source))]
+}
+
+/// PGN speed log untuk satu tick publikasi.
+pub fn encode_speedlog_pgns(state: &SpeedLogState, source: u8) -> Vec<N2kMessage> {
+    vec![pgn_128259(state, source, next_sid(source))]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::gps_data::CreateGpsRequest;
+    use crate::services::gps_service::Gps;
+    use crate::services::sensor::Sensor;
+
+    fn gps_state(latitude: f64, longitude: f64, fix_quality: &str) -> GpsState {
+        let req: CreateGpsRequest = serde_json::from_value(serde_json::json!({
+            "latitude": latitude, "longitude": longitude, "sog": 0.0, "cog": 0.0,
+            "fix_quality": fix_quality, "is_running": true
+        }))
+        .unwrap();
+        let mut state = Gps::create(req);
+        state.last_update = DateTime::parse_from_rfc3339("2024-05-01T12:00:00.5Z").unwrap().to_utc();
+        state
+    }
+
+    fn message(len: usize, sequence: u8) -> N2kMessage {
+        let data = (0..len as u8).collect();
+        N2kMessage { pgn: 129029, priority: 3, source: 0x1C, destination: BROADCAST, data, timestamp: Utc::now(), sequence }
+    }
+
+    #[test]
+    fn single_frame_payload_is_not_fast_packet() {
+        let frames = message(8, 0).frames();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].data, (0..8).collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn fast_packet_chunks_six_then_seven_bytes() {
+        let frames = message(22, 5).frames();
+        assert_eq!(frames.len(), 4);
+        assert!(frames.iter().all(|frame| frame.data.len() == 8 && frame.id == 0x0DF8051C));
+
+        // Frame 0: sequence di 3 bit atas, counter 0, panjang total, 6 byte pertama
+        assert_eq!(frames[0].data, [0xA0, 22, 0, 1, 2, 3, 4, 5]);
+        assert_eq!(frames[1].data, [0xA1, 6, 7, 8, 9, 10, 11, 12]);
+        assert_eq!(frames[2].data, [0xA2, 13, 14, 15, 16, 17, 18, 19]);
+        // Frame terakhir diisi 0xFF
+        assert_eq!(frames[3].data, [0xA3, 20, 21, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
+    }
+
+    #[test]
+    fn fast_packet_sequence_advances_per_source() {
+        // Alamat sumber khusus test, agar tidak berbagi counter dengan test lain
+        const SOURCE: u8 = 0xA0;
+        reset_source(SOURCE);
+        let sequences: Vec<u8> = (0..10).map(|_| N2kMessage::new(129029, 3, SOURCE, vec![0; 43], Utc::now()).sequence).collect();
+        assert_eq!(sequences, [0, 1, 2, 3, 4, 5, 6, 7, 0, 1]);
+        let message = N2kMessage::new(129029, 3, SOURCE, vec![0; 43], Utc::now());
+        assert!(message.frames().iter().all(|frame| frame.data[0] >> 5 == message.sequence));
+        // Pesan satu frame tidak memakai sequence
+        assert_eq!(N2kMessage::new(129025, 2, SOURCE, vec![0; 8], Utc::now()).sequence, 0);
+
+        reset_source(SOURCE);
+        assert_eq!(N2kMessage::new(129029, 3, SOURCE, vec![0; 43], Utc::now()).sequence, 0);
+    }
+
+    #[test]
+    fn sid_is_shared_per_tick_and_restarts_with_source() {
+        const SOURCE: u8 = 0xA1;
+        reset_source(SOURCE);
+        let state = gps_state(60.5, -24.25, "3d");
+        let first = encode_gps_pgns(&state, SOURCE);
+        let second = encode_gps_pgns(&state, SOURCE);
+        // 129026 dan 129029 dalam satu tick memakai SID yang sama
+        assert!(first.iter().filter(|m| m.pgn != 129025).all(|m| m.data[0] == 0));
+        assert!(second.iter().filter(|m| m.pgn != 129025).all(|m| m.data[0] == 1));
+
+        reset_source(SOURCE);
+        let replayed = encode_gps_pgns(&state, SOURCE);
+        let bytes = |messages: &[N2kMessage]| messages.iter().flat_map(N2kMessage::frames).map(|f| f.data).collect::<Vec<_>>();
+        assert_eq!(bytes(&replayed), bytes(&first));
+    }
+
+    #[test]
+    fn pgn_129025_layout() {
+        let message = pgn_129025(&gps_state(60.1234567, -24.5, "3d"), DEFAULT_GPS_SOURCE);
+        assert_eq!(message.can_id(), 0x09F8011C);
+        let mut expected = 601_234_567i32.to_le_bytes().to_vec();
+        expected.extend_from_slice(&(-245_000_000i32).to_le_bytes());
+        assert_eq!(message.data, expected);
+        assert_eq!(message.frames().len(), 1);
+    }
+
+    #[test]
+    fn pgn_129029_layout() {
+        let message = pgn_129029(&gps_state(60.5, -24.25, "dgps"), DEFAULT_GPS_SOURCE, 7);
+        let data = &message.data;
+        assert_eq!(message.can_id(), 0x0DF8051C);
+        assert_eq!(data.len(), 43);
+        assert_eq!(message.frames().len(), 7);
+
+        assert_eq!(data[0], 7);
+        assert_eq!(u16::from_le_bytes([data[1], data[2]]), 19_844); // hari sejak 1970-01-01
+        assert_eq!(u32::from_le_bytes(data[3..7].try_into().unwrap()), 432_005_000); // 43200.5 s (1e-4)
+        assert_eq!(i64::from_le_bytes(data[7..15].try_into().unwrap()), 605_000_000_000_000_000);
+        assert_eq!(i64::from_le_bytes(data[15..23].try_into().unwrap()), -242_500_000_000_000_000);
+        assert_eq!(i64::from_le_bytes(data[23..31].try_into().unwrap()), 0);
+        assert_eq!(data[31], 0x20); // GPS, metode DGNSS
+        assert_eq!(data[32], 0xFC);
+        assert_eq!(data[33], 8);
+        assert_eq!(i16::from_le_bytes([data[34], data[35]]), 90); // HDOP 0.9
+        assert_eq!(i16::from_le_bytes([data[36], data[37]]), 135); // PDOP 1.35
+        assert_eq!(i32::from_le_bytes(data[38..42].try_into().unwrap()), 0);
+        assert_eq!(data[42], 0);
+    }
+
+    #[test]
+    fn pgn_129029_without_fix_is_not_available() {
+        let data = pgn_129029(&gps_state(60.5, -24.25, "no_fix"), DEFAULT_GPS_SOURCE, 0).data;
+        assert_eq!(i64::from_le_bytes(data[7..15].try_into().unwrap()), i64::MAX);
+        assert_eq!(i64::from_le_bytes(data[15..23].try_into().unwrap()), i64::MAX);
+        assert_eq!(data[31], 0x00);
+        assert_eq!(i16::from_le_bytes([data[34], data[35]]), i16::MAX);
+        assert_eq!(i16::from_le_bytes([data[36], data[37]]), i16::MAX);
+    }
+
+    #[test]
+    fn non_finite_values_are_sent_as_not_available() {
+        let mut state = gps_state(60.5, -24.25, "3d");
+        state.latitude = f64::NAN;
+        state.longitude = f64::INFINITY;
+        state.sog = f64::NAN;
+        state.cog = f64::NAN;
+        state.hdop = f64::NAN;
+
+        let data = pgn_129025(&state, DEFAULT_GPS_SOURCE).data;
+        assert_eq!(data, [0xFF, 0xFF, 0xFF, 0x7F, 0xFF, 0xFF, 0xFF, 0x7F]);
+
+        let data = pgn_129026(&state, DEFAULT_GPS_SOURCE, 0).data;
+        assert_eq!(u16::from_le_bytes([data[2], data[3]]), u16::MAX); // COG
+        assert_eq!(u16::from_le_bytes([data[4], data[5]]), u16::MAX); // SOG
+
+        let data = pgn_129029(&state, DEFAULT_GPS_SOURCE, 0).data;
+        assert_eq!(i64::from_le_bytes(data[7..15].try_into().unwrap()), i64::MAX);
+        assert_eq!(i64::from_le_bytes(data[15..23].try_into().unwrap()), i64::MAX);
+        assert_eq!(i16::from_le_bytes([data[34], data[35]]), i16::MAX);
+
+        // Nilai terhingga tetap dikodekan seperti biasa
+        assert_eq!(scaled_i16(-12.4), -12);
+        assert_eq!(angle_i16(f64::NAN), 0x7FFFi16.to_le_bytes());
+        assert_eq!(angle_u16(90.0), 15708u16.to_le_bytes());
+    }
+}
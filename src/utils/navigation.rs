@@ -0,0 +1,104 @@
+use crate::data::route_data::LegType;
+use crate::utils::vessel_calculate;
+use std::f64::consts::PI;
+
+/// Jari-jari bumi dalam mil laut (6 371 km / 1 852 m)
+const EARTH_RADIUS_NM: f64 = 3440.065;
+
+/// Selisih sudut `a - b` dinormalisasi ke -180..180.
+pub fn angle_diff(a: f64, b: f64) -> f64 {
+    (a - b + 180.0).rem_euclid(360.0) - 180.0
+}
+
+/// Jarak great circle (NM) dan bearing awal (derajat true) dari titik 1 ke titik 2.
+pub fn great_circle(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> (f64, f64) {
+    let (phi1, phi2) = (lat1.to_radians(), lat2.to_radians());
+    let dphi = phi2 - phi1;
+    let dlambda = (lon2 - lon1).to_radians();
+
+    let a = (dphi / 2.0).sin().powi(2) + phi1.cos() * phi2.cos() * (dlambda / 2.0).sin().powi(2);
+    let distance = 2.0 * a.sqrt().atan2((1.0 - a).sqrt()) * EARTH_RADIUS_NM;
+    let y = dlambda.sin() * phi2.cos();
+    let x = phi1.cos() * phi2.sin() - phi1.sin() * phi2.cos() * dlambda.cos();
+    (distance, y.atan2(x).to_degrees().rem_euclid(360.0))
+}
+
+/// Jarak rhumb line (NM) dan bearing konstan (derajat true) dari titik 1 ke titik 2.
+pub fn rhumb_line(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> (f64, f64) {
+    let (phi1, phi2) = (lat1.to_radians(), lat2.to_radians());
+    let dphi = phi2 - phi1;
+    let mut dlambda = (lon2 - lon1).to_radians();
+    if dlambda.abs() > PI {
+        dlambda -= dlambda.signum() * 2.0 * PI;
+    }
+
+    let dpsi = ((PI / 4.0 + phi2 / 2.0).tan() / (PI / 4.0 + phi1 / 2.0).tan()).ln();
+    let q = if dpsi.abs() > 1e-12 { dphi / dpsi } else { phi1.cos() };
+    let distance = (dphi * dphi + q * q * dlambda * dlambda).sqrt() * EARTH_RADIUS_NM;
+    (distance, dlambda.atan2(dpsi).to_degrees().rem_euclid(360.0))
+}
+
+/// Jarak dan bearing sesuai jenis leg.
+pub fn leg_distance_bearing(leg: LegType, lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> (f64, f64) {
+    match leg {
+        LegType::GreatCircle => great_circle(lat1, lon1, lat2, lon2),
+        LegType::RhumbLine => rhumb_line(lat1, lon1, lat2, lon2),
+    }
+}
+
+/// Posisi kapal relatif terhadap satu leg (semua jarak dalam NM, sudut derajat true).
+#[derive(Debug, Clone, Copy)]
+pub struct LegGeometry {
+    pub leg_length: f64,
+    /// Bearing leg di titik awal
+    pub leg_bearing: f64,
+    /// Arah track di titik proyeksi kapal (great circle berubah sepanjang leg)
+    pub track_course: f64,
+    /// Positif = kapal di kanan track
+    pub cross_track: f64,
+    pub along_track: f64,
+    pub distance_to_waypoint: f64,
+    pub bearing_to_waypoint: f64,
+}
+
+/// Geometri leg `start` → `end` untuk kapal di `position` (lat, lon).
+pub fn leg_geometry(leg: LegType, start: (f64, f64), end: (f64, f64), position: (f64, f64)) -> LegGeometry {
+    let (leg_length, leg_bearing) = leg_distance_bearing(leg, start.0, start.1, end.0, end.1);
+    let (distance_to_waypoint, bearing_to_waypoint) = leg_distance_bearing(leg, position.0, position.1, end.0, end.1);
+    let (d13, theta13) = leg_distance_bearing(leg, start.0, start.1, position.0, position.1);
+    let relative = (theta13 - leg_bearing).to_radians();
+
+    let (cross_track, along_track, track_course) = match leg {
+        LegType::GreatCircle => {
+            let delta13 = d13 / EARTH_RADIUS_NM;
+            let dxt = (delta13.sin() * relative.sin()).asin();
+            let datd = (delta13.cos() / dxt.cos()).clamp(-1.0, 1.0).acos() * relative.cos().signum();
+            let along_track = datd * EARTH_RADIUS_NM;
+            // Arah great circle di titik proyeksi = bearing dari titik tsb ke tujuan
+            let (lat_p, lon_p) = vessel_calculate::destination(
+                start.0,
+                start.1,
+                leg_bearing,
+                along_track.max(0.0) * 1852.0,
+            );
+            let course = if along_track < leg_length {
+                great_circle(lat_p, lon_p, end.0, end.1).1
+            } else {
+                bearing_to_waypoint
+            };
+            (dxt * EARTH_RADIUS_NM, along_track, course)
+        }
+        LegType::RhumbLine => (d13 * relative.sin(), d13 * relative.cos(), leg_bearing),
+    };
+
+    let track_course = if leg_length < 1e-6 { bearing_to_waypoint } else { track_course };
+    LegGeometry {
+        leg_length,
+        leg_bearing,
+        track_course,
+        cross_track,
+        along_track,
+        distance_to_waypoint,
+        bearing_to_waypoint,
+    }
+}
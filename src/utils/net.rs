@@ -1,35 +1,61 @@
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use tokio::net::TcpStream;
 // DIUBAH: Menggunakan RwLock dari Tokio karena digunakan dalam konteks async
 use tokio::sync::{mpsc, RwLock};
-use tokio_tungstenite::{accept_async, tungstenite::Message};
+use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+use tokio_tungstenite::{accept_hdr_async, tungstenite::Message};
 use futures_util::{SinkExt, StreamExt};
+use crate::utils::signalk::{self, SignalKSession};
 
 // DIUBAH: Menggunakan tokio::sync::RwLock
 pub type Tx = mpsc::UnboundedSender<Message>;
-pub type Clients = Arc<RwLock<Vec<Tx>>>;
+pub type Clients = Arc<RwLock<Vec<WsClient>>>;
+
+/// Client WebSocket. `signalk` terisi jika client terhubung ke stream Signal K.
+pub struct WsClient {
+    pub tx: Tx,
+    pub signalk: Option<Arc<Mutex<SignalKSession>>>,
+}
 
 pub async fn broadcast_ws_message(clients: &Clients, message_string: String) {
     // DIUBAH: Menambahkan .into() sesuai petunjuk compiler
     let message = Message::Text(message_string.into());
 
     let clients_guard = clients.read().await;
-    for client_tx in clients_guard.iter() {
+    // Client Signal K hanya menerima delta (lihat signalk::broadcast_delta)
+    for client in clients_guard.iter().filter(|c| c.signalk.is_none()) {
         // Kirim pesan, abaikan jika ada error (client disconnect)
-        let _ = client_tx.send(message.clone());
+        let _ = client.tx.send(message.clone());
     }
 }
 
+// ErrorResponse dari tungstenite memang besar dan tipenya ditentukan oleh trait Callback
+#[allow(clippy::result_large_err)]
 pub async fn handle_websocket_connection(stream: TcpStream, clients: Clients) {
-    if let Ok(ws_stream) = accept_async(stream).await {
+    // Simpan URI request agar bisa membedakan stream Signal K dan stream JSON biasa
+    let mut uri = String::new();
+    let handshake = accept_hdr_async(stream, |req: &Request, resp: Response| {
+        uri = req.uri().to_string();
+        Ok(resp)
+    })
+    .await;
+
+    if let Ok(ws_stream) = handshake {
+        let (path, query) = uri.split_once('?').unwrap_or((uri.as_str(), ""));
+        if path.trim_end_matches('/') == signalk::SIGNALK_STREAM_PATH {
+            signalk::handle_signalk_connection(ws_stream, query, clients).await;
+            return;
+        }
+
         let (mut write, mut read) = ws_stream.split();
         let (tx, mut rx) = mpsc::unbounded_channel::<Message>();
         
         // Tambahkan client baru ke daftar
-        clients.write().await.push(tx);
+        clients.write().await.push(WsClient { tx, signalk: None });
 
         // Task untuk mengirim pesan dari channel ke client
-        tokio::spawn(async move {
+        let writer = tokio::spawn(async move {
             while let Some(msg) = rx.recv().await {
                 if write.send(msg).await.is_err() {
                     break;
@@ -41,19 +67,253 @@ pub async fn handle_websocket_connection(stream: TcpStream, clients: Clients) {
         while read.next().await.is_some() {}
 
         // Client disconnect, bersihkan daftar
-        clients.write().await.retain(|c| !c.is_closed());
+        writer.abort();
+        let _ = writer.await;
+        clients.write().await.retain(|c| !c.tx.is_closed());
         println!("A client disconnected.");
     }
 }
 
-// Fungsi TCP tidak berubah
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
-pub async fn handle_tcp_connection(mut socket: TcpStream) {
-    let mut buffer = [0; 1024];
-    match socket.read(&mut buffer).await {
-        Ok(n) if n > 0 => {
-            let _ = socket.write_all(b"TCP received").await;
+// === TCP ===
+use crate::services::sensor::SensorContext;
+use crate::utils::can_output::SharedCanOutput;
+use crate::utils::n2k::N2kMessage;
+use crate::utils::recording::SharedRecorder;
+use crate::utils::signalk::Delta;
+use crate::utils::udp_output::SharedUdpOutput;
+use crate::utils::nmea_input;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+/// Antrian frame per client TCP; client yang tidak membaca kehilangan frame, bukan memori server
+const TCP_QUEUE_LEN: usize = 256;
+/// Panjang baris input maksimal (kalimat NMEA + TAG block); client dengan baris lebih panjang diputus
+const MAX_TCP_LINE_LEN: usize = 1024;
+
+/// Format stream per koneksi TCP / output UDP
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StreamFormat {
+    Nmea,
+    Json,
+    /// NMEA 2000, format Yacht Devices RAW (satu baris per frame CAN)
+    Ydraw,
+    /// NMEA 2000, format Actisense N2K ASCII (satu baris per PGN)
+    Actisense,
+}
+
+impl StreamFormat {
+    fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "nmea" => Some(StreamFormat::Nmea),
+            "json" => Some(StreamFormat::Json),
+            "ydraw" => Some(StreamFormat::Ydraw),
+            "actisense" => Some(StreamFormat::Actisense),
+            _ => None,
+        }
+    }
+}
+
+/// Satu update sensor dalam semua representasi; tiap output memilih sesuai formatnya.
+#[derive(Debug, Clone)]
+pub struct OutputFrame {
+    pub nmea: Vec<String>,
+    pub json: String,
+    pub n2k: Vec<N2kMessage>,
+    pub signalk: Option<Delta>,
+}
+
+impl OutputFrame {
+    /// Render ke baris-baris teks (sudah termasuk terminator) sesuai format.
+    pub fn render(&self, format: StreamFormat) -> Vec<String> {
+        match format {
+            StreamFormat::Nmea => self.nmea.iter().map(|line| format!("{}\r\n", line)).collect(),
+            StreamFormat::Json if self.json.is_empty() => Vec::new(),
+            StreamFormat::Json => vec![format!("{}\n", self.json)],
+            StreamFormat::Ydraw => self
+                .n2k
+                .iter()
+                .flat_map(N2kMessage::to_ydraw)
+                .map(|line| format!("{}\r\n", line))
+                .collect(),
+            StreamFormat::Actisense => self.n2k.iter().map(|msg| format!("{}\r\n", msg.to_actisense())).collect(),
         }
-        _ => {}
     }
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone)]
+pub enum TcpFrame {
+    Data(Arc<OutputFrame>),
+    SetFormat(StreamFormat),
+    Reply(String),
+}
+
+/// Semua output non-MQTT yang dipakai bersama oleh thread publikasi.
+#[derive(Clone)]
+pub struct Outputs {
+    pub ws_clients: Clients,
+    pub tcp_clients: TcpClients,
+    pub udp: SharedUdpOutput,
+    pub can: SharedCanOutput,
+    pub recorder: SharedRecorder,
+    /// `true` selama replay dimuat; publikasi live berhenti
+    replaying: Arc<AtomicBool>,
+}
+
+impl Outputs {
+    pub fn new(ws_clients: Clients, tcp_clients: TcpClients, udp: SharedUdpOutput, can: SharedCanOutput) -> Self {
+        Self { ws_clients, tcp_clients, udp, can, recorder: SharedRecorder::default(), replaying: Arc::default() }
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        self.replaying.load(Ordering::Relaxed)
+    }
+
+    pub fn set_replaying(&self, replaying: bool) {
+        self.replaying.store(replaying, Ordering::Relaxed);
+    }
+
+    /// Kirim satu update sensor ke WebSocket (JSON & Signal K), TCP, UDP, dan CAN.
+    pub async fn publish(&self, frame: OutputFrame) {
+        self.can.send(&frame.n2k);
+        let frame = Arc::new(frame);
+        broadcast_tcp_frame(&self.tcp_clients, &frame).await;
+        self.udp.send(&frame).await;
+        // Frame dari log NMEA tidak punya JSON
+        if !frame.json.is_empty() {
+            broadcast_ws_message(&self.ws_clients, frame.json.clone()).await;
+        }
+        if let Some(delta) = &frame.signalk {
+            signalk::broadcast_delta(&self.ws_clients, delta).await;
+        }
+    }
+}
+
+pub type TcpTx = mpsc::Sender<TcpFrame>;
+pub type TcpClients = Arc<RwLock<Vec<TcpTx>>>;
+
+/// State simulator yang bisa diubah lewat kalimat NMEA dari client TCP.
+#[derive(Clone)]
+pub struct TcpContext {
+    pub clients: TcpClients,
+    pub sensors: SensorContext,
+}
+
+/// Kirim satu update sensor ke semua client TCP; frame dibuang untuk client yang antriannya penuh.
+pub async fn broadcast_tcp_frame(clients: &TcpClients, frame: &Arc<OutputFrame>) {
+    let frame = TcpFrame::Data(frame.clone());
+    let clients_guard = clients.read().await;
+    for client_tx in clients_guard.iter() {
+        let _ = client_tx.try_send(frame.clone());
+    }
+}
+
+/// Koneksi TCP ala multiplexer NMEA:
+/// - client menerima stream NMEA 0183, JSON per baris, atau NMEA 2000 teks (default sesuai port)
+/// - baris `FORMAT NMEA|JSON|YDRAW|ACTISENSE` mengganti format kapan saja
+/// - baris yang diawali `$`/`!` diterapkan ke state simulator (lihat `nmea_input`)
+pub async fn handle_tcp_connection(socket: TcpStream, ctx: TcpContext, default_format: StreamFormat) {
+    let (read, mut write) = socket.into_split();
+    let (tx, mut rx) = mpsc::channel::<TcpFrame>(TCP_QUEUE_LEN);
+    ctx.clients.write().await.push(tx.clone());
+
+    // Task untuk mengirim stream data ke client sesuai format yang dipilih
+    let writer = tokio::spawn(async move {
+        let mut format = default_format;
+        while let Some(frame) = rx.recv().await {
+            let text = match frame {
+                TcpFrame::Data(frame) => frame.render(format).concat(),
+                TcpFrame::SetFormat(new_format) => {
+                    format = new_format;
+                    continue;
+                }
+                TcpFrame::Reply(text) => text,
+            };
+            if write.write_all(text.as_bytes()).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut reader = BufReader::new(read);
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match (&mut reader).take(MAX_TCP_LINE_LEN as u64 + 1).read_until(b'\n', &mut buf).await {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
+        if buf.len() > MAX_TCP_LINE_LEN {
+            eprintln!("[TCP]: Client sent a line longer than {} bytes, disconnecting.", MAX_TCP_LINE_LEN);
+            break;
+        }
+        let Ok(line) = std::str::from_utf8(&buf) else { continue };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(value) = line.strip_prefix("FORMAT ").or_else(|| line.strip_prefix("format ")) {
+            let reply = match StreamFormat::parse(value.trim()) {
+                Some(format) => {
+                    // Menunggu antrian: perubahan format tidak boleh hilang
+                    let _ = tx.send(TcpFrame::SetFormat(format)).await;
+                    format!("OK FORMAT {}\r\n", value.trim().to_ascii_uppercase())
+                }
+                None => "ERR unknown format, use NMEA, JSON, YDRAW or ACTISENSE\r\n".to_string(),
+            };
+            let _ = tx.send(TcpFrame::Reply(reply)).await;
+        } else if line.starts_with('$') || line.starts_with('!') {
+            match nmea_input::apply_nmea_input(line, &ctx.sensors) {
+                Ok(kind) => println!("[TCP]: Applied {} from client.", kind),
+                Err(e) => eprintln!("[TCP]: Rejected input {:?}: {}", line, e),
+            }
+        }
+    }
+
+    drop(tx);
+    writer.abort();
+    let _ = writer.await;
+    ctx.clients.write().await.retain(|c| !c.is_closed());
+    println!("A TCP client disconnected.");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    fn frame() -> Arc<OutputFrame> {
+        Arc::new(OutputFrame { nmea: vec!["$HEHDT,10.0,T*1D".to_string()], json: String::new(), n2k: Vec::new(), signalk: None })
+    }
+
+    #[tokio::test]
+    async fn slow_tcp_client_queue_is_bounded() {
+        let clients: TcpClients = Arc::default();
+        let (tx, rx) = mpsc::channel(TCP_QUEUE_LEN);
+        clients.write().await.push(tx);
+        for _ in 0..TCP_QUEUE_LEN * 4 {
+            broadcast_tcp_frame(&clients, &frame()).await;
+        }
+        assert_eq!(rx.len(), TCP_QUEUE_LEN);
+    }
+
+    #[tokio::test]
+    async fn overlong_tcp_line_disconnects_client() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let ctx = TcpContext { clients: Arc::default(), sensors: SensorContext::default() };
+        let server = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            handle_tcp_connection(socket, ctx, StreamFormat::Nmea).await;
+        });
+
+        let mut client = TcpStream::connect(addr).await.unwrap();
+        client.write_all(b"FORMAT JSON\r\n").await.unwrap();
+        client.write_all(&vec![b'A'; MAX_TCP_LINE_LEN * 4]).await.unwrap();
+        let mut received = Vec::new();
+        let read = tokio::time::timeout(std::time::Duration::from_secs(5), client.read_to_end(&mut received)).await;
+        assert!(read.is_ok(), "server kept the connection open");
+        server.await.unwrap();
+    }
+}
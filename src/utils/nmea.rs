@@ -0,0 +1,332 @@
+use chrono::{DateTime, Timelike, Utc};
+
+/// Talker ID default untuk GPS (GNSS tunggal)
+pub const DEFAULT_GPS_TALKER: &str = "GP";
+/// Talker ID default untuk gyro (heading, north seeking gyro)
+pub const DEFAULT_GYRO_TALKER: &str = "HE";
+/// Talker ID default untuk anemometer (weather instruments)
+pub const DEFAULT_ANEMO_TALKER: &str = "WI";
+/// Talker ID default untuk barometer (weather instruments)
+pub const DEFAULT_BARO_TALKER: &str = "WI";
+/// Talker ID default untuk sensor suhu (transducer)
+pub const DEFAULT_THERMAL_TALKER: &str = "YX";
+/// Talker ID default untuk depth sounder
+pub const DEFAULT_DEPTH_TALKER: &str = "SD";
+/// Talker ID default untuk speed log (water speed sensor)
+pub const DEFAULT_SPEEDLOG_TALKER: &str = "VW";
+/// Talker ID default untuk navigasi rute (electronic chart system)
+pub const DEFAULT_ROUTE_TALKER: &str = "EC";
+
+/// Checksum NMEA 0183: XOR semua byte di antara '$' dan '*'.
+pub fn checksum(body: &str) -> u8 {
+    body.bytes().fold(0, |acc, b| acc ^ b)
+}
+
+/// Menyusun kalimat lengkap `$<talker><kind>,<fields>*hh` (tanpa CRLF).
+pub fn sentence(talker: &str, kind: &str, fields: &[String]) -> String {
+    let body = format!("{}{},{}", talker, kind, fields.join(","));
+    format!("${}*{:02X}", body, checksum(&body))
+}
+
+/// TAG block IEC 61162-450 `\c:<unix ms>,s:<source>*hh\` untuk log NMEA bertimestamp.
+pub fn tag_block(time: &DateTime<Utc>, source: &str) -> String {
+    let body = format!("c:{},s:{}", time.timestamp_millis(), source);
+    format!("\\{}*{:02X}\\", body, checksum(&body))
+}
+
+/// Memisahkan TAG block dari baris log: `(timestamp c:, source s:, kalimat)`.
+/// Nilai `c:` diterima dalam detik atau milidetik UNIX; checksum TAG block tidak divalidasi.
+pub fn split_tag_block(line: &str) -> (Option<DateTime<Utc>>, Option<String>, &str) {
+    let line = line.trim();
+    let Some((tag, sentence)) = line.strip_prefix('\\').and_then(|rest| rest.split_once('\\')) else {
+        return (None, None, line);
+    };
+
+    let fields = tag.split_once('*').map_or(tag, |(fields, _)| fields);
+    let (mut time, mut source) = (None, None);
+    for field in fields.split(',') {
+        match field.split_once(':') {
+            Some(("c", value)) => {
+                time = value.parse::<i64>().ok().and_then(|value| {
+                    if value > 100_000_000_000 { DateTime::from_timestamp_millis(value) } else { DateTime::from_timestamp(value, 0) }
+                });
+            }
+            Some(("s", value)) => source = Some(value.to_string()),
+            _ => {}
+        }
+    }
+    (time, source, sentence.trim())
+}
+
+/// Teks bebas untuk satu field NMEA (mis. nama waypoint): hanya ASCII tercetak tanpa karakter
+/// cadangan NMEA (`$ ! * , \ ^ ~`), dipotong ke `max_len` karakter.
+pub fn sanitize_field(value: &str, max_len: usize) -> String {
+    value
+        .trim()
+        .chars()
+        .filter(|c| matches!(c, ' '..='~') && !matches!(c, '$' | '!' | '*' | ',' | '\\' | '^' | '~'))
+        .take(max_len)
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// Talker ID valid: dua karakter huruf besar/angka, mis. "GP", "GN", "HE".
+pub fn is_valid_talker(talker: &str) -> bool {
+    talker.len() == 2 && talker.bytes().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+}
+
+/// Format sudut ke `d..dmm.mmmm` dengan jumlah digit derajat tertentu.
+fn format_degrees_minutes(value: f64, degree_digits: usize) -> String {
+    if !value.is_finite() {
+        return "NaN".to_string();
+    }
+    // Dihitung dalam satuan 1e-4 menit agar pembulatan tidak menghasilkan "60.0000"
+    let total = (value.abs() * 60.0 * 10_000.0).round() as u64;
+    let degrees = total / 600_000;
+    let minutes = (total % 600_000) / 10_000;
+    let fraction = total % 10_000;
+    format!("{:0width$}{:02}.{:04}", degrees, minutes, fraction, width = degree_digits)
+}
+
+/// Latitude ke pasangan field `ddmm.mmmm,N|S`.
+pub fn format_latitude(lat: f64) -> [String; 2] {
+    let hemisphere = if lat < 0.0 { "S" } else { "N" };
+    [format_degrees_minutes(lat, 2), hemisphere.to_string()]
+}
+
+/// Longitude ke pasangan field `dddmm.mmmm,E|W`.
+pub fn format_longitude(lon: f64) -> [String; 2] {
+    let hemisphere = if lon < 0.0 { "W" } else { "E" };
+    [format_degrees_minutes(lon, 3), hemisphere.to_string()]
+}
+
+/// Waktu UTC `hhmmss.ss`
+pub fn format_time(time: &DateTime<Utc>) -> String {
+    format!(
+        "{:02}{:02}{:02}.{:02}",
+        time.hour(),
+        time.minute(),
+        time.second(),
+        time.nanosecond().min(999_999_999) / 10_000_000
+    )
+}
+
+/// Tanggal UTC `ddmmyy`
+pub fn format_date(time: &DateTime<Utc>) -> String {
+    time.format("%d%m%y").to_string()
+}
+
+/// Sudut 0..360 dengan satu desimal.
+pub fn format_angle(angle: f64) -> String {
+    // NaN ditulis apa adanya, sama seperti field angka lain yang diformat `{:.1}`
+    if !angle.is_finite() {
+        return "NaN".to_string();
+    }
+    // Dibulatkan ke persepuluh derajat lebih dulu agar 359.96 menjadi "0.0", bukan "360.0"
+    let tenths = (angle.rem_euclid(360.0) * 10.0).round() as u64 % 3600;
+    format!("{}.{}", tenths / 10, tenths % 10)
+}
+
+/// Kalimat NMEA hasil parsing: `$<talker><kind>,<fields...>*hh`
+#[derive(Debug, Clone)]
+pub struct NmeaSentence {
+    pub talker: String,
+    pub kind: String,
+    pub fields: Vec<String>,
+}
+
+impl NmeaSentence {
+    /// Alamat lengkap, mis. "HEHDT".
+    pub fn address(&self) -> String {
+        format!("{}{}", self.talker, self.kind)
+    }
+
+    /// Field ke-`index` (setelah kind), string kosong jika tidak ada.
+    pub fn field(&self, index: usize) -> &str {
+        self.fields.get(index).map(String::as_str).unwrap_or("")
+    }
+
+    /// Field ke-`index` sebagai angka; `None` jika kosong, tidak valid, atau tidak hingga (`NaN`, `inf`).
+    pub fn number(&self, index: usize) -> Option<f64> {
+        self.field(index).parse().ok().filter(|value: &f64| value.is_finite())
+    }
+}
+
+/// Parsing satu kalimat NMEA 0183. Checksum divalidasi jika ada; kalimat non-ASCII ditolak.
+pub fn parse_sentence(line: &str) -> Result<NmeaSentence, String> {
+    let line = line.trim();
+    if !line.is_ascii() {
+        return Err(format!("Not an NMEA sentence (non-ASCII): {}", line.escape_default()));
+    }
+    let rest = line
+        .strip_prefix('$')
+        .or_else(|| line.strip_prefix('!'))
+        .ok_or_else(|| format!("Not an NMEA sentence: {}", line))?;
+
+    let body = match rest.split_once('*') {
+        Some((body, cs)) => {
+            let expected = u8::from_str_radix(cs.trim(), 16).map_err(|_| format!("Invalid checksum field: {}", line))?;
+            if checksum(body) != expected {
+                return Err(format!("Checksum mismatch: {}", line));
+            }
+            body
+        }
+        None => rest,
+    };
+
+    let mut parts = body.split(',');
+    let address = parts.next().unwrap_or("");
+    // Kalimat proprietary ($P...) tidak memiliki talker dua huruf
+    let (talker, kind) = if address.starts_with('P') || address.len() < 5 {
+        ("", address)
+    } else {
+        address.split_at(2)
+    };
+
+    Ok(NmeaSentence {
+        talker: talker.to_string(),
+        kind: kind.to_string(),
+        fields: parts.map(str::to_string).collect(),
+    })
+}
+
+/// Field `d..dmm.mmmm` + hemisphere ke derajat desimal; `None` jika bukan digit ASCII,
+/// menit di luar 0..60, atau hasil melebihi `max` derajat.
+fn parse_degrees_minutes(value: &str, hemisphere: &str, degree_digits: usize, negative: &str, max: f64) -> Option<f64> {
+    if !value.is_ascii() || value.len() <= degree_digits {
+        return None;
+    }
+    let degrees: u32 = value.get(..degree_digits)?.parse().ok()?;
+    let minutes: f64 = value.get(degree_digits..)?.parse().ok()?;
+    if !(0.0..60.0).contains(&minutes) {
+        return None;
+    }
+    let decimal = degrees as f64 + minutes / 60.0;
+    if decimal > max {
+        return None;
+    }
+    Some(if hemisphere == negative { -decimal } else { decimal })
+}
+
+pub fn parse_latitude(value: &str, hemisphere: &str) -> Option<f64> {
+    parse_degrees_minutes(value, hemisphere, 2, "S", 90.0)
+}
+
+pub fn parse_longitude(value: &str, hemisphere: &str) -> Option<f64> {
+    parse_degrees_minutes(value, hemisphere, 3, "W", 180.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(fields: &[&str]) -> Vec<String> {
+        fields.iter().map(|f| f.to_string()).collect()
+    }
+
+    #[test]
+    fn sentence_matches_reference_checksums() {
+        let gga = sentence("GP", "GGA", &strings(&["123519", "4807.038", "N", "01131.000", "E", "1", "08", "0.9", "545.4", "M", "46.9", "M", "", ""]));
+        assert_eq!(gga, "$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47");
+
+        let rmc = sentence("GP", "RMC", &strings(&["123519", "A", "4807.038", "N", "01131.000", "E", "022.4", "084.4", "230394", "003.1", "W"]));
+        assert_eq!(rmc, "$GPRMC,123519,A,4807.038,N,01131.000,E,022.4,084.4,230394,003.1,W*6A");
+    }
+
+    #[test]
+    fn checksum_xors_body_bytes() {
+        assert_eq!(checksum(""), 0);
+        assert_eq!(checksum("HEHDT,123.4,T"), b"HEHDT,123.4,T".iter().fold(0, |acc, b| acc ^ b));
+        assert_eq!(checksum("AA"), 0);
+    }
+
+    #[test]
+    fn degrees_minutes_round_without_sixty_minutes() {
+        assert_eq!(format_latitude(48.1173), ["4807.0380".to_string(), "N".to_string()]);
+        assert_eq!(format_longitude(-11.516666666), ["01131.0000".to_string(), "W".to_string()]);
+        assert_eq!(format_latitude(-33.5), ["3330.0000".to_string(), "S".to_string()]);
+        // 59.999996' harus dibulatkan ke derajat berikutnya, bukan "0960.0000"
+        assert_eq!(format_latitude(9.0 + 59.999996 / 60.0)[0], "1000.0000");
+        assert_eq!(format_longitude(179.0 + 59.99994 / 60.0)[0], "17959.9999");
+        assert_eq!(format_latitude(f64::NAN)[0], "NaN");
+    }
+
+    #[test]
+    fn angle_wraps_after_rounding() {
+        assert_eq!(format_angle(359.96), "0.0");
+        assert_eq!(format_angle(359.94), "359.9");
+        assert_eq!(format_angle(-10.0), "350.0");
+        assert_eq!(format_angle(725.0), "5.0");
+        assert_eq!(format_angle(f64::NAN), "NaN");
+    }
+
+    #[test]
+    fn parse_sentence_validates_checksum() {
+        let parsed = parse_sentence("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47\r\n").unwrap();
+        assert_eq!(parsed.address(), "GPGGA");
+        assert_eq!(parsed.field(1), "4807.038");
+        assert_eq!(parsed.number(7), Some(0.9));
+        assert_eq!(parsed.field(99), "");
+        assert_eq!(parse_latitude(parsed.field(1), parsed.field(2)), Some(48.0 + 7.038 / 60.0));
+
+        let mismatch = parse_sentence("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*48");
+        assert!(mismatch.unwrap_err().starts_with("Checksum mismatch"));
+        let invalid = parse_sentence("$HEHDT,10.0,T*ZZ");
+        assert!(invalid.unwrap_err().starts_with("Invalid checksum field"));
+        assert!(parse_sentence("GPGGA,1,2").is_err());
+
+        // Tanpa checksum tetap diterima
+        assert_eq!(parse_sentence("$HEHDT,10.0,T").unwrap().number(0), Some(10.0));
+        let proprietary = parse_sentence("$PGRME,15.0,M,45.0,M,25.0,M").unwrap();
+        assert_eq!((proprietary.talker.as_str(), proprietary.kind.as_str()), ("", "PGRME"));
+    }
+
+    #[test]
+    fn parse_sentence_rejects_non_ascii() {
+        assert!(parse_sentence("$GPGLL,4é00.0,N,01131.000,E").is_err());
+        assert!(parse_sentence("$aéGLL,4800.0,N,01131.000,E").is_err());
+        assert_eq!(parse_latitude("4é00.0", "N"), None);
+        assert_eq!(parse_longitude("01é1.000", "E"), None);
+    }
+
+    #[test]
+    fn numbers_and_coordinates_must_be_finite_and_in_range() {
+        let parsed = parse_sentence("$GPVTG,NaN,T,,M,inf,N,-inf,K").unwrap();
+        assert_eq!((parsed.number(0), parsed.number(4), parsed.number(6)), (None, None, None));
+
+        assert_eq!(parse_latitude("4807.038", "S"), Some(-(48.0 + 7.038 / 60.0)));
+        assert_eq!(parse_latitude("9100.000", "N"), None);
+        assert_eq!(parse_latitude("4860.000", "N"), None);
+        assert_eq!(parse_latitude("48NaN", "N"), None);
+        assert_eq!(parse_latitude("-100.00", "N"), None);
+        assert_eq!(parse_longitude("18000.000", "W"), Some(-180.0));
+        assert_eq!(parse_longitude("18100.000", "E"), None);
+    }
+
+    #[test]
+    fn tag_block_round_trips() {
+        let time = DateTime::from_timestamp_millis(1_714_564_800_100).unwrap();
+        let tag = tag_block(&time, "gps");
+        assert_eq!(tag, "\\c:1714564800100,s:gps*65\\");
+
+        let line = format!("{}$GPZDA,120000.10,01,05,2024,00,00*6C", tag);
+        let (parsed_time, source, sentence) = split_tag_block(&line);
+        assert_eq!(parsed_time, Some(time));
+        assert_eq!(source.as_deref(), Some("gps"));
+        assert_eq!(sentence, "$GPZDA,120000.10,01,05,2024,00,00*6C");
+
+        // `c:` dalam detik
+        let (parsed_time, _, _) = split_tag_block("\\c:1714564800*5A\\$HEHDT,10.0,T");
+        assert_eq!(parsed_time, DateTime::from_timestamp(1_714_564_800, 0));
+
+        assert_eq!(split_tag_block(" $HEHDT,10.0,T "), (None, None, "$HEHDT,10.0,T"));
+    }
+
+    #[test]
+    fn sanitize_field_drops_reserved_characters() {
+        assert_eq!(sanitize_field(" WP,1*$x\r\n", 10), "WP1x");
+        assert_eq!(sanitize_field("Pelabuhan Tanjung Priok", 10), "Pelabuhan");
+        assert_eq!(sanitize_field("Köln~^!\\", 10), "Kln");
+    }
+}
@@ -0,0 +1,181 @@
+use crate::data::gps_data::GpsTruth;
+use crate::data::gyro_data::GyroState;
+use crate::data::sensor_data::DataSource;
+use crate::data::vessel_data::Steering;
+use crate::services::gps_service::Gps;
+use crate::services::gyro_service::Gyro;
+use crate::services::sensor::SensorContext;
+use crate::utils::nmea::{self, NmeaSentence};
+use crate::utils::sim_clock;
+
+/// Pesan error jika heading/posisi sensor dikendalikan model kapal.
+pub const VESSEL_OWNS_MOTION: &str =
+    "Vessel model owns heading and position; use HSC/HTC/HTD or PATCH /api/vessel instead";
+
+/// Batas SOG (knot) dari kalimat input; nilai di atasnya dianggap rusak
+const MAX_SOG: f64 = 100.0;
+
+/// Heading/COG dari kalimat harus dalam 0..=360 derajat.
+fn heading(value: Option<f64>, name: &str) -> Result<Option<f64>, String> {
+    match value {
+        Some(value) if !(0.0..=360.0).contains(&value) => Err(format!("{} out of range: {}", name, value)),
+        value => Ok(value.map(|value| value.rem_euclid(360.0))),
+    }
+}
+
+fn sog(value: Option<f64>) -> Result<Option<f64>, String> {
+    match value {
+        Some(value) if !(0.0..=MAX_SOG).contains(&value) => Err(format!("SOG out of range: {}", value)),
+        value => Ok(value),
+    }
+}
+
+/// Menerapkan kalimat NMEA yang dikirim client (mis. autopilot) ke state simulator.
+/// Mengembalikan jenis kalimat yang diterapkan, atau pesan error jika ditolak.
+///
+/// Kalimat yang didukung:
+/// - HSC / HTC / HTD: perintah heading → heading hold model kapal, atau tanpa model kapal
+///   langsung `GyroState.yaw` dan `GpsState.cog`
+/// - HDT / THS: heading true → `GyroState.yaw`
+/// - ROT: rate of turn (derajat/menit) → `GyroState.yaw_rate`
+/// - RMC / GGA / GLL: posisi (RMC juga SOG/COG) → `GpsState`
+/// - VTG: COG/SOG → `GpsState`
+///
+/// Selama model kapal ada, heading dan posisi diambil dari model tersebut, sehingga kalimat
+/// heading/posisi ditolak (kecuali sensor sedang memutar log NMEA).
+pub fn apply_nmea_input(
+    line: &str,
+    sensors: &SensorContext,
+) -> Result<String, String> {
+    let sentence = nmea::parse_sentence(line)?;
+
+    match sentence.kind.as_str() {
+        "HSC" => apply_heading_command(&sentence, sentence.number(0), sensors),
+        // Field ke-9 HTC/HTD: commanded heading-to-steer
+        "HTC" | "HTD" => apply_heading_command(&sentence, sentence.number(9), sensors),
+        "HDT" | "THS" => {
+            let heading = heading(sentence.number(0), "Heading")?.ok_or("Missing heading field")?;
+            update_gyro(sensors, |gyro| gyro.yaw = heading)?;
+            Ok(sentence.address())
+        }
+        "ROT" => {
+            if sentence.field(1) == "V" {
+                return Err("ROT data marked invalid".to_string());
+            }
+            let rate_per_min = sentence.number(0).ok_or("Missing rate of turn field")?;
+            update_gyro(sensors, |gyro| gyro.yaw_rate = rate_per_min / 60.0)?;
+            Ok(sentence.address())
+        }
+        "RMC" => {
+            if sentence.field(1) != "A" {
+                return Err("RMC status is not valid (A)".to_string());
+            }
+            let lat = nmea::parse_latitude(sentence.field(2), sentence.field(3)).ok_or("Invalid latitude")?;
+            let lon = nmea::parse_longitude(sentence.field(4), sentence.field(5)).ok_or("Invalid longitude")?;
+            let (sog, cog) = (sog(sentence.number(6))?, heading(sentence.number(7), "COG")?);
+            update_gps(sensors, |gps| {
+                gps.latitude = lat;
+                gps.longitude = lon;
+                if let Some(sog) = sog { gps.sog = sog; }
+                if let Some(cog) = cog { gps.cog = cog; }
+            })?;
+            Ok(sentence.address())
+        }
+        "GGA" | "GLL" => {
+            // GGA: time,lat,N,lon,E,...  GLL: lat,N,lon,E,...
+            let offset = if sentence.kind == "GGA" { 1 } else { 0 };
+            let lat = nmea::parse_latitude(sentence.field(offset), sentence.field(offset + 1)).ok_or("Invalid latitude")?;
+            let lon = nmea::parse_longitude(sentence.field(offset + 2), sentence.field(offset + 3)).ok_or("Invalid longitude")?;
+            update_gps(sensors, |gps| {
+                gps.latitude = lat;
+                gps.longitude = lon;
+            })?;
+            Ok(sentence.address())
+        }
+        "VTG" => {
+            let (cog, sog) = (heading(sentence.number(0), "COG")?, sog(sentence.number(4))?);
+            update_gps(sensors, |gps| {
+                if let Some(cog) = cog { gps.cog = cog; }
+                if let Some(sog) = sog { gps.sog = sog; }
+            })?;
+            Ok(sentence.address())
+        }
+        other => Err(format!("Unsupported sentence: {}", other)),
+    }
+}
+
+fn apply_heading_command(
+    sentence: &NmeaSentence,
+    heading: Option<f64>,
+    sensors: &SensorContext,
+) -> Result<String, String> {
+    let heading = self::heading(heading, "Commanded heading")?.ok_or("Missing commanded heading field")?;
+    // Model kapal memegang heading: perintah menjadi heading hold yang dikemudikan model
+    let vessel = sensors.vessel();
+    if let Some(vessel) = vessel.write().unwrap().as_mut() {
+        vessel.steering = Steering::Heading { heading };
+        return Ok(sentence.address());
+    }
+    let gyro_result = update_gyro(sensors, |gyro| gyro.yaw = heading);
+    let gps_result = update_gps(sensors, |gps| gps.cog = heading);
+    // Cukup salah satu sensor yang aktif agar perintah dianggap berhasil
+    gyro_result.or(gps_result)?;
+    Ok(sentence.address())
+}
+
+/// Kalimat input mengubah posisi/gerak sebenarnya GPS; error GNSS diterapkan di langkah berikutnya.
+fn update_gps(sensors: &SensorContext, apply: impl FnOnce(&mut GpsTruth)) -> Result<(), String> {
+    let vessel_exists = sensors.vessel_state().is_some();
+    let state = sensors.shared::<Gps>().ok_or("GPS sensor not registered")?;
+    let mut guard = state.write().unwrap();
+    let gps = guard.as_mut().ok_or("GPS simulation not created")?;
+    if vessel_exists && gps.source == DataSource::Model {
+        return Err(VESSEL_OWNS_MOTION.to_string());
+    }
+    apply(&mut gps.truth);
+    // Override PATCH (replay log NMEA) tetap berlaku di atas nilai dari kalimat
+    let overrides = gps.overrides;
+    overrides.apply(&mut gps.truth);
+    gps.report_truth();
+    gps.last_update = sim_clock::now();
+    Ok(())
+}
+
+fn update_gyro(sensors: &SensorContext, apply: impl FnOnce(&mut GyroState)) -> Result<(), String> {
+    let vessel_exists = sensors.vessel_state().is_some();
+    let state = sensors.shared::<Gyro>().ok_or("Gyro sensor not registered")?;
+    let mut guard = state.write().unwrap();
+    let gyro = guard.as_mut().ok_or("Gyro simulation not created")?;
+    if vessel_exists && gyro.source == DataSource::Model {
+        return Err(VESSEL_OWNS_MOTION.to_string());
+    }
+    apply(gyro);
+    let overrides = gyro.overrides;
+    overrides.apply(gyro);
+    gyro.last_update = sim_clock::now();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_finite_or_out_of_range_values_are_rejected() {
+        let sensors = SensorContext::default();
+        for line in [
+            "$APHSC,NaN,T,,M",
+            "$APHSC,inf,T,,M",
+            "$APHSC,400.0,T,,M",
+            "$HEHDT,-1.0,T",
+            "$HEROT,NaN,A",
+            "$GPVTG,90.0,T,,M,1e9,N,,K",
+            "$GPRMC,120000,A,4807.038,N,01131.000,E,NaN,400.0,010524,,",
+            "$GPGLL,9100.000,N,01131.000,E",
+            "$GPGLL,4807.038,N,18100.000,E",
+        ] {
+            let err = apply_nmea_input(line, &sensors).unwrap_err();
+            assert!(!err.contains("not registered"), "{} reached the sensors: {}", line, err);
+        }
+    }
+}
@@ -0,0 +1,292 @@
+use crate::data::gps_data::GpsOverrides;
+use crate::data::gyro_data::GyroOverrides;
+use crate::data::recording_data::{RecordingFormat, ReplayState, ReplayStatus, StartNmeaLogRequest, UpdateReplayRequest};
+use crate::data::sensor_data::DataSource;
+use crate::services::gps_service::Gps;
+use crate::services::gyro_service::Gyro;
+use crate::services::sensor::SensorContext;
+use crate::utils::nmea::{self, NmeaSentence};
+use crate::utils::nmea_input;
+use crate::utils::playback::{self, PlaybackControl};
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+
+pub type SharedNmeaLogSource = Arc<NmeaLogSource>;
+
+/// Kalimat yang diterapkan ke GPS/gyro; kalimat lain di log dilewati
+const SUPPORTED: [&str; 5] = ["RMC", "GGA", "VTG", "HDT", "ROT"];
+
+pub struct LogSentence {
+    pub time: DateTime<Utc>,
+    pub sentence: String,
+}
+
+fn parse_time(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value, "%H%M%S%.f").ok()
+}
+
+/// Tanggal dari RMC (`ddmmyy`) atau ZDA (hari, bulan, tahun).
+fn sentence_date(sentence: &NmeaSentence) -> Option<NaiveDate> {
+    match sentence.kind.as_str() {
+        "RMC" => NaiveDate::parse_from_str(sentence.field(8), "%d%m%y").ok(),
+        "ZDA" => NaiveDate::from_ymd_opt(
+            sentence.field(3).parse().ok()?,
+            sentence.field(2).parse().ok()?,
+            sentence.field(1).parse().ok()?,
+        ),
+        _ => None,
+    }
+}
+
+fn sentence_time(sentence: &NmeaSentence) -> Option<NaiveTime> {
+    match sentence.kind.as_str() {
+        "RMC" | "GGA" | "ZDA" => parse_time(sentence.field(0)),
+        "GLL" => parse_time(sentence.field(4)),
+        _ => None,
+    }
+}
+
+/// Membaca log NMEA 0183. Waktu tiap kalimat diambil dari TAG block `c:` jika ada, jika tidak
+/// dari field waktu RMC/GGA/GLL/ZDA terakhir (pergantian hari dideteksi otomatis).
+/// Kalimat dengan checksum salah dilewati.
+pub fn read_nmea_log(path: &str) -> Result<Vec<LogSentence>, String> {
+    let file = File::open(path).map_err(|e| format!("Cannot open {}: {}", path, e))?;
+    let mut parsed = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(|e| e.to_string())?;
+        let (tag_time, _, text) = nmea::split_tag_block(&line);
+        if let Ok(sentence) = nmea::parse_sentence(text) {
+            parsed.push((tag_time, sentence, text.to_string()));
+        }
+    }
+
+    // Tanggal awal dari kalimat bertanggal pertama, agar fix sebelum RMC/ZDA pertama tetap berurutan
+    let mut date = parsed
+        .iter()
+        .find_map(|(_, sentence, _)| sentence_date(sentence))
+        .unwrap_or_default();
+    let mut current: Option<DateTime<Utc>> = None;
+    let mut timed = Vec::with_capacity(parsed.len());
+    for (tag_time, sentence, text) in parsed {
+        if let Some(time) = tag_time {
+            current = Some(time);
+        } else if let Some(time) = sentence_time(&sentence) {
+            date = sentence_date(&sentence).unwrap_or(date);
+            let mut candidate = date.and_time(time).and_utc();
+            if current.is_some_and(|current| candidate < current - Duration::hours(12)) {
+                date = date.succ_opt().unwrap_or(date);
+                candidate = date.and_time(time).and_utc();
+            }
+            current = Some(candidate);
+        }
+        if SUPPORTED.contains(&sentence.kind.as_str()) {
+            timed.push((current, text));
+        }
+    }
+
+    let first = timed
+        .iter()
+        .find_map(|(time, _)| *time)
+        .ok_or("NMEA log has no timestamps (TAG block, RMC, GGA, GLL or ZDA) or no RMC/GGA/VTG/HDT/ROT")?;
+    let mut sentences: Vec<LogSentence> = timed
+        .into_iter()
+        .map(|(time, sentence)| LogSentence { time: time.unwrap_or(first), sentence })
+        .collect();
+    sentences.sort_by_key(|s| s.time);
+    Ok(sentences)
+}
+
+/// Menandai GPS dan gyro (yang sudah dibuat) sebagai diisi dari log atau kembali ke model.
+fn set_source(ctx: &SensorContext, source: DataSource) {
+    if let Some(gps) = ctx.shared::<Gps>() {
+        if let Some(gps) = gps.write().unwrap().as_mut() {
+            gps.source = source;
+        }
+    }
+    if let Some(gyro) = ctx.shared::<Gyro>() {
+        if let Some(gyro) = gyro.write().unwrap().as_mut() {
+            gyro.source = source;
+        }
+    }
+}
+
+fn reset_overrides(ctx: &SensorContext) {
+    if let Some(gps) = ctx.shared::<Gps>() {
+        if let Some(gps) = gps.write().unwrap().as_mut() {
+            gps.overrides = GpsOverrides::default();
+        }
+    }
+    if let Some(gyro) = ctx.shared::<Gyro>() {
+        if let Some(gyro) = gyro.write().unwrap().as_mut() {
+            gyro.overrides = GyroOverrides::default();
+        }
+    }
+}
+
+struct ActiveLog {
+    control: Arc<Mutex<PlaybackControl>>,
+    task: JoinHandle<()>,
+}
+
+/// Pemutar log NMEA sebagai sumber data: RMC/GGA/VTG mengisi `GpsState`, HDT/ROT mengisi
+/// `GyroState`, lalu state dipublikasikan lewat output biasa. Model sintetis GPS/gyro dimatikan
+/// selama log diputar; PATCH sensor menjadi override di atas data log.
+pub struct NmeaLogSource {
+    ctx: SensorContext,
+    active: Mutex<Option<ActiveLog>>,
+}
+
+impl NmeaLogSource {
+    pub fn new(ctx: SensorContext) -> Self {
+        Self { ctx, active: Mutex::new(None) }
+    }
+
+    pub fn status(&self) -> Option<ReplayStatus> {
+        self.active.lock().unwrap().as_ref().map(|active| active.control.lock().unwrap().status.clone())
+    }
+
+    /// Memulai pemutaran log (pemutaran lama dihentikan). GPS atau gyro harus sudah dibuat.
+    pub fn start(&self, req: StartNmeaLogRequest, sentences: Vec<LogSentence>) -> Result<ReplayStatus, String> {
+        if self.ctx.get::<Gps>().is_none() && self.ctx.get::<Gyro>().is_none() {
+            return Err("Create the GPS or gyro simulation before playing an NMEA log.".to_string());
+        }
+
+        let status = ReplayStatus {
+            path: req.path,
+            format: RecordingFormat::Nmea,
+            message_count: 0,
+            duration: 0.0,
+            position: 0.0,
+            speed: req.speed,
+            looping: req.looping,
+            state: if req.paused { ReplayState::Paused } else { ReplayState::Playing },
+        };
+        let control = PlaybackControl::new(status, playback::offsets(sentences.iter().map(|s| s.time)));
+        let status = control.status.clone();
+
+        set_source(&self.ctx, DataSource::NmeaLog);
+        let control = Arc::new(Mutex::new(control));
+        let task = tokio::spawn(run_log(control.clone(), sentences, self.ctx.clone()));
+        if let Some(previous) = self.active.lock().unwrap().replace(ActiveLog { control, task }) {
+            previous.task.abort();
+        }
+        println!("[NMEA Log]: Playing {} ({} sentences, {:.1} s)", status.path, status.message_count, status.duration);
+        Ok(status)
+    }
+
+    pub fn update(&self, patch: UpdateReplayRequest) -> Option<ReplayStatus> {
+        let guard = self.active.lock().unwrap();
+        let mut control = guard.as_ref()?.control.lock().unwrap();
+        control.update(patch);
+        Some(control.status.clone())
+    }
+
+    /// Menghapus override PATCH; data log berlaku lagi mulai kalimat berikutnya.
+    pub fn clear_overrides(&self) -> bool {
+        if self.active.lock().unwrap().is_none() {
+            return false;
+        }
+        reset_overrides(&self.ctx);
+        true
+    }
+
+    /// Menghentikan pemutaran; GPS dan gyro kembali ke model sintetis dari posisi terakhir.
+    pub fn stop(&self) -> bool {
+        let Some(active) = self.active.lock().unwrap().take() else { return false };
+        active.task.abort();
+        set_source(&self.ctx, DataSource::Model);
+        reset_overrides(&self.ctx);
+        println!("[NMEA Log]: Stopped");
+        true
+    }
+}
+
+async fn run_log(control: Arc<Mutex<PlaybackControl>>, sentences: Vec<LogSentence>, ctx: SensorContext) {
+    let mut last_tick = Instant::now();
+    loop {
+        sleep(playback::TICK).await;
+        let elapsed = last_tick.elapsed().as_secs_f64();
+        last_tick = Instant::now();
+
+        let due = control.lock().unwrap().advance(elapsed);
+        if due.is_empty() {
+            continue;
+        }
+        // Sensor yang dibuat setelah log dimulai ikut diisi dari log
+        set_source(&ctx, DataSource::NmeaLog);
+        for entry in &sentences[due] {
+            // Kalimat GPS saat GPS belum dibuat (atau sebaliknya) cukup dilewati
+            let _ = nmea_input::apply_nmea_input(&entry.sentence, &ctx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sentence(talker: &str, kind: &str, fields: &[&str]) -> String {
+        nmea::sentence(talker, kind, &fields.iter().map(|field| field.to_string()).collect::<Vec<_>>())
+    }
+
+    fn read(name: &str, lines: &[String]) -> Result<Vec<LogSentence>, String> {
+        let path = std::env::temp_dir().join(format!("vessel-nmea-log-{}-{}.nmea", name, std::process::id()));
+        std::fs::write(&path, lines.join("\r\n")).unwrap();
+        let result = read_nmea_log(path.to_str().unwrap());
+        std::fs::remove_file(&path).unwrap();
+        result
+    }
+
+    fn times(sentences: &[LogSentence]) -> Vec<String> {
+        sentences.iter().map(|s| s.time.format("%Y-%m-%d %H:%M:%S").to_string()).collect()
+    }
+
+    #[test]
+    fn times_come_from_fix_sentences_and_roll_over_midnight() {
+        let hdt = sentence("HE", "HDT", &["90.0", "T"]);
+        let lines = [
+            hdt.clone(),
+            sentence("GP", "RMC", &["235959", "A", "6000.000", "N", "02400.000", "E", "5.0", "90.0", "300424", "", ""]),
+            sentence("GP", "GSV", &["1", "1", "00"]),
+            hdt.clone(),
+            sentence("GP", "GGA", &["000001", "6000.000", "N", "02400.000", "E", "1", "08", "0.9", "0.0", "M", "", "M", "", ""]),
+            // Checksum salah: dilewati
+            sentence("GP", "GGA", &["000002", "6000.000", "N", "02400.000", "E", "1", "08", "0.9", "0.0", "M", "", "M", "", ""])
+                .replace("000002", "000003"),
+            hdt,
+        ];
+        let sentences = read("rollover", &lines).unwrap();
+        assert_eq!(sentences.len(), 5);
+        assert_eq!(
+            times(&sentences),
+            [
+                "2024-04-30 23:59:59",
+                "2024-04-30 23:59:59",
+                "2024-04-30 23:59:59",
+                "2024-05-01 00:00:01",
+                "2024-05-01 00:00:01"
+            ]
+        );
+        assert!(sentences.iter().all(|s| !s.sentence.contains("GSV")));
+    }
+
+    #[test]
+    fn tag_block_time_takes_precedence() {
+        let hdt = sentence("HE", "HDT", &["90.0", "T"]);
+        let lines = [format!("\\c:1714564800*00\\{}", hdt), format!("\\c:1714564801500*00\\{}", hdt)];
+        let sentences = read("tag", &lines).unwrap();
+        assert_eq!(times(&sentences), ["2024-05-01 12:00:00", "2024-05-01 12:00:01"]);
+        assert_eq!(sentences[0].sentence, hdt);
+    }
+
+    #[test]
+    fn log_without_timestamps_is_rejected() {
+        assert!(read("untimed", &[sentence("HE", "HDT", &["90.0", "T"])]).is_err());
+        assert!(read_nmea_log("/nonexistent/vessel.nmea").is_err());
+    }
+}
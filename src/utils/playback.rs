@@ -0,0 +1,146 @@
+use crate::data::recording_data::{ReplayState, ReplayStatus, UpdateReplayRequest};
+use chrono::{DateTime, Utc};
+use std::ops::Range;
+use std::time::Duration;
+
+/// Resolusi waktu pemutaran
+pub const TICK: Duration = Duration::from_millis(20);
+pub const MIN_SPEED: f64 = 0.01;
+pub const MAX_SPEED: f64 = 1000.0;
+
+/// Offset (detik) setiap item dari item pertama; `times` harus terurut.
+pub fn offsets(times: impl Iterator<Item = DateTime<Utc>>) -> Vec<f64> {
+    let mut first = None;
+    times
+        .map(|time| {
+            let first = *first.get_or_insert(time);
+            (time - first).num_milliseconds() as f64 / 1000.0
+        })
+        .collect()
+}
+
+/// Posisi, kecepatan, pause, dan loop pemutaran yang dipakai bersama oleh replay rekaman
+/// dan sumber log NMEA. Task pemutar memanggil `advance` setiap `TICK`.
+pub struct PlaybackControl {
+    pub status: ReplayStatus,
+    offsets: Vec<f64>,
+    index: usize,
+}
+
+impl PlaybackControl {
+    pub fn new(mut status: ReplayStatus, offsets: Vec<f64>) -> Self {
+        status.duration = offsets.last().copied().unwrap_or(0.0);
+        status.message_count = offsets.len();
+        status.speed = status.speed.clamp(MIN_SPEED, MAX_SPEED);
+        Self { status, offsets, index: 0 }
+    }
+
+    /// Menerapkan PATCH speed/loop/seek/pause. Play setelah selesai = mulai lagi dari awal.
+    pub fn update(&mut self, patch: UpdateReplayRequest) {
+        if let Some(speed) = patch.speed { self.status.speed = speed.clamp(MIN_SPEED, MAX_SPEED); }
+        if let Some(looping) = patch.looping { self.status.looping = looping; }
+        if let Some(position) = patch.position {
+            self.seek(position.clamp(0.0, self.status.duration));
+            if self.status.state == ReplayState::Finished {
+                self.status.state = ReplayState::Paused;
+            }
+        }
+        match patch.paused {
+            Some(true) if self.status.state == ReplayState::Playing => self.status.state = ReplayState::Paused,
+            Some(false) if self.status.state == ReplayState::Finished => {
+                self.seek(0.0);
+                self.status.state = ReplayState::Playing;
+            }
+            Some(false) => self.status.state = ReplayState::Playing,
+            _ => {}
+        }
+    }
+
+    fn seek(&mut self, position: f64) {
+        self.status.position = position;
+        self.index = self.offsets.partition_point(|&offset| offset < position);
+    }
+
+    /// Memajukan posisi sebesar `elapsed` detik waktu nyata × speed; mengembalikan indeks item
+    /// yang jatuh tempo. Di akhir rekaman posisi kembali ke awal (loop) atau status menjadi selesai.
+    pub fn advance(&mut self, elapsed: f64) -> Range<usize> {
+        if self.status.state != ReplayState::Playing {
+            return 0..0;
+        }
+
+        self.status.position = (self.status.position + elapsed * self.status.speed).min(self.status.duration);
+        let end = self.offsets.partition_point(|&offset| offset <= self.status.position);
+        let due = self.index..end;
+        self.index = end;
+        if self.index >= self.offsets.len() {
+            if self.status.looping {
+                self.seek(0.0);
+            } else {
+                self.status.state = ReplayState::Finished;
+            }
+        }
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::recording_data::RecordingFormat;
+
+    fn control(offsets: Vec<f64>, looping: bool) -> PlaybackControl {
+        let status = ReplayStatus {
+            path: "test.nmea".to_string(),
+            format: RecordingFormat::Nmea,
+            message_count: 0,
+            duration: 0.0,
+            position: 0.0,
+            speed: 1.0,
+            looping,
+            state: ReplayState::Playing,
+        };
+        PlaybackControl::new(status, offsets)
+    }
+
+    #[test]
+    fn offsets_are_relative_to_first_item() {
+        let start = DateTime::parse_from_rfc3339("2024-05-01T23:59:59Z").unwrap().to_utc();
+        let times = [0, 500, 2_000].map(|ms| start + chrono::Duration::milliseconds(ms));
+        assert_eq!(offsets(times.into_iter()), [0.0, 0.5, 2.0]);
+    }
+
+    #[test]
+    fn advance_releases_due_items_and_finishes() {
+        let mut control = control(vec![0.0, 0.5, 1.0, 2.0], false);
+        assert_eq!((control.status.message_count, control.status.duration), (4, 2.0));
+        assert_eq!(control.advance(0.0), 0..1);
+        assert_eq!(control.advance(0.6), 1..2);
+        control.update(UpdateReplayRequest { speed: Some(2.0), ..Default::default() });
+        assert_eq!(control.advance(0.25), 2..3);
+        assert_eq!(control.advance(10.0), 3..4);
+        assert_eq!(control.status.state, ReplayState::Finished);
+        assert_eq!(control.advance(1.0), 0..0);
+
+        // Play setelah selesai memutar ulang dari awal
+        control.update(UpdateReplayRequest { paused: Some(false), ..Default::default() });
+        assert_eq!(control.advance(0.0), 0..1);
+    }
+
+    #[test]
+    fn looping_pause_and_seek() {
+        let mut control = control(vec![0.0, 1.0, 2.0], true);
+        assert_eq!(control.advance(2.0), 0..3);
+        assert_eq!((control.status.state, control.status.position), (ReplayState::Playing, 0.0));
+
+        control.update(UpdateReplayRequest { paused: Some(true), ..Default::default() });
+        assert_eq!(control.advance(5.0), 0..0);
+
+        control.update(UpdateReplayRequest { position: Some(1.5), paused: Some(false), ..Default::default() });
+        assert_eq!(control.advance(0.0), 2..2);
+        assert_eq!(control.advance(0.5), 2..3);
+
+        // Speed dibatasi ke MIN_SPEED..MAX_SPEED
+        control.update(UpdateReplayRequest { speed: Some(1e9), ..Default::default() });
+        assert_eq!(control.status.speed, MAX_SPEED);
+    }
+}
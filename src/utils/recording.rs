@@ -0,0 +1,288 @@
+use crate::data::recording_data::{RecordedMessage, RecordingFormat, RecordingStatus, StartRecordingRequest};
+use crate::utils::nmea;
+use bincode::Options;
+use chrono::Utc;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, ErrorKind, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+pub type SharedRecorder = Arc<Recorder>;
+
+const DEFAULT_DIRECTORY: &str = "recordings";
+/// Ukuran maksimal satu pesan bincode; prefix panjang yang rusak tidak boleh memicu alokasi raksasa
+const MAX_BINCODE_MESSAGE: u64 = 16 * 1024 * 1024;
+
+/// Opsi bincode untuk file rekaman: encoding fixint seperti `bincode::serialize`, dengan batas ukuran.
+fn bincode_options() -> impl bincode::Options {
+    bincode::options().with_fixint_encoding().allow_trailing_bytes().with_limit(MAX_BINCODE_MESSAGE)
+}
+
+struct ActiveRecording {
+    status: RecordingStatus,
+    writer: BufWriter<File>,
+}
+
+/// Path file rekaman/log dari request API, dibatasi di dalam `recordings/`. Path relatif terhadap
+/// direktori tersebut (awalan `recordings/` boleh ditulis); path absolut, `..`, dan symlink yang
+/// keluar dari direktori ditolak. Direktori induk dibuat jika belum ada.
+pub fn recording_path(path: &str) -> Result<PathBuf, String> {
+    let mut components = Path::new(path).components().peekable();
+    if components.peek() == Some(&Component::Normal(DEFAULT_DIRECTORY.as_ref())) {
+        components.next();
+    }
+    let mut relative = PathBuf::new();
+    for component in components {
+        match component {
+            Component::Normal(part) => relative.push(part),
+            Component::CurDir => {}
+            _ => return Err(format!("Invalid path {}: must be relative to {}/ without \"..\"", path, DEFAULT_DIRECTORY)),
+        }
+    }
+    if relative.as_os_str().is_empty() {
+        return Err(format!("Invalid path {}: missing file name", path));
+    }
+
+    let resolved = Path::new(DEFAULT_DIRECTORY).join(&relative);
+    let parent = resolved.parent().unwrap_or(Path::new(DEFAULT_DIRECTORY));
+    fs::create_dir_all(parent).map_err(|e| format!("Cannot create {}: {}", parent.display(), e))?;
+    let root = fs::canonicalize(DEFAULT_DIRECTORY).map_err(|e| format!("Cannot open {}: {}", DEFAULT_DIRECTORY, e))?;
+    // File yang sudah ada dicek langsung (symlink), file baru lewat direktori induknya
+    let target = match fs::canonicalize(&resolved) {
+        Ok(target) => target,
+        Err(_) => fs::canonicalize(parent).map_err(|e| format!("Cannot open {}: {}", parent.display(), e))?,
+    };
+    if !target.starts_with(&root) {
+        return Err(format!("Invalid path {}: outside {}/", path, DEFAULT_DIRECTORY));
+    }
+    Ok(resolved)
+}
+
+/// Perekam semua pesan yang dikirim publisher registry, dipakai bersama lewat `Outputs`.
+#[derive(Default)]
+pub struct Recorder {
+    active: Mutex<Option<ActiveRecording>>,
+}
+
+impl Recorder {
+    pub fn is_recording(&self) -> bool {
+        self.active.lock().unwrap().is_some()
+    }
+
+    pub fn status(&self) -> Option<RecordingStatus> {
+        self.active.lock().unwrap().as_ref().map(|active| active.status.clone())
+    }
+
+    /// Membuka file rekaman baru; rekaman yang sedang berjalan ditutup lebih dulu.
+    pub fn start(&self, req: StartRecordingRequest) -> Result<RecordingStatus, String> {
+        let started = Utc::now();
+        let path = req.path.unwrap_or_else(|| {
+            format!("recording-{}.{}", started.format("%Y%m%dT%H%M%SZ"), req.format.extension())
+        });
+        let path = recording_path(&path)?.display().to_string();
+        let file = File::create(&path).map_err(|e| format!("Cannot create {}: {}", path, e))?;
+
+        let status = RecordingStatus { path, format: req.format, started, message_count: 0 };
+        let previous = self.active.lock().unwrap().replace(ActiveRecording { status: status.clone(), writer: BufWriter::new(file) });
+        if let Some(previous) = previous {
+            finish(previous);
+        }
+        println!("[Recorder]: Recording {:?} to {}", status.format, status.path);
+        Ok(status)
+    }
+
+    /// Menutup rekaman; `None` jika tidak ada rekaman berjalan.
+    pub fn stop(&self) -> Option<RecordingStatus> {
+        let active = self.active.lock().unwrap().take()?;
+        Some(finish(active))
+    }
+
+    /// Menulis satu pesan; rekaman dihentikan jika penulisan gagal (mis. disk penuh).
+    pub fn record(&self, message: &RecordedMessage) {
+        let mut guard = self.active.lock().unwrap();
+        let Some(active) = guard.as_mut() else { return };
+        match write_message(&mut active.writer, active.status.format, message) {
+            Ok(()) => active.status.message_count += 1,
+            Err(e) => {
+                eprintln!("[Recorder]: Write error to {}: {}", active.status.path, e);
+                if let Some(active) = guard.take() {
+                    finish(active);
+                }
+            }
+        }
+    }
+}
+
+fn finish(mut active: ActiveRecording) -> RecordingStatus {
+    if let Err(e) = active.writer.flush() {
+        eprintln!("[Recorder]: Flush error to {}: {}", active.status.path, e);
+    }
+    println!("[Recorder]: Stopped {} ({} messages)", active.status.path, active.status.message_count);
+    active.status
+}
+
+fn write_message(writer: &mut BufWriter<File>, format: RecordingFormat, message: &RecordedMessage) -> Result<(), String> {
+    match format {
+        RecordingFormat::Nmea => {
+            let tag = nmea::tag_block(&message.time, &message.sensor);
+            for sentence in &message.nmea {
+                write!(writer, "{}{}\r\n", tag, sentence).map_err(|e| e.to_string())?;
+            }
+            Ok(())
+        }
+        RecordingFormat::Jsonl => {
+            serde_json::to_writer(&mut *writer, message).map_err(|e| e.to_string())?;
+            writer.write_all(b"\n").map_err(|e| e.to_string())
+        }
+        RecordingFormat::Bincode => bincode_options().serialize_into(writer, message).map_err(|e| e.to_string()),
+    }
+}
+
+/// Membaca seluruh file rekaman, diurutkan menurut waktu.
+pub fn read_recording(path: &str, format: RecordingFormat) -> Result<Vec<RecordedMessage>, String> {
+    let file = File::open(path).map_err(|e| format!("Cannot open {}: {}", path, e))?;
+    let mut reader = BufReader::new(file);
+    let mut messages = match format {
+        RecordingFormat::Nmea => read_nmea(reader)?,
+        RecordingFormat::Jsonl => reader
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !matches!(line, Ok(line) if line.trim().is_empty()))
+            .map(|(i, line)| {
+                let line = line.map_err(|e| e.to_string())?;
+                serde_json::from_str(&line).map_err(|e| format!("Line {}: {}", i + 1, e))
+            })
+            .collect::<Result<Vec<_>, String>>()?,
+        RecordingFormat::Bincode => {
+            let mut messages = Vec::new();
+            loop {
+                match bincode_options().deserialize_from::<_, RecordedMessage>(&mut reader) {
+                    Ok(message) => messages.push(message),
+                    Err(e) => match *e {
+                        bincode::ErrorKind::Io(ref io) if io.kind() == ErrorKind::UnexpectedEof => break,
+                        _ => return Err(format!("Message {}: {}", messages.len() + 1, e)),
+                    },
+                }
+            }
+            messages
+        }
+    };
+    if messages.is_empty() {
+        return Err("Recording contains no messages".to_string());
+    }
+    messages.sort_by_key(|message| message.time);
+    Ok(messages)
+}
+
+/// Log NMEA: kalimat berurutan dengan TAG block dan sumber yang sama digabung menjadi satu pesan.
+/// Baris tanpa TAG block memakai timestamp baris sebelumnya.
+fn read_nmea(reader: impl BufRead) -> Result<Vec<RecordedMessage>, String> {
+    let mut messages: Vec<RecordedMessage> = Vec::new();
+    for line in reader.lines() {
+        let line = line.map_err(|e| e.to_string())?;
+        let (time, source, sentence) = nmea::split_tag_block(&line);
+        if !(sentence.starts_with('$') || sentence.starts_with('!')) {
+            continue;
+        }
+
+        let last = messages.last_mut();
+        let time = time.or(last.as_ref().map(|m| m.time)).unwrap_or_else(Utc::now);
+        let sensor = source.unwrap_or_else(|| "nmea".to_string());
+        match last {
+            Some(last) if last.time == time && last.sensor == sensor => last.nmea.push(sentence.to_string()),
+            _ => messages.push(RecordedMessage {
+                time,
+                sensor,
+                mqtt: Vec::new(),
+                json: String::new(),
+                nmea: vec![sentence.to_string()],
+                n2k: Vec::new(),
+                signalk: None,
+            }),
+        }
+    }
+    Ok(messages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::DateTime;
+
+    #[test]
+    fn recording_path_stays_inside_recordings() {
+        assert_eq!(recording_path("test-path/a.jsonl").unwrap(), Path::new("recordings/test-path/a.jsonl"));
+        assert_eq!(recording_path("recordings/test-path/a.jsonl").unwrap(), Path::new("recordings/test-path/a.jsonl"));
+        for path in ["/etc/passwd", "../Cargo.toml", "test-path/../../Cargo.toml", "recordings", ""] {
+            assert!(recording_path(path).is_err(), "{}", path);
+        }
+        fs::remove_dir_all("recordings/test-path").unwrap();
+    }
+
+    fn message(sensor: &str) -> RecordedMessage {
+        RecordedMessage {
+            time: DateTime::from_timestamp_millis(1_714_564_800_100).unwrap(),
+            sensor: sensor.to_string(),
+            mqtt: Vec::new(),
+            json: "{}".to_string(),
+            nmea: vec!["$HEHDT,10.0,T*1D".to_string()],
+            n2k: Vec::new(),
+            signalk: None,
+        }
+    }
+
+    #[test]
+    fn bincode_round_trips_and_rejects_huge_length_prefix() {
+        // Rekaman lama (bincode::serialize) tetap terbaca
+        assert_eq!(bincode_options().serialize(&message("gps")).unwrap(), bincode::serialize(&message("gps")).unwrap());
+
+        let path = std::env::temp_dir().join(format!("vessel-test-{}.bin", std::process::id()));
+        let mut writer = BufWriter::new(File::create(&path).unwrap());
+        write_message(&mut writer, RecordingFormat::Bincode, &message("gyro")).unwrap();
+        write_message(&mut writer, RecordingFormat::Bincode, &message("gps")).unwrap();
+        drop(writer);
+        let messages = read_recording(path.to_str().unwrap(), RecordingFormat::Bincode).unwrap();
+        assert_eq!(messages.iter().map(|m| m.sensor.as_str()).collect::<Vec<_>>(), ["gyro", "gps"]);
+        assert_eq!(messages[0].time, message("gyro").time);
+
+        // Prefix panjang string pertama (fixint u64) yang mustahil
+        fs::write(&path, u64::MAX.to_le_bytes()).unwrap();
+        let result = read_recording(path.to_str().unwrap(), RecordingFormat::Bincode);
+        fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn nmea_and_jsonl_round_trip() {
+        for (format, extension) in [(RecordingFormat::Nmea, "nmea"), (RecordingFormat::Jsonl, "jsonl")] {
+            let path = std::env::temp_dir().join(format!("vessel-test-{}.{}", std::process::id(), extension));
+            let mut later = message("gps");
+            later.time += chrono::Duration::milliseconds(200);
+            later.nmea.push("$GPVTG,45.0,T,,M,5.0,N,9.3,K*4D".to_string());
+            let mut writer = BufWriter::new(File::create(&path).unwrap());
+            write_message(&mut writer, format, &later).unwrap();
+            write_message(&mut writer, format, &message("gyro")).unwrap();
+            drop(writer);
+
+            let messages = read_recording(path.to_str().unwrap(), format).unwrap();
+            fs::remove_file(&path).unwrap();
+            // Diurutkan menurut waktu; kalimat dengan TAG block sama digabung kembali
+            assert_eq!(messages.len(), 2, "{:?}", format);
+            assert_eq!((messages[0].sensor.as_str(), messages[0].time), ("gyro", message("gyro").time));
+            assert_eq!((messages[1].sensor.as_str(), messages[1].time), ("gps", later.time));
+            assert_eq!(messages[1].nmea, later.nmea);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn recording_path_rejects_symlink_escape() {
+        let link = Path::new(DEFAULT_DIRECTORY).join("test-escape");
+        fs::create_dir_all(DEFAULT_DIRECTORY).unwrap();
+        let _ = fs::remove_file(&link);
+        std::os::unix::fs::symlink(std::env::temp_dir(), &link).unwrap();
+        let result = recording_path("test-escape/a.jsonl");
+        fs::remove_file(&link).unwrap();
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,119 @@
+use crate::data::recording_data::{
+    RecordedMessage, RecordingFormat, ReplayState, ReplayStatus, StartReplayRequest, UpdateReplayRequest,
+};
+use crate::utils::mqtt_manager::{MqttManager, MqttState};
+use crate::utils::net::{OutputFrame, Outputs};
+use crate::utils::playback::{self, PlaybackControl};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+
+pub type SharedReplayer = Arc<Replayer>;
+
+struct ActiveReplay {
+    control: Arc<Mutex<PlaybackControl>>,
+    task: JoinHandle<()>,
+}
+
+/// Memutar ulang rekaman ke MQTT (broker milik sensor yang merekam), WebSocket, TCP, UDP, dan CAN
+/// dengan timing asli dikali `speed`. Selama replay dimuat, publikasi live berhenti.
+pub struct Replayer {
+    outputs: Outputs,
+    mqtt: HashMap<&'static str, Arc<MqttManager>>,
+    active: Mutex<Option<ActiveReplay>>,
+}
+
+impl Replayer {
+    pub fn new(outputs: Outputs, mqtt: HashMap<&'static str, Arc<MqttManager>>) -> Self {
+        Self { outputs, mqtt, active: Mutex::new(None) }
+    }
+
+    pub fn status(&self) -> Option<ReplayStatus> {
+        self.active.lock().unwrap().as_ref().map(|active| active.control.lock().unwrap().status.clone())
+    }
+
+    /// Memulai replay baru (replay lama dihentikan). `messages` harus terurut menurut waktu.
+    pub fn start(&self, req: StartReplayRequest, format: RecordingFormat, messages: Vec<RecordedMessage>) -> ReplayStatus {
+        let status = ReplayStatus {
+            path: req.path,
+            format,
+            message_count: 0,
+            duration: 0.0,
+            position: 0.0,
+            speed: req.speed,
+            looping: req.looping,
+            state: if req.paused { ReplayState::Paused } else { ReplayState::Playing },
+        };
+        let control = PlaybackControl::new(status, playback::offsets(messages.iter().map(|m| m.time)));
+        let status = control.status.clone();
+
+        let control = Arc::new(Mutex::new(control));
+        let task = tokio::spawn(run_replay(control.clone(), messages, self.outputs.clone(), self.mqtt.clone()));
+        if let Some(previous) = self.active.lock().unwrap().replace(ActiveReplay { control, task }) {
+            previous.task.abort();
+        }
+        self.outputs.set_replaying(true);
+        println!("[Replay]: Playing {} ({} messages, {:.1} s)", status.path, status.message_count, status.duration);
+        status
+    }
+
+    /// Mengubah speed/pause/loop atau seek; `None` jika tidak ada replay.
+    pub fn update(&self, patch: UpdateReplayRequest) -> Option<ReplayStatus> {
+        let guard = self.active.lock().unwrap();
+        let mut control = guard.as_ref()?.control.lock().unwrap();
+        control.update(patch);
+        Some(control.status.clone())
+    }
+
+    /// Menghentikan replay; publikasi live berjalan lagi.
+    pub fn stop(&self) -> bool {
+        let Some(active) = self.active.lock().unwrap().take() else { return false };
+        active.task.abort();
+        self.outputs.set_replaying(false);
+        println!("[Replay]: Stopped");
+        true
+    }
+}
+
+async fn run_replay(
+    control: Arc<Mutex<PlaybackControl>>,
+    messages: Vec<RecordedMessage>,
+    outputs: Outputs,
+    mqtt: HashMap<&'static str, Arc<MqttManager>>,
+) {
+    let mut last_tick = Instant::now();
+    loop {
+        sleep(playback::TICK).await;
+        let elapsed = last_tick.elapsed().as_secs_f64();
+        last_tick = Instant::now();
+
+        // Lock tidak ditahan selama pesan dikirim
+        let due = control.lock().unwrap().advance(elapsed);
+        for message in &messages[due] {
+            publish(message, &outputs, &mqtt).await;
+        }
+    }
+}
+
+async fn publish(message: &RecordedMessage, outputs: &Outputs, mqtt: &HashMap<&'static str, Arc<MqttManager>>) {
+    if let Some(manager) = mqtt.get(message.sensor.as_str()) {
+        if manager.state() != MqttState::Disconnected {
+            for msg in &message.mqtt {
+                if let Err(e) = manager.publish_message(std::slice::from_ref(&msg.topic), msg.payload.clone()).await {
+                    eprintln!("[Replay]: MQTT publish error to {}: {:?}", msg.topic, e);
+                }
+            }
+        }
+    }
+
+    outputs
+        .publish(OutputFrame {
+            nmea: message.nmea.clone(),
+            json: message.json.clone(),
+            n2k: message.n2k.clone(),
+            signalk: message.signalk.as_deref().and_then(|delta| serde_json::from_str(delta).ok()),
+        })
+        .await;
+}
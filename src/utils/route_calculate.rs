@@ -0,0 +1,177 @@
+use crate::data::route_data::{RoutePoint, RouteState};
+use crate::data::vessel_data::Steering;
+use crate::services::gps_service::Gps;
+use crate::services::sensor::SensorContext;
+use crate::utils::navigation::{self, angle_diff};
+use crate::utils::sim_clock;
+
+/// Jarak pandang ke depan untuk koreksi XTE (NM): makin kecil, makin tajam kembali ke track
+const LOOKAHEAD_NM: f64 = 0.25;
+/// Penguatan autopilot: laju belok (derajat/detik) per derajat selisih haluan
+const STEERING_GAIN: f64 = 0.5;
+
+/// Posisi, SOG, dan COG kapal dari model kapal, atau dari gerak sebenarnya GPS (tanpa error GNSS)
+/// jika model belum ada.
+fn own_ship(ctx: &SensorContext) -> Option<(f64, f64, f64, f64)> {
+    match ctx.vessel_state() {
+        Some(vessel) => Some((vessel.latitude, vessel.longitude, vessel.sog, vessel.cog)),
+        None => ctx.get::<Gps>().map(|gps| (gps.truth.latitude, gps.truth.longitude, gps.truth.sog, gps.truth.cog)),
+    }
+}
+
+/// Mengemudikan model kapal (laju belok + STW) atau, tanpa model kapal, langsung COG/SOG GPS.
+fn steer(ctx: &SensorContext, desired_course: f64, speed: f64, turn_rate: f64, dt_seconds: f64) {
+    let vessel = ctx.vessel();
+    let mut guard = vessel.write().unwrap();
+    if let Some(vessel) = guard.as_mut() {
+        let error = angle_diff(desired_course, vessel.cog);
+        vessel.steering = Steering::TurnRate { rate: (error * STEERING_GAIN).clamp(-turn_rate, turn_rate) };
+        vessel.stw = speed;
+        return;
+    }
+    drop(guard);
+
+    let Some(gps) = ctx.shared::<Gps>() else { return };
+    let mut guard = gps.write().unwrap();
+    if let Some(gps) = guard.as_mut() {
+        // Mengubah gerak sebenarnya; error GNSS ditambahkan GPS pada langkahnya sendiri
+        let truth = &mut gps.truth;
+        let max_change = turn_rate * dt_seconds;
+        let error = angle_diff(desired_course, truth.cog);
+        truth.cog = (truth.cog + error.clamp(-max_change, max_change)).rem_euclid(360.0);
+        truth.sog = speed;
+    }
+}
+
+/// Menghentikan belokan saat rute selesai; haluan dan kecepatan terakhir dipertahankan.
+fn stop_turning(ctx: &SensorContext) {
+    let vessel = ctx.vessel();
+    let mut guard = vessel.write().unwrap();
+    if let Some(vessel) = guard.as_mut() {
+        vessel.steering = Steering::TurnRate { rate: 0.0 };
+    }
+}
+
+/// Pindah ke waypoint berikutnya; waypoint yang dicapai menjadi awal leg baru.
+fn advance(state: &mut RouteState) {
+    let reached = &state.waypoints[state.active_index];
+    state.leg_start = Some(RoutePoint {
+        name: reached.name.clone(),
+        latitude: reached.latitude,
+        longitude: reached.longitude,
+    });
+    state.active_index += 1;
+    if state.active_index >= state.waypoints.len() {
+        if state.looping {
+            state.active_index = 0;
+        } else {
+            state.active_index = state.waypoints.len() - 1;
+            state.completed = true;
+        }
+    }
+}
+
+/// Satu langkah autopilot: hitung progres leg aktif, ganti waypoint saat tiba, lalu kemudikan kapal.
+pub fn calculate_next_route_state(state: &mut RouteState, ctx: &SensorContext) {
+    let Some((lat, lon, sog, cog)) = own_ship(ctx) else { return };
+    state.last_update = sim_clock::now();
+    let Some(waypoint) = state.active_waypoint().cloned() else { return };
+
+    let start = state.leg_start.get_or_insert_with(|| RoutePoint {
+        name: "ORIGIN".to_string(),
+        latitude: lat,
+        longitude: lon,
+    });
+    let geometry = navigation::leg_geometry(
+        waypoint.leg,
+        (start.latitude, start.longitude),
+        (waypoint.latitude, waypoint.longitude),
+        (lat, lon),
+    );
+
+    state.xte = geometry.cross_track;
+    state.dtw = geometry.distance_to_waypoint;
+    state.btw = geometry.bearing_to_waypoint;
+    state.bod = geometry.leg_bearing;
+    state.vmg = sog * angle_diff(cog, geometry.bearing_to_waypoint).to_radians().cos();
+    state.arrival_circle_entered = geometry.distance_to_waypoint <= state.arrival_radius;
+    state.perpendicular_passed = geometry.along_track >= geometry.leg_length;
+
+    if state.arrival_circle_entered || state.perpendicular_passed {
+        advance(state);
+        if state.completed {
+            stop_turning(ctx);
+        }
+        return;
+    }
+
+    let intercept = (geometry.cross_track / LOOKAHEAD_NM).atan().to_degrees();
+    state.desired_course = (geometry.track_course - intercept).rem_euclid(360.0);
+    steer(ctx, state.desired_course, waypoint.speed, state.turn_rate, state.calculation_rate_ms as f64 / 1000.0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::gps_data::GpsState;
+    use crate::services::route_service::Route;
+    use crate::services::sensor::Sensor;
+    use std::sync::{Arc, RwLock};
+
+    /// GPS tanpa model kapal di (50, 10) menuju timur 6 knot; posisi terukur digeser 0.5° ke utara
+    /// seperti lompatan multipath.
+    fn context() -> (SensorContext, Arc<RwLock<Option<GpsState>>>) {
+        let req = serde_json::from_value(serde_json::json!({
+            "latitude": 50.0, "longitude": 10.0, "sog": 6.0, "cog": 90.0, "is_running": true
+        }))
+        .unwrap();
+        let mut gps = Gps::create(req);
+        gps.latitude += 0.5;
+        let shared = Arc::new(RwLock::new(Some(gps)));
+        let mut ctx = SensorContext::default();
+        ctx.insert::<Gps>(shared.clone());
+        (ctx, shared)
+    }
+
+    fn route(waypoints: &[(f64, f64)]) -> RouteState {
+        let waypoints: Vec<_> = waypoints
+            .iter()
+            .map(|(lat, lon)| serde_json::json!({ "latitude": lat, "longitude": lon, "speed": 8.0 }))
+            .collect();
+        let mut state = Route::create(
+            serde_json::from_value(serde_json::json!({ "waypoints": waypoints, "is_running": true })).unwrap(),
+        );
+        state.calculation_rate_ms = 1000;
+        state
+    }
+
+    #[test]
+    fn follows_true_position_without_vessel_model() {
+        let (ctx, gps) = context();
+        // Waypoint 0.2° utara: posisi terukur (50.5) sudah melewatinya, posisi sebenarnya belum
+        let mut state = route(&[(50.2, 10.0)]);
+        calculate_next_route_state(&mut state, &ctx);
+
+        assert!(!state.completed);
+        assert!((state.dtw - 12.0).abs() < 0.1, "dtw {}", state.dtw);
+        assert!(state.btw < 1.0 || state.btw > 359.0, "btw {}", state.btw);
+        let gps = gps.read().unwrap().clone().unwrap();
+        // Belok dari 90° ke arah utara, dibatasi turn_rate × dt
+        assert_eq!(gps.truth.cog, 89.0);
+        assert_eq!(gps.truth.sog, 8.0);
+    }
+
+    #[test]
+    fn arrival_advances_to_next_waypoint() {
+        let (ctx, _) = context();
+        let mut state = route(&[(50.0, 10.001), (50.0, 11.0)]);
+        calculate_next_route_state(&mut state, &ctx);
+        assert_eq!(state.active_index, 1);
+        assert_eq!(state.leg_start.as_ref().map(|start| start.longitude), Some(10.001));
+
+        let mut state = route(&[(50.0, 10.001)]);
+        calculate_next_route_state(&mut state, &ctx);
+        assert!(state.completed);
+        assert!(state.active_waypoint().is_none());
+    }
+}
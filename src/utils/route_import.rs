@@ -0,0 +1,281 @@
+use crate::data::route_data::{ImportRouteQuery, LegType, RouteFormat, Waypoint};
+use crate::services::route_service::MAX_LEG_SPEED;
+use crate::utils::navigation;
+use chrono::{DateTime, Utc};
+use roxmltree::{Document, Node};
+
+/// Titik mentah hasil parsing sebelum diubah menjadi waypoint.
+struct ImportedPoint {
+    name: String,
+    latitude: f64,
+    longitude: f64,
+    time: Option<DateTime<Utc>>,
+    speed: Option<f64>,
+    leg: Option<LegType>,
+}
+
+impl ImportedPoint {
+    fn new(latitude: f64, longitude: f64) -> Self {
+        Self { name: String::new(), latitude, longitude, time: None, speed: None, leg: None }
+    }
+}
+
+/// Format dari elemen root: `<gpx>`, `<kml>`, atau `<route>` (RTZ).
+fn detect_format(doc: &Document) -> Result<RouteFormat, String> {
+    match doc.root_element().tag_name().name() {
+        "gpx" => Ok(RouteFormat::Gpx),
+        "kml" => Ok(RouteFormat::Kml),
+        "route" => Ok(RouteFormat::Rtz),
+        other => Err(format!("Unknown route file root element <{}>", other)),
+    }
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|n| n.tag_name().name() == name)
+}
+
+fn child_text(node: Node, name: &str) -> Option<String> {
+    child(node, name).and_then(|n| n.text()).map(|t| t.trim().to_string())
+}
+
+fn attribute_f64(node: Node, name: &str) -> Option<f64> {
+    node.attribute(name).and_then(|v| v.trim().parse().ok()).filter(|v: &f64| v.is_finite())
+}
+
+/// Lintang -90..90 dan bujur -180..180; NaN/inf atau di luar rentang = `None`.
+fn valid_position(lat: f64, lon: f64) -> Option<(f64, f64)> {
+    ((-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lon)).then_some((lat, lon))
+}
+
+fn position(node: Node) -> Result<(f64, f64), String> {
+    match (attribute_f64(node, "lat"), attribute_f64(node, "lon")) {
+        (Some(lat), Some(lon)) => valid_position(lat, lon)
+            .ok_or_else(|| format!("<{}> position out of range: {}, {}", node.tag_name().name(), lat, lon)),
+        _ => Err(format!("<{}> without valid lat/lon", node.tag_name().name())),
+    }
+}
+
+/// GPX `rte/rtept` atau `trk/trkseg/trkpt` (semua segmen digabung).
+fn parse_gpx(doc: &Document, track: bool) -> Result<Vec<ImportedPoint>, String> {
+    let has_route = doc.descendants().any(|n| n.tag_name().name() == "rtept");
+    let point_tag = if track || !has_route { "trkpt" } else { "rtept" };
+
+    doc.descendants()
+        .filter(|n| n.tag_name().name() == point_tag)
+        .map(|n| {
+            let (lat, lon) = position(n)?;
+            let mut point = ImportedPoint::new(lat, lon);
+            point.name = child_text(n, "name").unwrap_or_default();
+            point.time = child_text(n, "time")
+                .and_then(|t| DateTime::parse_from_rfc3339(&t).ok())
+                .map(|t| t.with_timezone(&Utc));
+            Ok(point)
+        })
+        .collect()
+}
+
+/// KML: koordinat `lon,lat[,alt]` dari setiap `<LineString>`, diurutkan sesuai dokumen.
+fn parse_kml(doc: &Document) -> Result<Vec<ImportedPoint>, String> {
+    let mut points = Vec::new();
+    for line in doc.descendants().filter(|n| n.tag_name().name() == "LineString") {
+        let coordinates = child_text(line, "coordinates").unwrap_or_default();
+        for tuple in coordinates.split_whitespace() {
+            let mut values = tuple.split(',').map(|v| v.parse::<f64>());
+            match (values.next(), values.next()) {
+                (Some(Ok(lon)), Some(Ok(lat))) if valid_position(lat, lon).is_some() => {
+                    points.push(ImportedPoint::new(lat, lon))
+                }
+                _ => return Err(format!("Invalid KML coordinate '{}'", tuple)),
+            }
+        }
+    }
+    Ok(points)
+}
+
+/// Atribut `<leg>` RTZ: kecepatan rencana (knot) dan jenis geometri.
+fn rtz_leg(leg: Option<Node>) -> (Option<f64>, Option<LegType>) {
+    let Some(leg) = leg else { return (None, None) };
+    let speed = attribute_f64(leg, "planSpeedMax").or_else(|| attribute_f64(leg, "planSpeedMin"));
+    let geometry = match leg.attribute("geometryType") {
+        Some("Loxodrome") => Some(LegType::RhumbLine),
+        Some("Orthodrome") => Some(LegType::GreatCircle),
+        _ => None,
+    };
+    (speed, geometry)
+}
+
+/// RTZ (IEC 61174): `<waypoints>/<waypoint>` dengan `<position>` dan `<leg>` menuju waypoint tsb;
+/// `<defaultWaypoint>` menjadi nilai default leg.
+fn parse_rtz(doc: &Document) -> Result<Vec<ImportedPoint>, String> {
+    let waypoints = doc
+        .descendants()
+        .find(|n| n.tag_name().name() == "waypoints")
+        .ok_or("RTZ file has no <waypoints>")?;
+    let (default_speed, default_leg) = rtz_leg(child(waypoints, "defaultWaypoint").and_then(|d| child(d, "leg")));
+
+    waypoints
+        .children()
+        .filter(|n| n.tag_name().name() == "waypoint")
+        .map(|n| {
+            let (lat, lon) = position(child(n, "position").ok_or("RTZ waypoint without <position>")?)?;
+            let (speed, leg) = rtz_leg(child(n, "leg"));
+            let mut point = ImportedPoint::new(lat, lon);
+            point.name = n.attribute("name").unwrap_or_default().trim().to_string();
+            point.speed = speed.or(default_speed);
+            point.leg = leg.or(default_leg);
+            Ok(point)
+        })
+        .collect()
+}
+
+/// Kecepatan rata-rata (knot) antar dua titik bertimestamp, dibatasi `MAX_LEG_SPEED`;
+/// `None` jika waktu tidak valid.
+fn timestamp_speed(from: &ImportedPoint, to: &ImportedPoint) -> Option<f64> {
+    let hours = (to.time? - from.time?).num_milliseconds() as f64 / 3_600_000.0;
+    if hours <= 0.0 {
+        return None;
+    }
+    let (distance, _) = navigation::great_circle(from.latitude, from.longitude, to.latitude, to.longitude);
+    Some((distance / hours).min(MAX_LEG_SPEED))
+}
+
+/// Panjang leg minimum saat memutar ulang track bertimestamp, dalam kelipatan radius kedatangan
+const MIN_LEG_RADII: f64 = 4.0;
+
+/// Membuang titik track yang terlalu rapat: titik yang lebih dekat dari `min_leg` (NM) ke titik
+/// terakhir yang dipakai akan langsung "tiba" di lingkaran kedatangan. Titik pertama dan terakhir
+/// selalu dipakai; kecepatan leg dihitung dari timestamp titik yang tersisa, jadi kecepatan rata-rata tetap sama.
+fn decimate(points: Vec<ImportedPoint>, min_leg: f64) -> Vec<ImportedPoint> {
+    let last = points.len().saturating_sub(1);
+    let mut kept: Vec<ImportedPoint> = Vec::new();
+    for (i, point) in points.into_iter().enumerate() {
+        let far_enough = kept.last().is_none_or(|previous| {
+            navigation::great_circle(previous.latitude, previous.longitude, point.latitude, point.longitude).0 >= min_leg
+        });
+        if far_enough {
+            kept.push(point);
+        } else if i == last && kept.len() > 1 {
+            // Titik akhir menggantikan titik terakhir yang dipakai agar rute tetap berakhir di ujung track
+            *kept.last_mut().unwrap() = point;
+        } else if i == last {
+            // Track pendek: titik awal tetap dipakai, rute minimal satu leg
+            kept.push(point);
+        }
+    }
+    kept
+}
+
+/// Mem-parsing file rute/track menjadi waypoint. Kecepatan leg: dari file (RTZ),
+/// dari timestamp (GPX track + `use_timestamps`, titik rapat dibuang), atau `query.speed`.
+pub fn parse_route(text: &str, query: &ImportRouteQuery) -> Result<Vec<Waypoint>, String> {
+    let doc = Document::parse(text).map_err(|e| format!("Invalid XML: {}", e))?;
+    let format = match query.format {
+        Some(format) => format,
+        None => detect_format(&doc)?,
+    };
+    let points = match format {
+        RouteFormat::Gpx => parse_gpx(&doc, query.track)?,
+        RouteFormat::Kml => parse_kml(&doc)?,
+        RouteFormat::Rtz => parse_rtz(&doc)?,
+    };
+    if points.is_empty() {
+        return Err("Route file contains no points".to_string());
+    }
+
+    let points = if query.use_timestamps { decimate(points, query.arrival_radius * MIN_LEG_RADII) } else { points };
+
+    // Nama dibersihkan untuk NMEA saat waypoint dipasang ke rute (`Route::update`)
+    let waypoints = points
+        .iter()
+        .enumerate()
+        .map(|(i, point)| {
+            let replayed = if query.use_timestamps && i > 0 { timestamp_speed(&points[i - 1], point) } else { None };
+            Waypoint {
+                name: point.name.clone(),
+                latitude: point.latitude,
+                longitude: point.longitude,
+                speed: replayed.or(point.speed).unwrap_or(query.speed),
+                leg: query.leg.or(point.leg).unwrap_or_default(),
+            }
+        })
+        .collect();
+    Ok(waypoints)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(use_timestamps: bool) -> ImportRouteQuery {
+        serde_json::from_value(serde_json::json!({ "use_timestamps": use_timestamps, "speed": 6.0 })).unwrap()
+    }
+
+    fn gpx_track(points: &[(&str, &str, &str)]) -> String {
+        let points: String = points
+            .iter()
+            .map(|(lat, lon, time)| format!(r#"<trkpt lat="{}" lon="{}"><time>{}</time></trkpt>"#, lat, lon, time))
+            .collect();
+        format!(r#"<gpx version="1.1"><trk><trkseg>{}</trkseg></trk></gpx>"#, points)
+    }
+
+    #[test]
+    fn rejects_non_finite_and_out_of_range_positions() {
+        for (lat, lon) in [("NaN", "10.0"), ("50.0", "inf"), ("91.0", "10.0"), ("50.0", "180.5")] {
+            let gpx = gpx_track(&[(lat, lon, "2024-05-01T12:00:00Z")]);
+            assert!(parse_route(&gpx, &query(false)).is_err(), "{} {}", lat, lon);
+        }
+        let kml = r#"<kml><Placemark><LineString><coordinates>10.0,50.0 NaN,50.1</coordinates></LineString></Placemark></kml>"#;
+        assert!(parse_route(kml, &query(false)).is_err());
+        let kml = r#"<kml><Placemark><LineString><coordinates>10.0,50.0,0 10.1,50.1,0</coordinates></LineString></Placemark></kml>"#;
+        assert_eq!(parse_route(kml, &query(false)).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn timestamp_speed_is_capped() {
+        // 1 NM dalam 1 ms
+        let gpx = gpx_track(&[
+            ("50.0", "10.0", "2024-05-01T12:00:00.000Z"),
+            ("50.016667", "10.0", "2024-05-01T12:00:00.001Z"),
+            ("50.033333", "10.0", "2024-05-01T12:10:00.001Z"),
+        ]);
+        let waypoints = parse_route(&gpx, &query(true)).unwrap();
+        assert_eq!(waypoints.len(), 3);
+        assert_eq!(waypoints[1].speed, MAX_LEG_SPEED);
+        assert!((waypoints[2].speed - 6.0).abs() < 0.01, "{}", waypoints[2].speed);
+        // Titik pertama tanpa leg sebelumnya memakai kecepatan default
+        assert_eq!(waypoints[0].speed, 6.0);
+    }
+
+    #[test]
+    fn decimation_keeps_both_ends_of_a_short_track() {
+        // Semua titik lebih dekat dari 4 × radius kedatangan (0.4 NM)
+        let gpx = gpx_track(&[
+            ("50.0", "10.0", "2024-05-01T12:00:00Z"),
+            ("50.001", "10.0", "2024-05-01T12:00:10Z"),
+            ("50.002", "10.0", "2024-05-01T12:00:20Z"),
+        ]);
+        let waypoints = parse_route(&gpx, &query(true)).unwrap();
+        assert_eq!(waypoints.iter().map(|w| w.latitude).collect::<Vec<_>>(), [50.0, 50.002]);
+
+        // Titik akhir yang rapat menggantikan titik terakhir yang dipakai
+        let gpx = gpx_track(&[
+            ("50.0", "10.0", "2024-05-01T12:00:00Z"),
+            ("50.01", "10.0", "2024-05-01T12:05:00Z"),
+            ("50.011", "10.0", "2024-05-01T12:05:30Z"),
+        ]);
+        let waypoints = parse_route(&gpx, &query(true)).unwrap();
+        assert_eq!(waypoints.iter().map(|w| w.latitude).collect::<Vec<_>>(), [50.0, 50.011]);
+    }
+
+    #[test]
+    fn rtz_legs_use_plan_speed_and_geometry() {
+        let rtz = r#"<route><waypoints>
+            <defaultWaypoint><leg planSpeedMax="8.0" geometryType="Loxodrome"/></defaultWaypoint>
+            <waypoint name="A"><position lat="50.0" lon="10.0"/></waypoint>
+            <waypoint name="B"><position lat="50.1" lon="10.1"/><leg planSpeedMax="NaN" geometryType="Orthodrome"/></waypoint>
+        </waypoints></route>"#;
+        let waypoints = parse_route(rtz, &query(false)).unwrap();
+        assert_eq!((waypoints[0].speed, waypoints[0].leg), (8.0, LegType::RhumbLine));
+        assert_eq!((waypoints[1].speed, waypoints[1].leg), (8.0, LegType::GreatCircle));
+    }
+}
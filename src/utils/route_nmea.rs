@@ -0,0 +1,126 @@
+use crate::data::route_data::{RouteSentence, RouteState, Waypoint};
+use crate::utils::nmea::{self, format_angle, format_latitude, format_longitude, format_time};
+
+/// Menghasilkan kalimat NMEA untuk setiap jenis di `sentences`, sesuai urutannya.
+/// Tidak ada kalimat jika rute sudah selesai atau belum punya leg aktif.
+/// `great_circle` = (jarak NM, bearing) great circle ke waypoint untuk BWC, default nilai leg;
+/// `variation` dari GPS untuk bearing magnetik BWC (kosong jika tidak ada).
+pub fn encode_route_sentences(
+    state: &RouteState,
+    talker: &str,
+    sentences: &[RouteSentence],
+    great_circle: Option<(f64, f64)>,
+    variation: Option<f64>,
+) -> Vec<String> {
+    let Some(waypoint) = state.active_waypoint() else { return Vec::new() };
+    if state.leg_start.is_none() {
+        return Vec::new();
+    }
+    let (distance, bearing) = great_circle.unwrap_or((state.dtw, state.btw));
+
+    sentences
+        .iter()
+        .map(|kind| match kind {
+            RouteSentence::Rmb => encode_rmb(state, waypoint, talker),
+            RouteSentence::Apb => encode_apb(state, waypoint, talker),
+            RouteSentence::Xte => encode_xte(state, talker),
+            RouteSentence::Bwc => encode_bwc(state, waypoint, talker, distance, bearing, variation),
+        })
+        .collect()
+}
+
+/// Status A/V dari boolean.
+fn status(flag: bool) -> String {
+    if flag { "A" } else { "V" }.to_string()
+}
+
+/// Besar XTE (NM) dan arah kemudi: kapal di kanan track → kemudi ke kiri (L).
+fn xte_fields(xte: f64) -> [String; 2] {
+    let steer = if xte > 0.0 { "L" } else { "R" };
+    [format!("{:.2}", xte.abs()), steer.to_string()]
+}
+
+/// RMB — Recommended Minimum Navigation Information
+pub fn encode_rmb(state: &RouteState, waypoint: &Waypoint, talker: &str) -> String {
+    let [xte, steer] = xte_fields(state.xte);
+    let [lat, ns] = format_latitude(waypoint.latitude);
+    let [lon, ew] = format_longitude(waypoint.longitude);
+    let origin = state.leg_start.as_ref().map(|p| p.name.clone()).unwrap_or_default();
+    let fields = [
+        "A".to_string(),
+        xte,
+        steer,
+        origin,
+        waypoint.name.clone(),
+        lat,
+        ns,
+        lon,
+        ew,
+        format!("{:.2}", state.dtw.min(999.99)),
+        format_angle(state.btw),
+        format!("{:.1}", state.vmg),
+        status(state.arrival_circle_entered),
+        "A".to_string(),
+    ];
+    nmea::sentence(talker, "RMB", &fields)
+}
+
+/// APB — Heading/Track Controller (Autopilot) Sentence "B", bearing true
+pub fn encode_apb(state: &RouteState, waypoint: &Waypoint, talker: &str) -> String {
+    let [xte, steer] = xte_fields(state.xte);
+    let fields = [
+        "A".to_string(),
+        "A".to_string(),
+        xte,
+        steer,
+        "N".to_string(),
+        status(state.arrival_circle_entered),
+        status(state.perpendicular_passed),
+        format_angle(state.bod),
+        "T".to_string(),
+        waypoint.name.clone(),
+        format_angle(state.btw),
+        "T".to_string(),
+        format_angle(state.desired_course),
+        "T".to_string(),
+        "A".to_string(),
+    ];
+    nmea::sentence(talker, "APB", &fields)
+}
+
+/// XTE — Cross-Track Error, Measured
+pub fn encode_xte(state: &RouteState, talker: &str) -> String {
+    let [xte, steer] = xte_fields(state.xte);
+    let fields = ["A".to_string(), "A".to_string(), xte, steer, "N".to_string(), "A".to_string()];
+    nmea::sentence(talker, "XTE", &fields)
+}
+
+/// BWC — Bearing & Distance to Waypoint (great circle)
+pub fn encode_bwc(
+    state: &RouteState,
+    waypoint: &Waypoint,
+    talker: &str,
+    distance: f64,
+    bearing: f64,
+    variation: Option<f64>,
+) -> String {
+    let [lat, ns] = format_latitude(waypoint.latitude);
+    let [lon, ew] = format_longitude(waypoint.longitude);
+    let magnetic = variation.map(|var| format_angle(bearing - var)).unwrap_or_default();
+    let fields = [
+        format_time(&state.last_update),
+        lat,
+        ns,
+        lon,
+        ew,
+        format_angle(bearing),
+        "T".to_string(),
+        magnetic,
+        "M".to_string(),
+        format!("{:.2}", distance),
+        "N".to_string(),
+        waypoint.name.clone(),
+        "A".to_string(),
+    ];
+    nmea::sentence(talker, "BWC", &fields)
+}
@@ -0,0 +1,466 @@
+use crate::data::anemo_data::AnemoState;
+use crate::data::baro_data::BaroState;
+use crate::data::depth_data::DepthState;
+use crate::data::gps_data::{FixQuality, GpsState};
+use crate::data::gyro_data::GyroState;
+use crate::data::route_data::{LegType, RouteState};
+use crate::data::speedlog_data::SpeedLogState;
+use crate::data::thermal_data::{ThermalKind, ThermalState};
+use crate::utils::net::{Clients, WsClient};
+use crate::utils::sim_clock;
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+use tokio_tungstenite::{tungstenite::Message, WebSocketStream};
+
+pub const SIGNALK_VERSION: &str = "1.7.0";
+pub const SIGNALK_STREAM_PATH: &str = "/signalk/v1/stream";
+/// Identitas kapal simulasi dalam model Signal K
+pub const SELF_URN: &str = "urn:mrn:signalk:uuid:6b0e776f-811a-4b35-980e-b93405371bc5";
+
+const KNOTS_TO_MPS: f64 = 0.514444;
+
+// === DELTA MODEL ===
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Delta {
+    pub context: String,
+    pub updates: Vec<DeltaUpdate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeltaUpdate {
+    #[serde(rename = "$source")]
+    pub source: String,
+    pub timestamp: String,
+    pub values: Vec<PathValue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathValue {
+    pub path: String,
+    pub value: serde_json::Value,
+}
+
+fn self_context() -> String {
+    format!("vessels.{}", SELF_URN)
+}
+
+fn delta(source: &str, timestamp: &DateTime<Utc>, values: Vec<(&str, serde_json::Value)>) -> Delta {
+    Delta {
+        context: self_context(),
+        updates: vec![DeltaUpdate {
+            source: source.to_string(),
+            timestamp: timestamp.to_rfc3339_opts(SecondsFormat::Millis, true),
+            values: values
+                .into_iter()
+                .map(|(path, value)| PathValue { path: path.to_string(), value })
+                .collect(),
+        }],
+    }
+}
+
+/// Delta GPS dalam satuan SI (radian, m/s).
+/// Tanpa fix, posisi/SOG/COG tidak dikirim.
+pub fn gps_delta(state: &GpsState) -> Delta {
+    let fix = state.fix();
+    let method = match fix {
+        FixQuality::NoFix => "no GPS",
+        FixQuality::Fix2d | FixQuality::Fix3d => "GNSS Fix",
+        FixQuality::Dgps => "DGNSS fix",
+        FixQuality::Rtk => "RTK fixed integer",
+    };
+    let mut values = Vec::new();
+    if fix.has_fix() {
+        values.extend([
+            ("navigation.position", json!({ "latitude": state.latitude, "longitude": state.longitude })),
+            ("navigation.speedOverGround", json!(state.sog * KNOTS_TO_MPS)),
+            ("navigation.courseOverGroundTrue", json!(state.cog.rem_euclid(360.0).to_radians())),
+            ("navigation.gnss.horizontalDilution", json!(state.hdop)),
+        ]);
+    }
+    values.push(("navigation.magneticVariation", json!(state.variation.to_radians())));
+    values.push(("navigation.gnss.methodQuality", json!(method)));
+    values.push(("navigation.gnss.satellites", json!(state.satellites_used())));
+    delta("vessel-simulator.gps", &state.last_update, values)
+}
+
+/// Delta gyro dalam satuan SI (radian, rad/s).
+pub fn gyro_delta(state: &GyroState) -> Delta {
+    delta(
+        "vessel-simulator.gyro",
+        &state.last_update,
+        vec![
+            ("navigation.headingTrue", json!(state.yaw.rem_euclid(360.0).to_radians())),
+            (
+                "navigation.attitude",
+                json!({
+                    "roll": state.roll.to_radians(),
+                    "pitch": state.pitch.to_radians(),
+                    "yaw": state.yaw.rem_euclid(360.0).to_radians(),
+                }),
+            ),
+            ("navigation.rateOfTurn", json!(state.yaw_rate.to_radians())),
+        ],
+    )
+}
+
+/// Sudut relatif haluan -π..π (negatif = port)
+fn relative_angle(degrees: f64) -> f64 {
+    ((degrees + 180.0).rem_euclid(360.0) - 180.0).to_radians()
+}
+
+/// Delta angin dalam satuan SI (radian, m/s).
+pub fn anemo_delta(state: &AnemoState) -> Delta {
+    delta(
+        "vessel-simulator.anemo",
+        &state.last_update,
+        vec![
+            ("environment.wind.speedApparent", json!(state.apparent_wind_speed * KNOTS_TO_MPS)),
+            ("environment.wind.angleApparent", json!(relative_angle(state.apparent_wind_angle))),
+            ("environment.wind.speedOverGround", json!(state.wind_speed * KNOTS_TO_MPS)),
+            ("environment.wind.angleTrueGround", json!(relative_angle(state.true_wind_angle))),
+            ("environment.wind.directionTrue", json!(state.wind_direction.rem_euclid(360.0).to_radians())),
+        ],
+    )
+}
+
+/// Delta barometer dalam satuan SI (Pa, Kelvin, rasio 0..1).
+pub fn baro_delta(state: &BaroState) -> Delta {
+    delta(
+        "vessel-simulator.baro",
+        &state.last_update,
+        vec![
+            ("environment.outside.pressure", json!(state.pressure * 100.0)),
+            ("environment.outside.temperature", json!(state.air_temperature + 273.15)),
+            ("environment.outside.relativeHumidity", json!(state.relative_humidity / 100.0)),
+            ("environment.outside.dewPointTemperature", json!(state.dew_point + 273.15)),
+        ],
+    )
+}
+
+/// Delta kedalaman dalam meter; kedalaman null saat tidak ada echo.
+pub fn depth_delta(state: &DepthState) -> Delta {
+    delta(
+        "vessel-simulator.depth",
+        &state.last_update,
+        vec![
+            ("environment.depth.belowTransducer", json!(state.depth_below_transducer)),
+            ("environment.depth.belowSurface", json!(state.depth_below_surface)),
+            ("environment.depth.belowKeel", json!(state.depth_below_keel)),
+            ("environment.depth.surfaceToTransducer", json!(state.transducer_offset)),
+            ("environment.depth.transducerToKeel", json!(state.keel_offset)),
+        ],
+    )
+}
+
+/// Delta speed log dan arus dalam satuan SI (m/s, radian).
+pub fn speedlog_delta(state: &SpeedLogState) -> Delta {
+    delta(
+        "vessel-simulator.speedlog",
+        &state.last_update,
+        vec![
+            ("navigation.speedThroughWater", json!(state.stw * KNOTS_TO_MPS)),
+            ("navigation.speedThroughWaterTransverse", json!(state.transverse_water_speed * KNOTS_TO_MPS)),
+            ("navigation.leewayAngle", json!(state.leeway.to_radians())),
+            (
+                "environment.current",
+                json!({
+                    "drift": state.current_drift * KNOTS_TO_MPS,
+                    "setTrue": state.current_set.rem_euclid(360.0).to_radians(),
+                }),
+            ),
+        ],
+    )
+}
+
+/// Delta progres rute (m, m/s, radian) di `courseGreatCircle` atau `courseRhumbline` sesuai leg aktif;
+/// `None` jika rute sudah selesai.
+pub fn route_delta(state: &RouteState) -> Option<Delta> {
+    let waypoint = state.active_waypoint()?;
+    let course = match waypoint.leg {
+        LegType::GreatCircle => "navigation.courseGreatCircle",
+        LegType::RhumbLine => "navigation.courseRhumbline",
+    };
+    let paths = [
+        ("nextPoint.position", json!({ "latitude": waypoint.latitude, "longitude": waypoint.longitude })),
+        ("nextPoint.distance", json!(state.dtw * 1852.0)),
+        ("nextPoint.bearingTrue", json!(state.btw.to_radians())),
+        ("nextPoint.velocityMadeGood", json!(state.vmg * KNOTS_TO_MPS)),
+        ("crossTrackError", json!(state.xte * 1852.0)),
+        ("bearingTrackTrue", json!(state.bod.to_radians())),
+    ]
+    .map(|(path, value)| (format!("{}.{}", course, path), value));
+
+    Some(delta(
+        "vessel-simulator.route",
+        &state.last_update,
+        paths.iter().map(|(path, value)| (path.as_str(), value.clone())).collect(),
+    ))
+}
+
+/// Path Signal K standar per jenis channel suhu.
+fn thermal_path(kind: ThermalKind) -> Option<&'static str> {
+    match kind {
+        ThermalKind::SeaWater => Some("environment.water.temperature"),
+        ThermalKind::EngineRoom => Some("environment.inside.engineRoom.temperature"),
+        ThermalKind::EngineCoolant => Some("propulsion.main.coolantTemperature"),
+        ThermalKind::Exhaust => Some("propulsion.main.exhaustTemperature"),
+        ThermalKind::Cabin => Some("environment.inside.mainCabin.temperature"),
+        ThermalKind::Refrigeration => Some("environment.inside.refrigerator.temperature"),
+        ThermalKind::Other => None,
+    }
+}
+
+/// Delta suhu dalam Kelvin. Channel pertama tiap jenis memakai path standar;
+/// channel lain memakai `environment.inside.{name}.temperature`.
+pub fn thermal_delta(state: &ThermalState) -> Delta {
+    let mut used = Vec::new();
+    let paths: Vec<(String, serde_json::Value)> = state
+        .channels
+        .iter()
+        .map(|channel| {
+            let path = match thermal_path(channel.kind) {
+                Some(path) if !used.contains(&channel.kind) => {
+                    used.push(channel.kind);
+                    path.to_string()
+                }
+                _ => format!("environment.inside.{}.temperature", channel.name),
+            };
+            (path, json!(channel.temperature + 273.15))
+        })
+        .collect();
+
+    delta(
+        "vessel-simulator.thermal",
+        &state.last_update,
+        paths.iter().map(|(path, value)| (path.as_str(), value.clone())).collect(),
+    )
+}
+
+// === SUBSCRIPTIONS ===
+
+#[derive(Debug, Deserialize)]
+struct SubscribeItem {
+    path: String,
+    period: Option<u64>,
+    #[serde(rename = "minPeriod")]
+    min_period: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UnsubscribeItem {
+    path: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClientMessage {
+    context: Option<String>,
+    subscribe: Option<Vec<SubscribeItem>>,
+    unsubscribe: Option<Vec<UnsubscribeItem>>,
+}
+
+/// Batas panjang pola dan jumlah langganan per client, karena penyaringan berjalan di task publikasi
+const MAX_PATTERN_LEN: usize = 256;
+const MAX_SUBSCRIPTIONS: usize = 64;
+
+#[derive(Debug)]
+struct Subscription {
+    pattern: String,
+    interval: Option<Duration>,
+}
+
+/// Langganan satu client Signal K: pola path dan waktu kirim terakhir per path.
+#[derive(Debug, Default)]
+pub struct SignalKSession {
+    subscriptions: Vec<Subscription>,
+    last_sent: HashMap<String, Instant>,
+}
+
+impl SignalKSession {
+    /// Langganan awal sesuai query `?subscribe=self|all|none` (default `self`).
+    fn from_query(query: &str) -> Self {
+        let mode = query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(key, _)| *key == "subscribe")
+            .map(|(_, value)| value)
+            .unwrap_or("self");
+
+        let mut session = SignalKSession::default();
+        if mode != "none" {
+            session.subscriptions.push(Subscription { pattern: "*".to_string(), interval: None });
+        }
+        session
+    }
+
+    fn apply(&mut self, message: ClientMessage) {
+        // Simulator hanya punya satu kapal (self), konteks lain diabaikan
+        let context = message.context.unwrap_or_else(|| "vessels.self".to_string());
+        if !matches!(context.as_str(), "*" | "vessels.*" | "vessels.self") && context != self_context() {
+            return;
+        }
+
+        for item in message.unsubscribe.unwrap_or_default() {
+            if item.path == "*" {
+                self.subscriptions.clear();
+            } else {
+                self.subscriptions.retain(|s| s.pattern != item.path);
+            }
+        }
+
+        for item in message.subscribe.unwrap_or_default() {
+            self.subscriptions.retain(|s| s.pattern != item.path);
+            if item.path.len() > MAX_PATTERN_LEN || self.subscriptions.len() >= MAX_SUBSCRIPTIONS {
+                eprintln!("[Signal K]: Subscription to {:.64} ignored (pattern too long or too many subscriptions)", item.path);
+                continue;
+            }
+            let interval = item.min_period.or(item.period).map(Duration::from_millis);
+            self.subscriptions.push(Subscription { pattern: item.path, interval });
+        }
+    }
+
+    /// Menyaring delta sesuai langganan dan periode; `None` jika tidak ada nilai yang perlu dikirim.
+    pub fn filter(&mut self, delta: &Delta) -> Option<Delta> {
+        let now = Instant::now();
+        let mut updates = Vec::new();
+
+        for update in &delta.updates {
+            let mut values = Vec::new();
+            for value in &update.values {
+                let Some(sub) = self.subscriptions.iter().find(|s| path_matches(&s.pattern, &value.path)) else {
+                    continue;
+                };
+                if let (Some(interval), Some(last)) = (sub.interval, self.last_sent.get(&value.path)) {
+                    if now.duration_since(*last) < interval {
+                        continue;
+                    }
+                }
+                self.last_sent.insert(value.path.clone(), now);
+                values.push(value.clone());
+            }
+            if !values.is_empty() {
+                updates.push(DeltaUpdate { values, ..update.clone() });
+            }
+        }
+
+        (!updates.is_empty()).then(|| Delta { context: delta.context.clone(), updates })
+    }
+}
+
+/// Pencocokan path dengan wildcard `*` (boleh melintasi beberapa segmen). Glob dua pointer:
+/// saat gagal, kembali ke `*` terakhir saja, jadi waktunya O(pola × path) tanpa backtracking eksponensial.
+fn path_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, path) = (pattern.as_bytes(), path.as_bytes());
+    let (mut p, mut t) = (0, 0);
+    // Posisi `*` terakhir di pola dan posisi path yang sedang ditelannya
+    let mut star: Option<(usize, usize)> = None;
+    while t < path.len() {
+        if p < pattern.len() && pattern[p] == b'*' {
+            star = Some((p, t));
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == path[t] {
+            p += 1;
+            t += 1;
+        } else if let Some((star_p, star_t)) = star {
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, t));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&b| b == b'*')
+}
+
+// === STREAMING ===
+
+fn hello_message() -> String {
+    json!({
+        "name": "vessel-nddu",
+        "version": SIGNALK_VERSION,
+        "self": self_context(),
+        "roles": ["master", "main"],
+        "timestamp": sim_clock::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+    })
+    .to_string()
+}
+
+/// Kirim delta ke semua client Signal K, masing-masing disaring sesuai langganannya.
+pub async fn broadcast_delta(clients: &Clients, delta: &Delta) {
+    let clients_guard = clients.read().await;
+    for client in clients_guard.iter() {
+        let Some(session) = &client.signalk else { continue };
+        let filtered = session.lock().unwrap().filter(delta);
+        if let Some(filtered) = filtered {
+            if let Ok(text) = serde_json::to_string(&filtered) {
+                let _ = client.tx.send(Message::Text(text.into()));
+            }
+        }
+    }
+}
+
+/// Koneksi WebSocket di `/signalk/v1/stream`: hello, lalu delta + pesan subscribe/unsubscribe.
+pub async fn handle_signalk_connection(ws_stream: WebSocketStream<TcpStream>, query: &str, clients: Clients) {
+    let (mut write, mut read) = ws_stream.split();
+    let (tx, mut rx) = mpsc::unbounded_channel::<Message>();
+    let session = Arc::new(Mutex::new(SignalKSession::from_query(query)));
+
+    let _ = tx.send(Message::Text(hello_message().into()));
+    clients.write().await.push(WsClient { tx, signalk: Some(session.clone()) });
+
+    let writer = tokio::spawn(async move {
+        while let Some(msg) = rx.recv().await {
+            if write.send(msg).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    while let Some(Ok(msg)) = read.next().await {
+        if let Message::Text(text) = msg {
+            match serde_json::from_str::<ClientMessage>(&text) {
+                Ok(message) => session.lock().unwrap().apply(message),
+                Err(e) => eprintln!("[Signal K]: Ignoring invalid client message: {}", e),
+            }
+        }
+    }
+
+    writer.abort();
+    let _ = writer.await;
+    clients.write().await.retain(|c| !c.tx.is_closed());
+    println!("A Signal K client disconnected.");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::path_matches;
+
+    #[test]
+    fn path_matches_wildcards() {
+        assert!(path_matches("*", "navigation.position"));
+        assert!(path_matches("navigation.*", "navigation.speedOverGround"));
+        assert!(path_matches("navigation.*.value", "navigation.attitude.roll.value"));
+        assert!(path_matches("*.speed*", "navigation.speedThroughWater"));
+        assert!(path_matches("environment.depth.belowKeel", "environment.depth.belowKeel"));
+        assert!(!path_matches("environment.depth.belowKeel", "environment.depth.belowKeelX"));
+        assert!(!path_matches("navigation.*", "environment.wind.angleApparent"));
+        assert!(!path_matches("*.angle", "environment.wind.angleApparent"));
+    }
+
+    #[test]
+    fn path_matches_pathological_pattern() {
+        // Dengan backtracking rekursif pola ini eksponensial; di sini cukup satu lintasan per `*`
+        let pattern = "a*".repeat(100) + "b";
+        assert!(!path_matches(&pattern, &"a".repeat(200)));
+        assert!(path_matches(&pattern, &("a".repeat(200) + "b")));
+        assert!(path_matches(&("a*".repeat(100) + "a"), &"a".repeat(200)));
+        assert!(!path_matches(&"a*".repeat(100), &"a".repeat(99)));
+    }
+}
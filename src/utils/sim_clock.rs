@@ -0,0 +1,167 @@
+use crate::data::clock_data::{ClockStatus, UpdateClockRequest};
+use chrono::{DateTime, Duration, Utc};
+use std::sync::{LazyLock, Mutex};
+use std::time::Instant;
+
+/// Panjang satu langkah kalkulasi dalam waktu simulasi (ms)
+pub const STEP_MS: u64 = 100;
+/// Interval tick thread kalkulasi (waktu nyata, ms)
+pub const TICK_MS: u64 = 100;
+pub const MIN_SPEED: f64 = 0.1;
+pub const MAX_SPEED: f64 = 100.0;
+/// Batas langkah yang diantrekan lewat API sekaligus
+const MAX_PENDING_STEPS: u32 = 36_000;
+/// Batas langkah per tick thread kalkulasi, agar lock state tidak dipegang terlalu lama:
+/// 2× langkah nominal pada `MAX_SPEED`, sehingga tick yang sedikit terlambat masih terkejar
+const MAX_STEPS_PER_TICK: u32 = (2.0 * MAX_SPEED * TICK_MS as f64 / STEP_MS as f64) as u32;
+
+struct ClockState {
+    time: DateTime<Utc>,
+    speed: f64,
+    paused: bool,
+    pending_steps: u32,
+    lag_ms: u64,
+    /// Sisa pecahan langkah dari tick sebelumnya
+    accumulator: f64,
+    last_tick: Instant,
+}
+
+impl ClockState {
+    fn realtime() -> Self {
+        Self { time: Utc::now(), speed: 1.0, paused: false, pending_steps: 0, lag_ms: 0, accumulator: 0.0, last_tick: Instant::now() }
+    }
+}
+
+/// Jam simulasi global: semua `last_update`, tanggal WMM, dan fase gelombang memakai waktu ini.
+/// Waktu hanya maju saat thread kalkulasi menjalankan langkah, jadi pause/step/time warp
+/// berlaku serentak untuk semua sensor.
+static CLOCK: LazyLock<Mutex<ClockState>> = LazyLock::new(|| Mutex::new(ClockState::realtime()));
+
+/// Waktu simulasi saat ini.
+pub fn now() -> DateTime<Utc> {
+    CLOCK.lock().unwrap().time
+}
+
+pub fn status() -> ClockStatus {
+    let clock = CLOCK.lock().unwrap();
+    ClockStatus {
+        time: clock.time,
+        speed: clock.speed,
+        paused: clock.paused,
+        step_ms: STEP_MS,
+        pending_steps: clock.pending_steps,
+        lag_ms: clock.lag_ms,
+    }
+}
+
+pub fn update(patch: UpdateClockRequest) -> ClockStatus {
+    {
+        let mut clock = CLOCK.lock().unwrap();
+        if let Some(time) = patch.time { clock.time = time; }
+        if let Some(speed) = patch.speed { clock.speed = speed.clamp(MIN_SPEED, MAX_SPEED); }
+        if let Some(paused) = patch.paused {
+            clock.paused = paused;
+            clock.accumulator = 0.0;
+        }
+    }
+    status()
+}
+
+/// Mengantrekan langkah tunggal; dijalankan thread kalkulasi pada tick berikutnya.
+pub fn step(steps: u32) -> ClockStatus {
+    {
+        let mut clock = CLOCK.lock().unwrap();
+        clock.pending_steps = clock.pending_steps.saturating_add(steps).min(MAX_PENDING_STEPS);
+    }
+    status()
+}
+
+/// Kembali ke waktu nyata: waktu sekarang, speed 1×, tidak di-pause.
+pub fn reset() -> ClockStatus {
+    *CLOCK.lock().unwrap() = ClockState::realtime();
+    status()
+}
+
+impl ClockState {
+    /// Lihat `due_steps`; `elapsed_ms` = waktu nyata sejak tick sebelumnya.
+    fn take_steps(&mut self, elapsed_ms: f64) -> u32 {
+        if !self.paused {
+            self.accumulator += elapsed_ms * self.speed / STEP_MS as f64;
+        }
+        let realtime = self.accumulator.floor();
+        self.accumulator -= realtime;
+
+        let from_api = self.pending_steps.min(MAX_STEPS_PER_TICK);
+        self.pending_steps -= from_api;
+        let realtime = realtime as u64;
+        let from_realtime = realtime.min((MAX_STEPS_PER_TICK - from_api) as u64);
+        // Sisa sampai satu tick penuh dibawa ke tick berikutnya; hanya kelebihannya yang menjadi lag
+        let behind = realtime - from_realtime;
+        let carried = behind.min(MAX_STEPS_PER_TICK as u64);
+        self.accumulator += carried as f64;
+        self.lag_ms = self.lag_ms.saturating_add((behind - carried).saturating_mul(STEP_MS));
+        from_api + from_realtime as u32
+    }
+}
+
+/// Jumlah langkah yang harus dijalankan thread kalkulasi sejak tick sebelumnya:
+/// waktu nyata yang berlalu × speed (kecuali di-pause), ditambah langkah dari API, maksimal
+/// `MAX_STEPS_PER_TICK`. Langkah API yang tersisa menunggu tick berikutnya; langkah waktu nyata
+/// yang tertunda dikejar di tick berikutnya, dan yang melebihi satu tick penuh dibuang dan dicatat
+/// di `lag_ms`, agar kalkulasi tidak makin tertinggal.
+pub fn due_steps() -> u32 {
+    let mut clock = CLOCK.lock().unwrap();
+    let elapsed_ms = clock.last_tick.elapsed().as_secs_f64() * 1000.0;
+    clock.last_tick = Instant::now();
+    clock.take_steps(elapsed_ms)
+}
+
+/// Memajukan waktu simulasi satu langkah.
+pub fn advance() {
+    let mut clock = CLOCK.lock().unwrap();
+    clock.time += Duration::milliseconds(STEP_MS as i64);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock(speed: f64) -> ClockState {
+        ClockState { speed, ..ClockState::realtime() }
+    }
+
+    #[test]
+    fn max_speed_keeps_up_with_tick_jitter() {
+        let mut clock = clock(MAX_SPEED);
+        // Tick bergantian terlambat dan lebih cepat: rata-rata tetap 100 ms
+        let total: u32 = [130.0, 70.0, 160.0, 40.0, 100.0].iter().map(|&elapsed| clock.take_steps(elapsed)).sum();
+        assert_eq!(total, 500);
+        assert_eq!(clock.lag_ms, 0);
+    }
+
+    #[test]
+    fn long_stall_beyond_one_tick_is_reported_as_lag() {
+        let mut clock = clock(MAX_SPEED);
+        // 1 s macet pada 100×: 1000 langkah, maksimal 200 per tick + 200 dibawa
+        assert_eq!(clock.take_steps(1000.0), MAX_STEPS_PER_TICK);
+        assert_eq!(clock.lag_ms, 600 * STEP_MS);
+        assert_eq!(clock.take_steps(0.0), MAX_STEPS_PER_TICK);
+        assert_eq!(clock.take_steps(0.0), 0);
+    }
+
+    #[test]
+    fn paused_clock_only_runs_api_steps() {
+        let mut clock = ClockState { paused: true, pending_steps: 250, ..clock(1.0) };
+        assert_eq!(clock.take_steps(1000.0), MAX_STEPS_PER_TICK);
+        assert_eq!(clock.take_steps(1000.0), 50);
+        assert_eq!(clock.take_steps(1000.0), 0);
+        assert_eq!(clock.lag_ms, 0);
+    }
+
+    #[test]
+    fn fractional_steps_accumulate() {
+        let mut clock = clock(0.5);
+        let total: u32 = (0..10).map(|_| clock.take_steps(100.0)).sum();
+        assert_eq!(total, 5);
+    }
+}
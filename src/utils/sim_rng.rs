@@ -0,0 +1,118 @@
+use crate::data::seed_data::SeedStatus;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{LazyLock, Mutex};
+
+/// Generator noise simulasi. ChaCha dipakai (bukan `StdRng`) karena urutannya dijamin sama
+/// di semua versi `rand`, jadi seed yang sama menghasilkan data yang sama.
+pub type SimRng = ChaCha8Rng;
+
+/// Variabel lingkungan untuk master seed saat server mulai; jika kosong, seed dipilih acak.
+const SEED_ENV: &str = "VESSEL_SEED";
+
+struct RngState {
+    seed: u64,
+    /// Satu stream per simulator (nama sensor), dibuat saat pertama dipakai
+    streams: HashMap<String, SimRng>,
+}
+
+/// Master seed global. Setiap simulator punya stream sendiri yang diturunkan dari master seed
+/// dan namanya, sehingga urutan noise satu sensor tidak bergantung pada sensor lain.
+static RNG: LazyLock<Mutex<RngState>> = LazyLock::new(|| {
+    let seed = match std::env::var(SEED_ENV).map(|value| value.trim().parse::<u64>()) {
+        Ok(Ok(seed)) => seed,
+        Ok(Err(e)) => {
+            eprintln!("[Seed]: Invalid {}: {}; using a random seed", SEED_ENV, e);
+            rand::rng().next_u64()
+        }
+        Err(_) => rand::rng().next_u64(),
+    };
+    Mutex::new(RngState { seed, streams: HashMap::new() })
+});
+
+/// FNV-1a, stabil antar versi Rust (berbeda dengan `DefaultHasher`).
+fn fnv1a(name: &str) -> u64 {
+    name.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+fn derive(seed: u64, name: &str) -> u64 {
+    seed ^ fnv1a(name)
+}
+
+pub fn seed() -> u64 {
+    RNG.lock().unwrap().seed
+}
+
+/// Seed stream milik satu simulator.
+pub fn stream_seed(name: &str) -> u64 {
+    derive(seed(), name)
+}
+
+pub fn status(names: &[&'static str]) -> SeedStatus {
+    let seed = seed();
+    SeedStatus {
+        seed,
+        streams: names.iter().map(|name| (name.to_string(), derive(seed, name))).collect::<BTreeMap<_, _>>(),
+    }
+}
+
+/// Mengganti master seed; semua stream dimulai ulang dari awal.
+pub fn set_seed(seed: u64) {
+    let mut rng = RNG.lock().unwrap();
+    rng.seed = seed;
+    rng.streams.clear();
+}
+
+/// Seed acak baru, mis. untuk kembali ke run yang tidak deterministik.
+pub fn randomize() -> u64 {
+    let seed = rand::rng().next_u64();
+    set_seed(seed);
+    seed
+}
+
+/// Memulai ulang stream satu simulator, dipanggil saat simulasinya dibuat.
+pub fn reset_stream(name: &str) {
+    RNG.lock().unwrap().streams.remove(name);
+}
+
+/// Menjalankan `f` dengan stream milik simulator `name`.
+pub fn with_stream<T>(name: &str, f: impl FnOnce(&mut SimRng) -> T) -> T {
+    let mut rng = RNG.lock().unwrap();
+    let seed = rng.seed;
+    // Nama hanya dialokasikan saat stream pertama kali dibuat
+    if !rng.streams.contains_key(name) {
+        rng.streams.insert(name.to_string(), SimRng::seed_from_u64(derive(seed, name)));
+    }
+    f(rng.streams.get_mut(name).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draw(name: &str) -> Vec<u64> {
+        (0..4).map(|_| with_stream(name, |rng| rng.next_u64())).collect()
+    }
+
+    // Satu test saja: master seed global, test paralel lain tidak boleh menggantinya di tengah jalan
+    #[test]
+    fn same_seed_restarts_identical_independent_streams() {
+        set_seed(42);
+        let gps = draw("test-gps");
+        let gyro = draw("test-gyro");
+        assert_ne!(gps, gyro);
+        assert_eq!(stream_seed("test-gps"), 42 ^ fnv1a("test-gps"));
+
+        // Urutan pemakaian stream lain tidak memengaruhi stream ini
+        set_seed(42);
+        draw("test-gyro");
+        assert_eq!(draw("test-gps"), gps);
+
+        reset_stream("test-gps");
+        assert_eq!(draw("test-gps"), gps);
+
+        set_seed(43);
+        assert_ne!(draw("test-gps"), gps);
+    }
+}
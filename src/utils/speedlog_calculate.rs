@@ -0,0 +1,47 @@
+use crate::data::speedlog_data::SpeedLogState;
+use crate::data::vessel_data::VesselState;
+use crate::utils::vessel_calculate;
+use crate::utils::sim_clock;
+
+/// Vektor (utara, timur) → komponen (longitudinal, transversal) relatif `heading`.
+fn to_body(north: f64, east: f64, heading: f64) -> (f64, f64) {
+    let rad = heading.to_radians();
+    (north * rad.cos() + east * rad.sin(), -north * rad.sin() + east * rad.cos())
+}
+
+/// SOG (knot) dan COG (derajat true) dari model speed log untuk heading tertentu.
+pub fn ground_velocity(state: &SpeedLogState, heading: f64) -> (f64, f64) {
+    vessel_calculate::ground_velocity(state.stw, heading, state.leeway, state.current_set, state.current_drift)
+}
+
+/// Mengambil STW, heading, leeway, dan arus dari model kapal.
+pub fn sample_vessel(state: &mut SpeedLogState, vessel: &VesselState) {
+    state.stw = vessel.stw;
+    state.heading = vessel.heading;
+    state.leeway = vessel.leeway;
+    state.current_set = vessel.current_set;
+    state.current_drift = vessel.current_drift;
+}
+
+/// Memperbarui heading (dari gyro jika ada) dan semua kecepatan turunan.
+pub fn calculate_next_speedlog_state(state: &mut SpeedLogState, heading: Option<f64>) {
+    if let Some(heading) = heading {
+        state.heading = heading.rem_euclid(360.0);
+    }
+
+    (state.sog, state.cog) = ground_velocity(state, state.heading);
+
+    let leeway = state.leeway.to_radians();
+    state.longitudinal_water_speed = state.stw * leeway.cos();
+    state.transverse_water_speed = state.stw * leeway.sin();
+
+    let (north, east) = vessel_calculate::ground_vector(
+        state.stw,
+        state.heading,
+        state.leeway,
+        state.current_set,
+        state.current_drift,
+    );
+    (state.longitudinal_ground_speed, state.transverse_ground_speed) = to_body(north, east, state.heading);
+    state.last_update = sim_clock::now();
+}
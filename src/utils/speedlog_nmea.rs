@@ -0,0 +1,52 @@
+use crate::data::speedlog_data::{SpeedLogSentence, SpeedLogState};
+use crate::utils::nmea::{self, format_angle};
+
+const KNOTS_TO_KMH: f64 = 1.852;
+
+/// Menghasilkan kalimat NMEA untuk setiap jenis di `sentences`, sesuai urutannya.
+/// `variation` dari GPS; tanpa GPS field heading magnetik VHW dikosongkan.
+pub fn encode_speedlog_sentences(
+    state: &SpeedLogState,
+    talker: &str,
+    sentences: &[SpeedLogSentence],
+    variation: Option<f64>,
+) -> Vec<String> {
+    sentences
+        .iter()
+        .map(|kind| match kind {
+            SpeedLogSentence::Vhw => encode_vhw(state, talker, variation),
+            SpeedLogSentence::Vbw => encode_vbw(state, talker),
+        })
+        .collect()
+}
+
+/// VHW — Water Speed and Heading (heading true/magnetik, STW dalam knot dan km/h)
+pub fn encode_vhw(state: &SpeedLogState, talker: &str, variation: Option<f64>) -> String {
+    let magnetic = variation
+        .map(|var| format_angle((state.heading - var).rem_euclid(360.0)))
+        .unwrap_or_default();
+    let fields = [
+        format_angle(state.heading),
+        "T".to_string(),
+        magnetic,
+        "M".to_string(),
+        format!("{:.2}", state.stw),
+        "N".to_string(),
+        format!("{:.2}", state.stw * KNOTS_TO_KMH),
+        "K".to_string(),
+    ];
+    nmea::sentence(talker, "VHW", &fields)
+}
+
+/// VBW — Dual Ground/Water Speed (knot, transversal negatif = ke kiri)
+pub fn encode_vbw(state: &SpeedLogState, talker: &str) -> String {
+    let fields = [
+        format!("{:.2}", state.longitudinal_water_speed),
+        format!("{:.2}", state.transverse_water_speed),
+        "A".to_string(),
+        format!("{:.2}", state.longitudinal_ground_speed),
+        format!("{:.2}", state.transverse_ground_speed),
+        "A".to_string(),
+    ];
+    nmea::sentence(talker, "VBW", &fields)
+}
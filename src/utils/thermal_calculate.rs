@@ -0,0 +1,26 @@
+use crate::data::thermal_data::{ThermalChannel, ThermalState};
+use rand_distr::{Distribution, StandardNormal};
+use crate::utils::sim_clock;
+use crate::utils::sim_rng::SimRng;
+
+/// Satu langkah satu channel: drift over-temperature, respons orde satu menuju target, lalu noise.
+fn calculate_next_channel(channel: &mut ThermalChannel, dt_seconds: f64, rng: &mut SimRng) {
+    if let Some(drift) = channel.drift {
+        channel.drift_offset = (channel.drift_offset + drift.rate * dt_seconds / 60.0).min(drift.max_offset);
+    }
+
+    let target = channel.setpoint + channel.drift_offset;
+    let alpha = 1.0 - (-dt_seconds / channel.time_constant.max(0.1)).exp();
+    channel.value += (target - channel.value) * alpha;
+
+    let noise: f64 = StandardNormal.sample(rng);
+    channel.temperature = channel.value + channel.noise * noise;
+}
+
+pub fn calculate_next_thermal_state(state: &mut ThermalState, rng: &mut SimRng) {
+    let dt_seconds = state.calculation_rate_ms as f64 / 1000.0;
+    for channel in &mut state.channels {
+        calculate_next_channel(channel, dt_seconds, rng);
+    }
+    state.last_update = sim_clock::now();
+}
@@ -0,0 +1,34 @@
+use crate::data::thermal_data::{ThermalChannel, ThermalKind, ThermalSentence, ThermalState};
+use crate::utils::nmea;
+
+/// Menghasilkan kalimat NMEA untuk setiap jenis di `sentences`, sesuai urutannya.
+pub fn encode_thermal_sentences(state: &ThermalState, talker: &str, sentences: &[ThermalSentence]) -> Vec<String> {
+    sentences
+        .iter()
+        .flat_map(|kind| match kind {
+            ThermalSentence::Mtw => state
+                .channels
+                .iter()
+                .filter(|c| c.kind == ThermalKind::SeaWater)
+                .map(|c| encode_mtw(c, talker))
+                .collect::<Vec<_>>(),
+            ThermalSentence::Xdr => state.channels.iter().map(|c| encode_xdr(c, talker)).collect(),
+        })
+        .collect()
+}
+
+/// MTW — Mean Temperature of Water (°C)
+pub fn encode_mtw(channel: &ThermalChannel, talker: &str) -> String {
+    nmea::sentence(talker, "MTW", &[format!("{:.1}", channel.temperature), "C".to_string()])
+}
+
+/// XDR — Transducer Measurements, tipe C (suhu) dengan nama channel sebagai ID transduser
+pub fn encode_xdr(channel: &ThermalChannel, talker: &str) -> String {
+    let fields = [
+        "C".to_string(),
+        format!("{:.1}", channel.temperature),
+        "C".to_string(),
+        channel.name.clone(),
+    ];
+    nmea::sentence(talker, "XDR", &fields)
+}
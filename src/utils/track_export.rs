@@ -0,0 +1,180 @@
+use crate::data::track_data::TrackPoint;
+use chrono::SecondsFormat;
+use std::fmt::Write;
+
+fn timestamp(point: &TrackPoint) -> String {
+    point.time.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+fn optional(value: Option<f64>) -> String {
+    value.map(|v| format!("{:.2}", v)).unwrap_or_default()
+}
+
+/// GPX 1.1: satu `trk`; SOG/COG/heading/attitude di `<extensions>` setiap `trkpt`.
+pub fn to_gpx(points: &[TrackPoint]) -> String {
+    let mut out = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<gpx version=\"1.1\" creator=\"vessel\" xmlns=\"http://www.topografix.com/GPX/1/1\" ",
+        "xmlns:vessel=\"urn:vessel:track\">\n",
+        "  <trk>\n    <name>Simulated track</name>\n    <trkseg>\n",
+    ));
+    for p in points {
+        let _ = write!(
+            out,
+            "      <trkpt lat=\"{:.7}\" lon=\"{:.7}\"><time>{}</time><extensions>\
+             <vessel:sog>{:.2}</vessel:sog><vessel:cog>{:.2}</vessel:cog>",
+            p.latitude, p.longitude, timestamp(p), p.sog, p.cog
+        );
+        for (tag, value) in [("heading", p.heading), ("roll", p.roll), ("pitch", p.pitch)] {
+            if let Some(value) = value {
+                let _ = write!(out, "<vessel:{tag}>{:.2}</vessel:{tag}>", value);
+            }
+        }
+        out.push_str("</extensions></trkpt>\n");
+    }
+    out.push_str("    </trkseg>\n  </trk>\n</gpx>\n");
+    out
+}
+
+fn simple_array_data(out: &mut String, name: &str, values: impl Iterator<Item = f64>) {
+    let _ = writeln!(out, "          <gx:SimpleArrayData name=\"{}\">", name);
+    for value in values {
+        let _ = writeln!(out, "            <gx:value>{:.2}</gx:value>", value);
+    }
+    out.push_str("          </gx:SimpleArrayData>\n");
+}
+
+/// KML `gx:Track`: heading/pitch/roll sebagai `gx:angles`, SOG/COG sebagai `gx:SimpleArrayData`.
+pub fn to_kml(points: &[TrackPoint]) -> String {
+    let mut out = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<kml xmlns=\"http://www.opengis.net/kml/2.2\" xmlns:gx=\"http://www.google.com/kml/ext/2.2\">\n",
+        "<Document>\n  <name>Simulated track</name>\n",
+        "  <Schema id=\"motion\">\n",
+        "    <gx:SimpleArrayField name=\"sog\" type=\"float\"><displayName>SOG (kn)</displayName></gx:SimpleArrayField>\n",
+        "    <gx:SimpleArrayField name=\"cog\" type=\"float\"><displayName>COG (deg)</displayName></gx:SimpleArrayField>\n",
+        "  </Schema>\n",
+        "  <Placemark>\n    <name>Simulated track</name>\n    <gx:Track>\n",
+    ));
+    for p in points {
+        let _ = writeln!(out, "      <when>{}</when>", timestamp(p));
+    }
+    for p in points {
+        let _ = writeln!(out, "      <gx:coord>{:.7} {:.7} 0</gx:coord>", p.longitude, p.latitude);
+    }
+    for p in points {
+        let _ = writeln!(
+            out,
+            "      <gx:angles>{:.2} {:.2} {:.2}</gx:angles>",
+            p.heading.unwrap_or(p.cog),
+            p.pitch.unwrap_or(0.0),
+            p.roll.unwrap_or(0.0)
+        );
+    }
+    out.push_str("      <ExtendedData>\n        <SchemaData schemaUrl=\"#motion\">\n");
+    simple_array_data(&mut out, "sog", points.iter().map(|p| p.sog));
+    simple_array_data(&mut out, "cog", points.iter().map(|p| p.cog));
+    out.push_str("        </SchemaData>\n      </ExtendedData>\n    </gx:Track>\n  </Placemark>\n</Document>\n</kml>\n");
+    out
+}
+
+/// GeoJSON: satu Feature `LineString`; waktu di `coordTimes` dan data gerak per titik
+/// sebagai array paralel di `properties`. LineString butuh minimal dua titik: satu titik
+/// dikirim sebagai `Point`, track kosong sebagai FeatureCollection tanpa feature.
+pub fn to_geojson(points: &[TrackPoint]) -> serde_json::Value {
+    let values = |f: fn(&TrackPoint) -> serde_json::Value| points.iter().map(f).collect::<Vec<_>>();
+    let geometry = match points {
+        [] => return serde_json::json!({ "type": "FeatureCollection", "features": [] }),
+        [p] => serde_json::json!({ "type": "Point", "coordinates": [p.longitude, p.latitude] }),
+        _ => serde_json::json!({
+            "type": "LineString",
+            "coordinates": values(|p| serde_json::json!([p.longitude, p.latitude])),
+        }),
+    };
+    serde_json::json!({
+        "type": "FeatureCollection",
+        "features": [{
+            "type": "Feature",
+            "geometry": geometry,
+            "properties": {
+                "name": "Simulated track",
+                "coordTimes": values(|p| serde_json::json!(timestamp(p))),
+                "sog": values(|p| serde_json::json!(p.sog)),
+                "cog": values(|p| serde_json::json!(p.cog)),
+                "heading": values(|p| serde_json::json!(p.heading)),
+                "roll": values(|p| serde_json::json!(p.roll)),
+                "pitch": values(|p| serde_json::json!(p.pitch)),
+            }
+        }]
+    })
+}
+
+/// CSV dengan header; kolom heading/roll/pitch kosong jika tidak tersedia.
+pub fn to_csv(points: &[TrackPoint]) -> String {
+    let mut out = String::from("timestamp,latitude,longitude,sog,cog,heading,roll,pitch\n");
+    for p in points {
+        let _ = writeln!(
+            out,
+            "{},{:.7},{:.7},{:.2},{:.2},{},{},{}",
+            timestamp(p),
+            p.latitude,
+            p.longitude,
+            p.sog,
+            p.cog,
+            optional(p.heading),
+            optional(p.roll),
+            optional(p.pitch)
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Duration};
+
+    fn points(count: usize) -> Vec<TrackPoint> {
+        let start = DateTime::parse_from_rfc3339("2024-05-01T12:00:00Z").unwrap().to_utc();
+        (0..count)
+            .map(|i| TrackPoint {
+                time: start + Duration::seconds(i as i64),
+                latitude: 60.0 + i as f64 * 0.001,
+                longitude: 24.5,
+                sog: 5.0,
+                cog: 0.0,
+                heading: Some(1.5),
+                roll: None,
+                pitch: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn geojson_geometry_follows_point_count() {
+        assert_eq!(to_geojson(&[]), serde_json::json!({ "type": "FeatureCollection", "features": [] }));
+
+        let single = to_geojson(&points(1));
+        assert_eq!(single["features"][0]["geometry"], serde_json::json!({ "type": "Point", "coordinates": [24.5, 60.0] }));
+        assert_eq!(single["features"][0]["properties"]["coordTimes"], serde_json::json!(["2024-05-01T12:00:00.000Z"]));
+
+        let line = to_geojson(&points(3));
+        assert_eq!(line["features"][0]["geometry"]["type"], "LineString");
+        assert_eq!(line["features"][0]["geometry"]["coordinates"].as_array().map(Vec::len), Some(3));
+        assert_eq!(line["features"][0]["properties"]["roll"], serde_json::json!([null, null, null]));
+    }
+
+    #[test]
+    fn gpx_and_csv_contain_every_point() {
+        let points = points(2);
+        let gpx = to_gpx(&points);
+        assert_eq!(gpx.matches("<trkpt ").count(), 2);
+        assert!(gpx.contains("<trkpt lat=\"60.0010000\" lon=\"24.5000000\"><time>2024-05-01T12:00:01.000Z</time>"));
+        assert!(gpx.contains("<vessel:heading>1.50</vessel:heading>") && !gpx.contains("<vessel:roll>"));
+
+        let csv = to_csv(&points);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[1], "2024-05-01T12:00:00.000Z,60.0000000,24.5000000,5.00,0.00,1.50,,");
+    }
+}
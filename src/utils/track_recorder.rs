@@ -0,0 +1,71 @@
+use crate::data::track_data::{SharedTrack, TrackDecimation, TrackInfo, TrackLog, TrackPoint, UpdateTrackRequest};
+use crate::utils::navigation;
+
+const METERS_PER_NM: f64 = 1852.0;
+
+/// Apakah titik baru cukup jauh (jarak) atau cukup lama (waktu) dari titik terakhir.
+fn is_due(decimation: TrackDecimation, last: &TrackPoint, point: &TrackPoint) -> bool {
+    match decimation {
+        TrackDecimation::Distance { meters } => {
+            let (distance, _) = navigation::great_circle(last.latitude, last.longitude, point.latitude, point.longitude);
+            distance * METERS_PER_NM >= meters
+        }
+        TrackDecimation::Time { seconds } => {
+            (point.time - last.time).num_milliseconds() as f64 / 1000.0 >= seconds
+        }
+    }
+}
+
+/// Mencatat titik ke track sesuai aturan desimasi; titik pertama selalu dicatat.
+pub fn record(track: &SharedTrack, point: TrackPoint) {
+    let mut log = track.lock().unwrap();
+    if !log.settings.enabled {
+        return;
+    }
+    if let Some(last) = log.points.back() {
+        if !is_due(log.settings.decimation, last, &point) {
+            return;
+        }
+    }
+    log.points.push_back(point);
+    trim(&mut log);
+}
+
+fn trim(log: &mut TrackLog) {
+    let excess = log.points.len().saturating_sub(log.settings.max_points);
+    log.points.drain(..excess);
+}
+
+pub fn info(track: &SharedTrack) -> TrackInfo {
+    let log = track.lock().unwrap();
+    TrackInfo {
+        settings: log.settings.clone(),
+        point_count: log.points.len(),
+        start: log.points.front().map(|p| p.time),
+        end: log.points.back().map(|p| p.time),
+    }
+}
+
+pub fn update_settings(track: &SharedTrack, patch: UpdateTrackRequest) {
+    let mut log = track.lock().unwrap();
+    if let Some(enabled) = patch.enabled { log.settings.enabled = enabled; }
+    if let Some(decimation) = patch.decimation {
+        log.settings.decimation = match decimation {
+            TrackDecimation::Distance { meters } => TrackDecimation::Distance { meters: meters.max(0.0) },
+            TrackDecimation::Time { seconds } => TrackDecimation::Time { seconds: seconds.max(0.0) },
+        };
+    }
+    if let Some(max_points) = patch.max_points {
+        log.settings.max_points = max_points.max(1);
+        trim(&mut log);
+    }
+}
+
+/// Salinan semua titik untuk diekspor, tanpa menahan lock selama format ditulis.
+pub fn points(track: &SharedTrack) -> Vec<TrackPoint> {
+    track.lock().unwrap().points.iter().cloned().collect()
+}
+
+pub fn clear(track: &SharedTrack) {
+    track.lock().unwrap().points.clear();
+}
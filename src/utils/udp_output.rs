@@ -0,0 +1,116 @@
+use crate::data::output_data::UdpOutputConfig;
+use crate::utils::net::OutputFrame;
+use socket2::SockRef;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, RwLock};
+use tokio::net::UdpSocket;
+
+pub type SharedUdpOutput = Arc<UdpOutput>;
+
+struct ActiveUdp {
+    config: UdpOutputConfig,
+    target: SocketAddr,
+    socket: UdpSocket,
+}
+
+/// Publisher UDP (unicast, broadcast, atau multicast) yang dipakai bersama oleh semua service.
+/// Socket dibuat ulang setiap kali konfigurasi berubah lewat `/api/outputs/udp`.
+#[derive(Default)]
+pub struct UdpOutput {
+    active: RwLock<Option<Arc<ActiveUdp>>>,
+}
+
+impl UdpOutput {
+    pub fn config(&self) -> Option<UdpOutputConfig> {
+        self.active.read().unwrap().as_ref().map(|a| a.config.clone())
+    }
+
+    /// Membuat socket baru sesuai config; config lama tetap dipakai jika gagal.
+    pub async fn configure(&self, config: UdpOutputConfig) -> Result<(), String> {
+        let ip: IpAddr = config
+            .destination
+            .parse()
+            .map_err(|_| format!("Invalid destination IP: {}", config.destination))?;
+        if config.port == 0 {
+            return Err("Invalid destination port: 0".to_string());
+        }
+        let target = SocketAddr::new(ip, config.port);
+
+        let bind_addr = if ip.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" };
+        let socket = UdpSocket::bind(bind_addr).await.map_err(|e| e.to_string())?;
+        socket.set_broadcast(true).map_err(|e| e.to_string())?;
+        // Socket IPv6 memakai hop limit, bukan IP_TTL
+        let sock = SockRef::from(&socket);
+        match ip {
+            IpAddr::V4(v4) if v4.is_multicast() => sock.set_multicast_ttl_v4(config.ttl),
+            IpAddr::V4(_) => sock.set_ttl(config.ttl),
+            IpAddr::V6(v6) if v6.is_multicast() => sock.set_multicast_hops_v6(config.ttl),
+            IpAddr::V6(_) => sock.set_unicast_hops_v6(config.ttl),
+        }
+        .map_err(|e| e.to_string())?;
+
+        println!("[UDP Output]: Sending {:?} to udp://{}", config.format, target);
+        *self.active.write().unwrap() = Some(Arc::new(ActiveUdp { config, target, socket }));
+        Ok(())
+    }
+
+    pub fn clear(&self) -> bool {
+        self.active.write().unwrap().take().is_some()
+    }
+
+    /// Kirim satu update sensor: satu datagram per baris (kalimat NMEA / frame N2K), atau satu datagram JSON.
+    pub async fn send(&self, frame: &OutputFrame) {
+        let Some(active) = self.active.read().unwrap().clone() else { return };
+        if !active.config.enabled {
+            return;
+        }
+
+        for datagram in frame.render(active.config.format) {
+            if let Err(e) = active.socket.send_to(datagram.as_bytes(), active.target).await {
+                eprintln!("[UDP Output]: Send error to {}: {}", active.target, e);
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::net::StreamFormat;
+
+    fn config(destination: &str, port: u16) -> UdpOutputConfig {
+        UdpOutputConfig { destination: destination.to_string(), port, ttl: 4, format: StreamFormat::Nmea, enabled: true }
+    }
+
+    #[tokio::test]
+    async fn port_zero_is_rejected() {
+        let output = UdpOutput::default();
+        assert!(output.configure(config("127.0.0.1", 0)).await.is_err());
+        assert!(output.config().is_none());
+        assert!(output.configure(config("127.0.0.1", 10110)).await.is_ok());
+        // Config lama tetap dipakai jika PATCH tidak valid
+        assert!(output.configure(config("127.0.0.1", 0)).await.is_err());
+        assert_eq!(output.config().map(|config| config.port), Some(10110));
+    }
+
+    #[tokio::test]
+    async fn hop_limit_is_set_per_address_family() {
+        let output = UdpOutput::default();
+        for destination in ["239.192.0.1", "::1", "ff02::1"] {
+            // Sandbox tanpa IPv6 tidak bisa membuat socket sama sekali
+            if destination.contains(':') && std::net::UdpSocket::bind("[::1]:0").is_err() {
+                continue;
+            }
+            output.configure(config(destination, 10110)).await.unwrap();
+            let active = output.active.read().unwrap().clone().unwrap();
+            let sock = SockRef::from(&active.socket);
+            let hops = match active.target.ip() {
+                IpAddr::V4(_) => sock.multicast_ttl_v4(),
+                IpAddr::V6(v6) if v6.is_multicast() => sock.multicast_hops_v6(),
+                IpAddr::V6(_) => sock.unicast_hops_v6(),
+            };
+            assert_eq!(hops.unwrap(), 4, "{}", destination);
+        }
+    }
+}
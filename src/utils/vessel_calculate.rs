@@ -0,0 +1,89 @@
+use crate::data::vessel_data::{Steering, VesselState};
+use crate::utils::navigation::angle_diff;
+use crate::utils::sim_clock;
+
+const EARTH_RADIUS: f64 = 6_371_000.0;
+const KNOTS_TO_MPS: f64 = 0.514444;
+/// Batas sudut kemudi (derajat)
+const MAX_RUDDER: f64 = 35.0;
+/// Kecepatan minimum untuk konstanta waktu Nomoto (m/s), mencegah T tak hingga saat diam
+const MIN_STEERAGE_SPEED: f64 = 0.5;
+/// Heading hold: laju belok (derajat/detik) per derajat selisih heading, dan batasnya
+const HEADING_GAIN: f64 = 0.5;
+const MAX_HEADING_RATE: f64 = 3.0;
+
+/// Vektor kecepatan di atas dasar (utara, timur) dalam knot:
+/// STW sepanjang heading + leeway, ditambah arus (set/drift).
+pub fn ground_vector(stw: f64, heading: f64, leeway: f64, current_set: f64, current_drift: f64) -> (f64, f64) {
+    let water = (heading + leeway).to_radians();
+    let current = current_set.to_radians();
+    (
+        stw * water.cos() + current_drift * current.cos(),
+        stw * water.sin() + current_drift * current.sin(),
+    )
+}
+
+/// SOG (knot) dan COG (derajat true). Saat hampir diam, COG mengikuti heading agar tidak melompat-lompat.
+pub fn ground_velocity(stw: f64, heading: f64, leeway: f64, current_set: f64, current_drift: f64) -> (f64, f64) {
+    let (north, east) = ground_vector(stw, heading, leeway, current_set, current_drift);
+    let sog = north.hypot(east);
+    let cog = if sog < 1e-6 { heading } else { east.atan2(north).to_degrees() };
+    (sog, cog.rem_euclid(360.0))
+}
+
+/// Posisi tujuan (great circle) dari posisi awal, arah (derajat), dan jarak (m).
+pub fn destination(latitude: f64, longitude: f64, bearing: f64, distance: f64) -> (f64, f64) {
+    let lat_rad = latitude.to_radians();
+    let lon_rad = longitude.to_radians();
+    let course_rad = bearing.to_radians();
+    let angular_distance = distance / EARTH_RADIUS;
+
+    let new_lat_rad = (lat_rad.sin() * angular_distance.cos()
+        + lat_rad.cos() * angular_distance.sin() * course_rad.cos())
+    .asin();
+
+    let new_lon_rad = lon_rad
+        + (course_rad.sin() * angular_distance.sin() * lat_rad.cos())
+            .atan2(angular_distance.cos() - lat_rad.sin() * new_lat_rad.sin());
+
+    (
+        new_lat_rad.to_degrees().clamp(-90.0, 90.0),
+        (new_lon_rad.to_degrees() + 180.0).rem_euclid(360.0) - 180.0,
+    )
+}
+
+/// Laju belok baru. Model Nomoto orde satu: T·ṙ + r = K·δ, dengan K = K'·U/L dan T = T'·L/U.
+fn next_rate_of_turn(state: &VesselState, dt_seconds: f64) -> f64 {
+    match state.steering {
+        Steering::TurnRate { rate } => rate,
+        Steering::Heading { heading } => {
+            (angle_diff(heading, state.heading) * HEADING_GAIN).clamp(-MAX_HEADING_RATE, MAX_HEADING_RATE)
+        }
+        Steering::Rudder { angle } => {
+            let speed = state.stw * KNOTS_TO_MPS;
+            let length = state.length.max(1.0);
+            let steady_rate = state.nomoto_k * speed / length * angle.clamp(-MAX_RUDDER, MAX_RUDDER);
+            let time_constant = state.nomoto_t * length / speed.max(MIN_STEERAGE_SPEED);
+            let alpha = 1.0 - (-dt_seconds / time_constant).exp();
+            state.rate_of_turn + (steady_rate - state.rate_of_turn) * alpha
+        }
+    }
+}
+
+/// Menghitung ulang SOG/COG setelah heading, STW, leeway, atau arus berubah.
+pub fn update_ground_velocity(state: &mut VesselState) {
+    (state.sog, state.cog) =
+        ground_velocity(state.stw, state.heading, state.leeway, state.current_set, state.current_drift);
+}
+
+/// Satu langkah integrasi: kemudi → laju belok → heading; heading + STW + arus → posisi.
+pub fn calculate_next_vessel_state(state: &mut VesselState, dt_ms: u64) {
+    let dt_seconds = dt_ms as f64 / 1000.0;
+    state.rate_of_turn = next_rate_of_turn(state, dt_seconds);
+    state.heading = (state.heading + state.rate_of_turn * dt_seconds).rem_euclid(360.0);
+    update_ground_velocity(state);
+
+    let distance = state.sog * KNOTS_TO_MPS * dt_seconds;
+    (state.latitude, state.longitude) = destination(state.latitude, state.longitude, state.cog, distance);
+    state.last_update = sim_clock::now();
+}